@@ -0,0 +1,162 @@
+//! `#[derive(Healable)]` for `meta_axiomatic_self_healer`, enabled there with the
+//! `derive` feature.
+//!
+//! Fields opt in with `#[healable]` and are healed under their field name:
+//!
+//! - `#[healable]` exposes a `String` field
+//! - `#[healable(with = "path")]` exposes a field of any type through a module with
+//!   `fn to_text(&T) -> String` and `fn from_text(String) -> Result<T, String>`
+//! - `#[healable(redact)]` exposes the field but keeps its text out of the reports
+//!   `monitor_and_heal_fields` returns; it combines with `with`
+//! - `#[healable(skip)]` marks a field as deliberately left out
+//!
+//! Fields without the attribute are left out too.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Path, Type};
+
+#[proc_macro_derive(Healable, attributes(healable))]
+pub fn derive_healable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// How one field is exposed
+struct Exposed {
+    ident: syn::Ident,
+    with: Option<Path>,
+    redact: bool,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            other => {
+                return Err(Error::new(
+                    other.span(),
+                    "Healable can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Healable can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut exposed = Vec::new();
+    for field in fields {
+        if let Some(field) = exposure(field)? {
+            exposed.push(field);
+        }
+    }
+
+    let names: Vec<String> = exposed.iter().map(|f| f.ident.to_string()).collect();
+    let reads = exposed.iter().zip(&names).map(|(field, name)| {
+        let ident = &field.ident;
+        match &field.with {
+            Some(with) => {
+                quote_spanned! { with.span()=> (#name.to_string(), #with::to_text(&self.#ident)) }
+            }
+            None => quote! { (#name.to_string(), ::std::clone::Clone::clone(&self.#ident)) },
+        }
+    });
+    let writes = exposed.iter().zip(&names).map(|(field, name)| {
+        let ident = &field.ident;
+        match &field.with {
+            Some(with) => {
+                let read = quote_spanned! { with.span()=> #with::from_text(value)? };
+                quote! { #name => { self.#ident = #read; } }
+            }
+            None => quote! { #name => { self.#ident = value; } },
+        }
+    });
+    let redacted: Vec<&String> =
+        exposed.iter().zip(&names).filter(|(f, _)| f.redact).map(|(_, n)| n).collect();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::meta_axiomatic_self_healer::Healable for #ident #ty_generics
+        #where_clause
+        {
+            fn fields(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                ::std::vec![#(#reads),*]
+            }
+
+            fn set_field(
+                &mut self,
+                path: &str,
+                value: ::std::string::String,
+            ) -> ::std::result::Result<(), ::std::string::String> {
+                match path {
+                    #(#writes)*
+                    _ => return ::std::result::Result::Err(::std::format!("no field '{}'", path)),
+                }
+                ::std::result::Result::Ok(())
+            }
+
+            fn redacted(&self, path: &str) -> bool {
+                [#(#redacted),*].contains(&path)
+            }
+        }
+    })
+}
+
+/// Read a field's `#[healable]` attribute; `None` when the field is left out
+fn exposure(field: &syn::Field) -> syn::Result<Option<Exposed>> {
+    let mut attrs = field.attrs.iter().filter(|a| a.path().is_ident("healable"));
+    let Some(attr) = attrs.next() else {
+        return Ok(None);
+    };
+    if let Some(extra) = attrs.next() {
+        return Err(Error::new(extra.span(), "duplicate #[healable] attribute"));
+    }
+
+    let (mut skip, mut redact, mut with) = (None, false, None);
+    if !matches!(attr.meta, syn::Meta::Path(_)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = Some(meta.path.span());
+            } else if meta.path.is_ident("redact") {
+                redact = true;
+            } else if meta.path.is_ident("with") {
+                let path: LitStr = meta.value()?.parse()?;
+                with = Some(path.parse::<Path>()?);
+            } else {
+                return Err(meta.error("expected `skip`, `redact` or `with = \"path\"`"));
+            }
+            Ok(())
+        })?;
+    }
+    if let Some(span) = skip {
+        if redact || with.is_some() {
+            return Err(Error::new(span, "`skip` can't be combined with `redact` or `with`"));
+        }
+        return Ok(None);
+    }
+    if with.is_none() && !is_string(&field.ty) {
+        return Err(Error::new(
+            field.ty.span(),
+            "#[healable] fields must be `String`; use #[healable(with = \"path\")] to \
+             convert other types",
+        ));
+    }
+    let ident = field.ident.clone().expect("named fields have names");
+    Ok(Some(Exposed { ident, with, redact }))
+}
+
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|s| s.ident == "String" && s.arguments.is_empty())
+        }
+        _ => false,
+    }
+}
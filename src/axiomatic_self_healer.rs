@@ -1,21 +1,92 @@
 // Adaptive Axiomatic Regularizer (AAR) and AxiomaticSelfHealer
 // A meta-learning framework for self-correcting AI systems
 
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io::{BufRead, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Lets `#[derive(Healable)]` name this crate from inside it too
+extern crate self as meta_axiomatic_self_healer;
+
+/// `#[derive(Healable)]` for structs whose `#[healable]` fields are healed in place
+#[cfg(feature = "derive")]
+pub use meta_axiomatic_self_healer_derive::Healable;
 
 /// Core axioms that guide the system's behavior
-#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[derive(Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axiom {
     Consistency,
     Completeness,
     Transparency,
     Safety,
     Fairness,
+    /// A domain-specific axiom, identified by name; see
+    /// `AdaptiveAxiomaticRegularizer::register_axiom`
+    Custom(String),
+}
+
+impl Axiom {
+    /// The built-in axioms; custom ones are never included
+    pub const ALL: [Axiom; 5] = [
+        Axiom::Consistency,
+        Axiom::Completeness,
+        Axiom::Transparency,
+        Axiom::Safety,
+        Axiom::Fairness,
+    ];
+
+    pub fn custom(name: impl Into<String>) -> Self {
+        Axiom::Custom(name.into())
+    }
+
+    /// The variant name for built-ins, the given name for custom axioms
+    pub fn name(&self) -> &str {
+        match self {
+            Axiom::Consistency => "Consistency",
+            Axiom::Completeness => "Completeness",
+            Axiom::Transparency => "Transparency",
+            Axiom::Safety => "Safety",
+            Axiom::Fairness => "Fairness",
+            Axiom::Custom(name) => name,
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Axiom::Custom(_))
+    }
+}
+
+impl FromStr for Axiom {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Consistency" => Ok(Axiom::Consistency),
+            "Completeness" => Ok(Axiom::Completeness),
+            "Transparency" => Ok(Axiom::Transparency),
+            "Safety" => Ok(Axiom::Safety),
+            "Fairness" => Ok(Axiom::Fairness),
+            // The `Debug` form, so names round-trip through snapshots and exports
+            other => other
+                .strip_prefix("Custom(\"")
+                .and_then(|rest| rest.strip_suffix("\")"))
+                .map(|name| Axiom::Custom(name.replace("\\\"", "\"").replace("\\\\", "\\")))
+                .ok_or_else(|| format!("unknown axiom '{}'", other)),
+        }
+    }
 }
 
 /// Violation severity levels
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Severity {
     Low,
     Medium,
@@ -23,272 +94,16098 @@ pub enum Severity {
     Critical,
 }
 
-/// Represents a detected axiom violation
-#[derive(Debug, Clone)]
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Low" => Ok(Severity::Low),
+            "Medium" => Ok(Severity::Medium),
+            "High" => Ok(Severity::High),
+            "Critical" => Ok(Severity::Critical),
+            other => Err(format!("unknown severity '{}'", other)),
+        }
+    }
+}
+
+/// Represents a detected axiom violation.
+///
+/// `Debug` shortens the context to `DEBUG_CONTEXT_CHARS`; see `debug_full`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Violation {
     pub axiom: Axiom,
     pub severity: Severity,
     pub context: String,
     pub timestamp: u64,
+    /// Free-form annotations: the `rule` that matched, the `span` of the match in
+    /// `context` as `start..end` byte offsets, and the `segment` index and `role` in
+    /// segmented mode
+    pub metadata: BTreeMap<String, String>,
 }
 
-/// Adaptive Axiomatic Regularizer - monitors and enforces axioms
-pub struct AdaptiveAxiomaticRegularizer {
-    axiom_weights: HashMap<Axiom, f64>,
-    violation_history: Arc<Mutex<Vec<Violation>>>,
-    learning_rate: f64,
-    threshold: f64,
+/// Characters of a violation's context shown by its `Debug` output; the middle of
+/// longer contexts is elided so raw user text doesn't end up wholesale in logs
+pub const DEBUG_CONTEXT_CHARS: usize = 64;
+
+impl Violation {
+    /// Serialize as one line of `schema::VIOLATION` JSON
+    pub fn to_json(&self) -> String {
+        import::violation_to_json(self)
+    }
+
+    /// Byte range of the match in `context`, if detection recorded one
+    pub fn span(&self) -> Option<Range<usize>> {
+        let (start, end) = self.metadata.get("span")?.split_once("..")?;
+        let span = start.parse().ok()?..end.parse().ok()?;
+        self.context.get(span.clone()).map(|_| span)
+    }
+
+    /// Parse a line written by `to_json`, upgrading older schema versions
+    pub fn from_json(line: &str) -> Result<Self, schema::ArtifactError> {
+        import::violation_from_json(line, &schema::Migrations::builtin())
+    }
+
+    /// `Debug` rendering with the whole context, for when it is genuinely wanted
+    pub fn debug_full(&self) -> impl fmt::Debug + '_ {
+        ContextDebug(self, usize::MAX)
+    }
 }
 
-impl AdaptiveAxiomaticRegularizer {
-    pub fn new() -> Self {
-        let mut axiom_weights = HashMap::new();
-        axiom_weights.insert(Axiom::Consistency, 1.0);
-        axiom_weights.insert(Axiom::Completeness, 1.0);
-        axiom_weights.insert(Axiom::Transparency, 1.0);
-        axiom_weights.insert(Axiom::Safety, 1.5);
-        axiom_weights.insert(Axiom::Fairness, 1.0);
+impl fmt::Debug for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ContextDebug(self, DEBUG_CONTEXT_CHARS).fmt(f)
+    }
+}
+
+/// Debug view of a value whose contexts are shortened to the given number of chars
+struct ContextDebug<'a, T>(&'a T, usize);
+
+impl fmt::Debug for ContextDebug<'_, Violation> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ContextDebug(v, max) = *self;
+        f.debug_struct("Violation")
+            .field("axiom", &v.axiom)
+            .field("severity", &v.severity)
+            .field("context", &elide_middle(&v.context, max))
+            .field("timestamp", &v.timestamp)
+            .field("metadata", &v.metadata)
+            .finish()
+    }
+}
 
+/// Source of timestamps (unix epoch millis) for recorded violations
+pub trait Clock: Send + Sync {
+    fn now_millis(&self) -> u64;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// A clock that only moves when told to; clones share the same time
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    millis: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new(start_millis: u64) -> Self {
         Self {
-            axiom_weights,
-            violation_history: Arc::new(Mutex::new(Vec::new())),
-            learning_rate: 0.01,
-            threshold: 0.5,
+            millis: Arc::new(AtomicU64::new(start_millis)),
         }
     }
 
-    /// Detect violations in the given context
-    pub fn detect_violations(&self, context: &str) -> Vec<Violation> {
-        let mut violations = Vec::new();
+    pub fn advance(&self, by: Duration) {
+        self.millis.fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+    }
 
-        // Example detection logic (simplified)
-        if context.contains("inconsistent") {
-            violations.push(Violation {
-                axiom: Axiom::Consistency,
-                severity: Severity::High,
-                context: context.to_string(),
-                timestamp: Self::current_timestamp(),
-            });
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+/// Event counts grouped into fixed-width time buckets, oldest first
+#[derive(Debug, Clone)]
+pub struct TrendBuckets {
+    width_ms: u64,
+    retain: usize,
+    buckets: VecDeque<(u64, usize)>,
+}
+
+impl TrendBuckets {
+    /// Buckets of `width`, keeping at most `retain` of the most recent ones
+    pub fn new(width: Duration, retain: usize) -> Self {
+        Self {
+            width_ms: (width.as_millis() as u64).max(1),
+            retain: retain.max(1),
+            buckets: VecDeque::new(),
         }
+    }
 
-        if context.contains("unsafe") {
-            violations.push(Violation {
-                axiom: Axiom::Safety,
-                severity: Severity::Critical,
-                context: context.to_string(),
-                timestamp: Self::current_timestamp(),
-            });
+    pub fn width(&self) -> Duration {
+        Duration::from_millis(self.width_ms)
+    }
+
+    /// Add `count` events at `timestamp`
+    pub fn record(&mut self, timestamp: u64, count: usize) {
+        let start = timestamp - timestamp % self.width_ms;
+        match self.buckets.iter_mut().rev().find(|(s, _)| *s == start) {
+            Some((_, n)) => *n += count,
+            None => {
+                let at = self
+                    .buckets
+                    .iter()
+                    .position(|(s, _)| *s > start)
+                    .unwrap_or(self.buckets.len());
+                self.buckets.insert(at, (start, count));
+            }
+        }
+        while self.buckets.len() > self.retain {
+            self.buckets.pop_front();
         }
+    }
 
-        violations
+    /// Events in buckets that overlap `[now - window, now]`
+    pub fn count_within(&self, now: u64, window: Duration) -> usize {
+        let since = now.saturating_sub(window.as_millis() as u64);
+        self.buckets
+            .iter()
+            .filter(|(start, _)| start + self.width_ms > since && *start <= now)
+            .map(|(_, n)| n)
+            .sum()
     }
 
-    /// Calculate regularization penalty for violations
-    pub fn calculate_penalty(&self, violations: &[Violation]) -> f64 {
-        violations.iter().map(|v| {
-            let weight = self.axiom_weights.get(&v.axiom).unwrap_or(&1.0);
-            let severity_multiplier = match v.severity {
-                Severity::Low => 1.0,
-                Severity::Medium => 2.0,
-                Severity::High => 4.0,
-                Severity::Critical => 8.0,
-            };
-            weight * severity_multiplier
-        }).sum()
+    /// `(bucket start, count)` pairs, oldest first
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        self.buckets.iter().copied()
     }
 
-    /// Update axiom weights based on feedback
-    pub fn update_weights(&mut self, axiom: Axiom, feedback: f64) {
-        if let Some(weight) = self.axiom_weights.get_mut(&axiom) {
-            *weight += self.learning_rate * feedback;
-            *weight = weight.max(0.1).min(10.0); // Clamp between 0.1 and 10.0
+    /// Counts of every complete bucket from the oldest retained one up to the bucket
+    /// containing `now` (exclusive), with empty buckets filled in as zero
+    pub fn complete_counts(&self, now: u64) -> Vec<usize> {
+        let current = now - now % self.width_ms;
+        let Some(&(first, _)) = self.buckets.front() else {
+            return Vec::new();
+        };
+        let mut counts = vec![0; (current.saturating_sub(first) / self.width_ms) as usize];
+        for &(start, n) in &self.buckets {
+            if start < current {
+                counts[((start - first) / self.width_ms) as usize] = n;
+            }
         }
+        let keep = counts.len().saturating_sub(self.retain);
+        counts.split_off(keep)
     }
+}
 
-    /// Record a violation in history
-    pub fn record_violation(&self, violation: Violation) {
-        if let Ok(mut history) = self.violation_history.lock() {
-            history.push(violation);
+/// Which detection pass a rule belongs to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DetectionTier {
+    /// Cheap checks that run on every context
+    #[default]
+    Fast,
+    /// Expensive checks that run when the `TieringPolicy` says so
+    Thorough,
+}
+
+/// When the thorough detection pass runs
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TieringPolicy {
+    /// Run every rule on every context
+    #[default]
+    AlwaysBoth,
+    /// Run thorough rules only when fast rules found something
+    EscalateOnHit,
+    /// As `EscalateOnHit`, and also on a `rate` fraction of contexts the fast pass
+    /// found clean, to estimate what it misses; drawn from a generator seeded with
+    /// `seed`. Only observed contexts (`detect_violations`) are sampled.
+    EscalateOnHitPlusSample { rate: f64, seed: u64 },
+}
+
+/// Which detection passes ran on a context
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TiersRun {
+    pub fast: bool,
+    pub thorough: bool,
+    /// The thorough pass ran as a sample of clean-looking traffic
+    pub sampled: bool,
+}
+
+impl TiersRun {
+    fn union(self, other: TiersRun) -> TiersRun {
+        TiersRun {
+            fast: self.fast || other.fast,
+            thorough: self.thorough || other.thorough,
+            sampled: self.sampled || other.sampled,
         }
     }
+}
 
-    fn current_timestamp() -> u64 {
-        // Simplified timestamp (in real implementation, use proper time crate)
-        0
+/// Detection pass counts for observed contexts, for estimating the fast pass's
+/// miss rate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TieringStats {
+    pub fast_passes: u64,
+    pub thorough_passes: u64,
+    /// Thorough passes run because the fast pass found something
+    pub escalated_on_hit: u64,
+    /// Thorough passes run as a sample of contexts the fast pass found clean
+    pub sampled: u64,
+    /// Sampled contexts where the thorough pass found something the fast pass missed
+    pub fast_misses: u64,
+}
+
+impl TieringStats {
+    /// Fraction of sampled clean-looking contexts that were not clean; `None`
+    /// before any sample
+    pub fn miss_rate(&self) -> Option<f64> {
+        (self.sampled > 0).then(|| self.fast_misses as f64 / self.sampled as f64)
     }
 }
 
-/// Self-healing system that automatically corrects violations
-pub struct AxiomaticSelfHealer {
-    regularizer: AdaptiveAxiomaticRegularizer,
-    correction_strategies: HashMap<Axiom, Vec<CorrectionStrategy>>,
-    auto_heal: bool,
+/// A rule that flags an axiom violation when its pattern appears in a context: as a
+/// substring, or as a regular expression for rules made with `DetectionRule::regex`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectionRule {
+    pub pattern: String,
+    pub axiom: Axiom,
+    /// Severity of matches, unless `severity_expr` decides it per match
+    pub severity: Severity,
+    pub severity_expr: Option<severity_expr::SeverityExpr>,
+    pub tier: DetectionTier,
+    /// Compiled `pattern`, for regex rules
+    #[cfg(feature = "regex")]
+    regex: Option<RuleRegex>,
 }
 
+/// Compares by source, as `Regex` itself can't be compared
+#[cfg(feature = "regex")]
 #[derive(Debug, Clone)]
-pub enum CorrectionStrategy {
-    Rollback,
-    Recompute,
-    Interpolate,
-    QueryUser,
-    ApplyDefault,
-}
+struct RuleRegex(regex::Regex);
 
-impl AxiomaticSelfHealer {
-    pub fn new(regularizer: AdaptiveAxiomaticRegularizer) -> Self {
-        let mut correction_strategies = HashMap::new();
-        
-        correction_strategies.insert(
-            Axiom::Consistency,
-            vec![CorrectionStrategy::Rollback, CorrectionStrategy::Recompute],
-        );
-        correction_strategies.insert(
-            Axiom::Safety,
-            vec![CorrectionStrategy::Rollback, CorrectionStrategy::QueryUser],
-        );
-        correction_strategies.insert(
-            Axiom::Completeness,
-            vec![CorrectionStrategy::Interpolate, CorrectionStrategy::ApplyDefault],
-        );
+#[cfg(feature = "regex")]
+impl PartialEq for RuleRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
 
+impl DetectionRule {
+    pub fn new(pattern: impl Into<String>, axiom: Axiom, severity: Severity) -> Self {
         Self {
-            regularizer,
-            correction_strategies,
-            auto_heal: true,
+            pattern: pattern.into(),
+            axiom,
+            severity,
+            severity_expr: None,
+            tier: DetectionTier::Fast,
+            #[cfg(feature = "regex")]
+            regex: None,
         }
     }
 
-    /// Monitor and heal violations
-    pub fn monitor_and_heal(&mut self, context: &str) -> Result<String, String> {
-        let violations = self.regularizer.detect_violations(context);
+    /// A rule flagging the first match of a regular expression
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str, axiom: Axiom, severity: Severity) -> Result<Self, regex::Error> {
+        let regex = regex::Regex::new(pattern)?;
+        Ok(Self { regex: Some(RuleRegex(regex)), ..Self::new(pattern, axiom, severity) })
+    }
+
+    /// Whether `pattern` is matched as a regular expression
+    pub fn is_regex(&self) -> bool {
+        #[cfg(feature = "regex")]
+        return self.regex.is_some();
+        #[cfg(not(feature = "regex"))]
+        false
+    }
 
-        if violations.is_empty() {
-            return Ok(context.to_string());
+    /// Span of the first match in `context`
+    fn find(&self, context: &str) -> Option<Range<usize>> {
+        #[cfg(feature = "regex")]
+        if let Some(RuleRegex(regex)) = &self.regex {
+            return regex.find(context).map(|m| m.range());
         }
+        let start = context.find(self.pattern.as_str())?;
+        Some(start..start + self.pattern.len())
+    }
 
-        let penalty = self.regularizer.calculate_penalty(&violations);
-        
-        if penalty > self.regularizer.threshold && self.auto_heal {
-            self.heal_violations(&violations, context)
-        } else {
-            // Record violations but don't heal
-            for v in violations {
-                self.regularizer.record_violation(v);
-            }
-            Ok(context.to_string())
+    fn match_count(&self, context: &str) -> usize {
+        #[cfg(feature = "regex")]
+        if let Some(RuleRegex(regex)) = &self.regex {
+            return regex.find_iter(context).count();
         }
+        context.matches(self.pattern.as_str()).count()
     }
 
-    /// Apply correction strategies to heal violations
-    fn heal_violations(&mut self, violations: &[Violation], context: &str) -> Result<String, String> {
-        let mut healed_context = context.to_string();
+    /// Place the rule in another detection pass
+    pub fn with_tier(mut self, tier: DetectionTier) -> Self {
+        self.tier = tier;
+        self
+    }
 
-        for violation in violations {
-            if let Some(strategies) = self.correction_strategies.get(&violation.axiom) {
-                for strategy in strategies {
-                    match self.apply_strategy(strategy, &healed_context, violation) {
-                        Ok(corrected) => {
-                            healed_context = corrected;
-                            break;
-                        }
-                        Err(_) => continue,
-                    }
-                }
-            }
-            self.regularizer.record_violation(violation.clone());
-        }
+    /// Decide each match's severity with an expression; see `severity_expr`
+    pub fn with_severity_expr(mut self, source: &str) -> Result<Self, severity_expr::ParseError> {
+        self.severity_expr = Some(severity_expr::SeverityExpr::parse(source)?);
+        Ok(self)
+    }
 
-        Ok(healed_context)
+    /// Every severity the rule's matches can have
+    pub fn severities(&self) -> BTreeSet<Severity> {
+        match &self.severity_expr {
+            Some(expr) => expr.outcomes(),
+            None => BTreeSet::from([self.severity]),
+        }
     }
 
-    /// Apply a specific correction strategy
-    fn apply_strategy(
+    fn severity_of(
         &self,
-        strategy: &CorrectionStrategy,
         context: &str,
-        violation: &Violation,
-    ) -> Result<String, String> {
-        match strategy {
-            CorrectionStrategy::Rollback => {
-                Ok(format!("[ROLLED_BACK] {}", context))
-            }
-            CorrectionStrategy::Recompute => {
-                Ok(context.replace("inconsistent", "consistent"))
-            }
-            CorrectionStrategy::Interpolate => {
-                Ok(format!("{} [INTERPOLATED]", context))
-            }
-            CorrectionStrategy::QueryUser => {
-                Err("User intervention required".to_string())
-            }
-            CorrectionStrategy::ApplyDefault => {
-                Ok(format!("{} [DEFAULT_APPLIED]", context))
+        span: Range<usize>,
+        metadata: &BTreeMap<String, String>,
+    ) -> Severity {
+        let Some(expr) = &self.severity_expr else {
+            return self.severity;
+        };
+        expr.evaluate(&severity_expr::MatchVars {
+            match_count: self.match_count(context),
+            position_ratio: span.start as f64 / context.len().max(1) as f64,
+            context_len: context.len(),
+            match_len: span.len(),
+            metadata: metadata.clone(),
+        })
+    }
+
+    /// Name used to identify the rule in coverage and reports
+    pub fn name(&self) -> String {
+        format!("rule:{}", self.pattern)
+    }
+
+    /// Axioms this rule is capable of producing
+    pub fn covers(&self) -> &[Axiom] {
+        std::slice::from_ref(&self.axiom)
+    }
+}
+
+/// What `axiom_rules!` expands to; not part of the public API
+#[doc(hidden)]
+pub mod macro_support {
+    use super::{Axiom, DetectionRule, Severity};
+
+    /// The axiom names `axiom_rules!` accepts
+    #[allow(non_upper_case_globals)]
+    pub mod axioms {
+        use super::Axiom;
+
+        pub const consistency: Axiom = Axiom::Consistency;
+        pub const completeness: Axiom = Axiom::Completeness;
+        pub const transparency: Axiom = Axiom::Transparency;
+        pub const safety: Axiom = Axiom::Safety;
+        pub const fairness: Axiom = Axiom::Fairness;
+    }
+
+    /// The severity names `axiom_rules!` accepts
+    #[allow(non_upper_case_globals)]
+    pub mod severities {
+        use super::Severity;
+
+        pub const low: Severity = Severity::Low;
+        pub const medium: Severity = Severity::Medium;
+        pub const high: Severity = Severity::High;
+        pub const critical: Severity = Severity::Critical;
+    }
+
+    /// Fail the build, through a const panic, when an `axiom_rules!` pattern can't be
+    /// used
+    pub const fn check_pattern(pattern: &str) {
+        if let Some(problem) = pattern_problem(pattern) {
+            panic!("{}", problem);
+        }
+    }
+
+    /// What is wrong with an `axiom_rules!` pattern. With the `regex` feature this
+    /// catches malformed syntax; semantic errors, such as an unknown class name, still
+    /// panic when the rules are built.
+    pub const fn pattern_problem(pattern: &str) -> Option<&'static str> {
+        let bytes = pattern.as_bytes();
+        if bytes.is_empty() {
+            return Some("detection rule pattern is empty");
+        }
+        #[cfg(feature = "regex")]
+        return regex_problem(bytes);
+        #[cfg(not(feature = "regex"))]
+        {
+            let mut i = 0;
+            while i < bytes.len() {
+                if matches!(bytes[i], b'\\' | b'.' | b'+' | b'*' | b'?' | b'(' | b')' | b'[')
+                    || matches!(bytes[i], b']' | b'{' | b'}' | b'|' | b'^' | b'$')
+                {
+                    return Some(
+                        "detection rule pattern uses regex syntax; enable the `regex` feature",
+                    );
+                }
+                i += 1;
             }
+            None
         }
     }
 
-    /// Get violation statistics
-    pub fn get_statistics(&self) -> ViolationStatistics {
-        if let Ok(history) = self.regularizer.violation_history.lock() {
-            let total = history.len();
-            let mut by_axiom = HashMap::new();
-            let mut by_severity = HashMap::new();
+    #[cfg(feature = "regex")]
+    const fn regex_problem(bytes: &[u8]) -> Option<&'static str> {
+        let mut depth = 0usize;
+        // Whether the item before can take a quantifier
+        let mut repeatable = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => {
+                    if i + 1 == bytes.len() {
+                        return Some("detection rule pattern ends in a lone backslash");
+                    }
+                    i += 1;
+                    // `\p{..}` and `\x{..}` carry their braces with them
+                    let braced = matches!(bytes[i], b'p' | b'P' | b'x' | b'u' | b'U');
+                    if braced && i + 1 < bytes.len() && bytes[i + 1] == b'{' {
+                        while i < bytes.len() && bytes[i] != b'}' {
+                            i += 1;
+                        }
+                        if i == bytes.len() {
+                            return Some("detection rule pattern has an unclosed escape `{`");
+                        }
+                    }
+                    repeatable = true;
+                }
+                b'(' => {
+                    depth += 1;
+                    repeatable = false;
+                    // `(?` opens flags or a non-capturing group rather than quantifying
+                    if i + 1 < bytes.len() && bytes[i + 1] == b'?' {
+                        i += 1;
+                    }
+                }
+                b')' => {
+                    if depth == 0 {
+                        return Some("detection rule pattern has an unopened `)`");
+                    }
+                    depth -= 1;
+                    repeatable = true;
+                }
+                b'|' => repeatable = false,
+                b'*' | b'+' | b'?' | b'{' => {
+                    if !repeatable {
+                        return Some(
+                            "detection rule pattern has a quantifier with nothing to repeat",
+                        );
+                    }
+                    if bytes[i] == b'{' {
+                        match counted_repetition_end(bytes, i) {
+                            Some(end) => i = end,
+                            None => {
+                                return Some(
+                                    "detection rule pattern has a malformed counted repetition",
+                                )
+                            }
+                        }
+                    }
+                    // A `?` after a quantifier makes it lazy
+                    if i + 1 < bytes.len() && bytes[i + 1] == b'?' {
+                        i += 1;
+                    }
+                    repeatable = false;
+                }
+                b'[' => match class_end(bytes, i) {
+                    Some(end) => {
+                        i = end;
+                        repeatable = true;
+                    }
+                    None => return Some("detection rule pattern has an unclosed `[`"),
+                },
+                _ => repeatable = true,
+            }
+            i += 1;
+        }
+        if depth != 0 {
+            return Some("detection rule pattern has an unclosed `(`");
+        }
+        None
+    }
 
-            for violation in history.iter() {
-                *by_axiom.entry(violation.axiom.clone()).or_insert(0) += 1;
-                *by_severity.entry(violation.severity).or_insert(0) += 1;
+    /// Index of the `}` closing `{n}`, `{n,}` or `{n,m}` at `open`
+    #[cfg(feature = "regex")]
+    const fn counted_repetition_end(bytes: &[u8], open: usize) -> Option<usize> {
+        let mut i = open + 1;
+        let mut digits = 0;
+        let mut comma = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'0'..=b'9' => digits += 1,
+                b',' if !comma && digits > 0 => comma = true,
+                b'}' if digits > 0 => return Some(i),
+                _ => return None,
             }
+            i += 1;
+        }
+        None
+    }
 
-            ViolationStatistics {
-                total,
-                by_axiom,
-                by_severity,
+    /// Index of the `]` closing the class at `open`, which may nest
+    #[cfg(feature = "regex")]
+    const fn class_end(bytes: &[u8], open: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut i = open;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'[' => {
+                    depth += 1;
+                    // A `]` straight after the opening bracket (or its `^`) is literal
+                    if i + 1 < bytes.len() && bytes[i + 1] == b'^' {
+                        i += 1;
+                    }
+                    if i + 1 < bytes.len() && bytes[i + 1] == b']' {
+                        i += 1;
+                    }
+                }
+                b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
             }
-        } else {
-            ViolationStatistics::default()
+            i += 1;
         }
+        None
+    }
+
+    /// A rule for a pattern `check_pattern` accepted
+    pub fn rule(pattern: &str, axiom: Axiom, severity: Severity) -> DetectionRule {
+        #[cfg(feature = "regex")]
+        return DetectionRule::regex(pattern, axiom, severity)
+            .unwrap_or_else(|err| panic!("axiom_rules! pattern {:?}: {}", pattern, err));
+        #[cfg(not(feature = "regex"))]
+        DetectionRule::new(pattern, axiom, severity)
     }
 }
 
-#[derive(Debug, Default)]
-pub struct ViolationStatistics {
-    pub total: usize,
-    pub by_axiom: HashMap<Axiom, usize>,
-    pub by_severity: HashMap<Severity, usize>,
+/// Declare detection rules as plain data, next to the domain code that owns them.
+///
+/// Each rule is `<axiom> <severity>: "<pattern>"` with lowercase names, and the rules
+/// expand to a `Vec<DetectionRule>` to hand to `add_rule`; nothing is registered
+/// globally. Prefix the list with `fn name` (optionally `pub`) to define a function
+/// returning the rules instead of an expression:
+///
+/// `axiom_rules! { pub fn key_rules { safety critical: r"leak(ed)?\s+key" } }`
+///
+/// With the `regex` feature every pattern is a regular expression, and malformed
+/// syntax fails the build. Without it patterns are substrings, and any regex
+/// metacharacter fails the build rather than being matched literally.
+///
+/// # Panics
+///
+/// Only the syntax of a regex pattern is checked at compile time. A pattern that is
+/// well formed but still invalid, such as one naming an unknown class like
+/// `\p{Klingon}` or using an unknown escape like `\q`, panics when the rules are
+/// built.
+///
+/// Misspelled axiom or severity names are rejected at compile time with an error
+/// pointing at the offending name, and empty patterns are rejected too, since an
+/// empty pattern would flag every context.
+#[macro_export]
+macro_rules! axiom_rules {
+    // Names resolve in modules holding only the lowercase ones, so a misspelled or
+    // capitalized name fails with an error pointing at that token
+    (@axiom $name:ident) => { $crate::macro_support::axioms::$name };
+    (@severity $name:ident) => { $crate::macro_support::severities::$name };
+    ($vis:vis fn $name:ident { $($rules:tt)* }) => {
+        $vis fn $name() -> ::std::vec::Vec<$crate::DetectionRule> {
+            $crate::axiom_rules!($($rules)*)
+        }
+    };
+    ($($axiom:ident $severity:ident : $pattern:literal),* $(,)?) => {
+        ::std::vec![$({
+            const _: () = $crate::macro_support::check_pattern($pattern);
+            $crate::macro_support::rule(
+                $pattern,
+                $crate::axiom_rules!(@axiom $axiom),
+                $crate::axiom_rules!(@severity $severity),
+            )
+        }),*]
+    };
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A pluggable check run by `detect_violations` alongside the substring rules.
+///
+/// The regularizer fills in each returned violation's context and timestamp, and its
+/// `rule` metadata with the detector's name unless the detector set one, so a
+/// detector only has to decide the axiom and severity and, where it can, record a
+/// `span`. Violations of disabled axioms are dropped.
+pub trait ViolationDetector: Send + Sync {
+    /// Identifies the detector for `remove_detector` and `disable_detector`
+    fn name(&self) -> &str;
 
-    #[test]
-    fn test_violation_detection() {
-        let aar = AdaptiveAxiomaticRegularizer::new();
-        let violations = aar.detect_violations("This is an inconsistent result");
-        assert!(!violations.is_empty());
-    }
+    /// Axioms the detector can produce
+    fn covers(&self) -> Vec<Axiom>;
 
-    #[test]
-    fn test_self_healing() {
-        let aar = AdaptiveAxiomaticRegularizer::new();
-        let mut healer = AxiomaticSelfHealer::new(aar);
-        
-        let result = healer.monitor_and_heal("This is inconsistent");
-        assert!(result.is_ok());
+    /// Violations in the context's text. Detection is keyless like the rest of the
+    /// regularizer; document keys only matter to the healer's `ContextTracker`
+    fn detect(&self, context: &str) -> Vec<Violation>;
+
+    fn tier(&self) -> DetectionTier {
+        DetectionTier::Fast
     }
 
-    #[test]
+    /// Detectors run in descending priority, so the first of several identical hits
+    /// (and its metadata) comes from the highest; rules count as 0
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+/// A violation of `axiom` for a detector to return, with `span` recorded if given
+fn detected(axiom: Axiom, severity: Severity, span: Option<Range<usize>>) -> Violation {
+    let mut metadata = BTreeMap::new();
+    if let Some(span) = span {
+        metadata.insert("span".to_string(), format!("{}..{}", span.start, span.end));
+    }
+    Violation {
+        axiom,
+        severity,
+        context: String::new(),
+        timestamp: 0,
+        metadata,
+    }
+}
+
+/// Flags each listed keyword that appears in a context, e.g. loaded terms for
+/// `Axiom::Fairness`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordDetector {
+    name: String,
+    axiom: Axiom,
+    severity: Severity,
+    keywords: Vec<String>,
+    ignore_case: bool,
+}
+
+impl KeywordDetector {
+    pub fn new(
+        name: impl Into<String>,
+        axiom: Axiom,
+        severity: Severity,
+        keywords: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            axiom,
+            severity,
+            keywords: keywords.into_iter().map(Into::into).filter(|k| !k.is_empty()).collect(),
+            ignore_case: false,
+        }
+    }
+
+    /// Match keywords regardless of ASCII case
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+}
+
+impl ViolationDetector for KeywordDetector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn covers(&self) -> Vec<Axiom> {
+        vec![self.axiom.clone()]
+    }
+
+    fn detect(&self, context: &str) -> Vec<Violation> {
+        // ASCII lowercasing keeps byte offsets, so spans stay valid in `context`
+        let haystack = match self.ignore_case {
+            true => context.to_ascii_lowercase(),
+            false => context.to_string(),
+        };
+        self.keywords
+            .iter()
+            .filter_map(|keyword| {
+                let needle = match self.ignore_case {
+                    true => keyword.to_ascii_lowercase(),
+                    false => keyword.clone(),
+                };
+                let start = haystack.find(&needle)?;
+                let mut violation =
+                    detected(self.axiom.clone(), self.severity, Some(start..start + needle.len()));
+                violation.metadata.insert("keyword".to_string(), keyword.clone());
+                Some(violation)
+            })
+            .collect()
+    }
+}
+
+/// Flags contexts too short (or too long) to be complete answers: an
+/// `Axiom::Completeness` violation when the trimmed context has fewer than
+/// `min_chars` characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthDetector {
+    name: String,
+    min_chars: usize,
+    max_chars: Option<usize>,
+    severity: Severity,
+}
+
+impl LengthDetector {
+    pub fn new(name: impl Into<String>, min_chars: usize) -> Self {
+        Self { name: name.into(), min_chars, max_chars: None, severity: Severity::Medium }
+    }
+
+    /// Also flag contexts with more than `max_chars` characters
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = Some(max_chars);
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl ViolationDetector for LengthDetector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn covers(&self) -> Vec<Axiom> {
+        vec![Axiom::Completeness]
+    }
+
+    fn detect(&self, context: &str) -> Vec<Violation> {
+        let chars = context.trim().chars().count();
+        let problem = if chars < self.min_chars {
+            format!("{} chars, below {}", chars, self.min_chars)
+        } else if self.max_chars.is_some_and(|max| chars > max) {
+            format!("{} chars, above {}", chars, self.max_chars.unwrap_or_default())
+        } else {
+            return Vec::new();
+        };
+        let mut violation = detected(Axiom::Completeness, self.severity, None);
+        violation.metadata.insert("length".to_string(), problem);
+        vec![violation]
+    }
+}
+
+/// Flags the first match of a regular expression; a thorough-tier detector unless
+/// placed otherwise
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct RegexDetector {
+    name: String,
+    regex: regex::Regex,
+    axiom: Axiom,
+    severity: Severity,
+    tier: DetectionTier,
+}
+
+#[cfg(feature = "regex")]
+impl RegexDetector {
+    pub fn new(
+        name: impl Into<String>,
+        pattern: &str,
+        axiom: Axiom,
+        severity: Severity,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            name: name.into(),
+            regex: regex::Regex::new(pattern)?,
+            axiom,
+            severity,
+            tier: DetectionTier::Thorough,
+        })
+    }
+
+    pub fn with_tier(mut self, tier: DetectionTier) -> Self {
+        self.tier = tier;
+        self
+    }
+}
+
+#[cfg(feature = "regex")]
+impl ViolationDetector for RegexDetector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn covers(&self) -> Vec<Axiom> {
+        vec![self.axiom.clone()]
+    }
+
+    fn detect(&self, context: &str) -> Vec<Violation> {
+        self.regex
+            .find(context)
+            .map(|m| detected(self.axiom.clone(), self.severity, Some(m.start()..m.end())))
+            .into_iter()
+            .collect()
+    }
+
+    fn tier(&self) -> DetectionTier {
+        self.tier
+    }
+}
+
+/// The check a `FnDetector` runs
+pub type DetectFn = dyn Fn(&str) -> Vec<Violation> + Send + Sync;
+
+/// A detector made from a closure, for checks too small to deserve their own type
+pub struct FnDetector {
+    name: String,
+    covers: Vec<Axiom>,
+    detect: Box<DetectFn>,
+    tier: DetectionTier,
+    priority: i32,
+}
+
+impl FnDetector {
+    /// `detect` may only return violations of the `covers` axioms
+    pub fn new(
+        name: impl Into<String>,
+        covers: impl IntoIterator<Item = Axiom>,
+        detect: impl Fn(&str) -> Vec<Violation> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            covers: covers.into_iter().collect(),
+            detect: Box::new(detect),
+            tier: DetectionTier::Fast,
+            priority: 0,
+        }
+    }
+
+    pub fn with_tier(mut self, tier: DetectionTier) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl fmt::Debug for FnDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnDetector")
+            .field("name", &self.name)
+            .field("covers", &self.covers)
+            .field("tier", &self.tier)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+impl ViolationDetector for FnDetector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn covers(&self) -> Vec<Axiom> {
+        self.covers.clone()
+    }
+
+    fn detect(&self, context: &str) -> Vec<Violation> {
+        (self.detect)(context)
+    }
+
+    fn tier(&self) -> DetectionTier {
+        self.tier
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+/// A rule or registered detector, as `scan_tier` runs it
+enum Scanner<'a> {
+    Rule(&'a DetectionRule),
+    Detector(&'a dyn ViolationDetector),
+}
+
+/// Violations kept by a regularizer by default; see `with_history_capacity`
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100_000;
+
+/// Ring buffer of recorded violations with counts maintained as entries come and go,
+/// so the counts always describe exactly what is still buffered
+#[derive(Debug)]
+struct ViolationHistory {
+    entries: VecDeque<Violation>,
+    capacity: usize,
+    counts: HashMap<(Axiom, Severity), usize>,
+    evicted: u64,
+}
+
+impl ViolationHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            counts: HashMap::new(),
+            evicted: 0,
+        }
+    }
+
+    fn push(&mut self, violation: Violation) {
+        if self.entries.len() == self.capacity {
+            self.evict_oldest();
+        }
+        *self.counts.entry((violation.axiom.clone(), violation.severity)).or_insert(0) += 1;
+        self.entries.push_back(violation);
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some(oldest) = self.entries.pop_front() else {
+            return;
+        };
+        let key = (oldest.axiom, oldest.severity);
+        if let Some(count) = self.counts.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&key);
+            }
+        }
+        self.evicted += 1;
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.entries.len() > self.capacity {
+            self.evict_oldest();
+        }
+    }
+
+    /// Replace the contents, keeping the newest entries that fit
+    fn replace(&mut self, violations: Vec<Violation>) {
+        self.entries.clear();
+        self.counts.clear();
+        for violation in violations {
+            self.push(violation);
+        }
+    }
+
+    fn take(&mut self) -> Vec<Violation> {
+        self.counts.clear();
+        std::mem::take(&mut self.entries).into()
+    }
+
+    fn to_vec(&self) -> Vec<Violation> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// Totals for the axioms `include` accepts, from the counters alone
+    fn statistics(&self, include: impl Fn(&Axiom) -> bool) -> ViolationStatistics {
+        let mut statistics = ViolationStatistics::default();
+        for ((axiom, severity), count) in self.counts.iter().filter(|((a, _), _)| include(a)) {
+            statistics.total += count;
+            *statistics.by_axiom.entry(axiom.clone()).or_insert(0) += count;
+            *statistics.by_severity.entry(*severity).or_insert(0) += count;
+        }
+        statistics
+    }
+}
+
+/// Penalty multiplier of each severity level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeverityMultipliers {
+    pub low: f64,
+    pub medium: f64,
+    pub high: f64,
+    pub critical: f64,
+}
+
+impl SeverityMultipliers {
+    pub fn get(&self, severity: Severity) -> f64 {
+        match severity {
+            Severity::Low => self.low,
+            Severity::Medium => self.medium,
+            Severity::High => self.high,
+            Severity::Critical => self.critical,
+        }
+    }
+}
+
+impl Default for SeverityMultipliers {
+    fn default() -> Self {
+        Self { low: 1.0, medium: 2.0, high: 4.0, critical: 8.0 }
+    }
+}
+
+/// A custom axiom with everything it brings: its starting weight, how its
+/// severities are scored and how its violations are corrected
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxiomDefinition {
+    pub name: String,
+    pub weight: f64,
+    /// Replace the regularizer-wide multipliers for this axiom's violations
+    pub severity_multipliers: Option<SeverityMultipliers>,
+    pub strategies: Vec<CorrectionStrategy>,
+}
+
+impl AxiomDefinition {
+    pub fn new(name: impl Into<String>, weight: f64) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            severity_multipliers: None,
+            strategies: Vec::new(),
+        }
+    }
+
+    pub fn with_severity_multipliers(mut self, multipliers: SeverityMultipliers) -> Self {
+        self.severity_multipliers = Some(multipliers);
+        self
+    }
+
+    pub fn with_strategies(mut self, strategies: Vec<CorrectionStrategy>) -> Self {
+        self.strategies = strategies;
+        self
+    }
+}
+
+/// How `AdaptiveAxiomaticRegularizer::record_outcome` adapts weights and the threshold.
+///
+/// An outcome is adverse when the violation went unhealed, when its axiom recurred
+/// `recurrence_limit` times within `recurrence_window`, or when the penalty passed to
+/// `record_penalty` last rose above `penalty_rise` times its running average. Adverse
+/// outcomes raise the axiom's weight by `weight_step` (through `update_weights`, so
+/// the axiom's learning rate and the clamping apply) and move the threshold `decay`
+/// of the way toward `min_threshold`; other outcomes lower the weight and move the
+/// threshold `decay` of the way back to where it was when the policy was set.
+///
+/// Each outcome multiplies the axiom's step by `rate_decay`, never below
+/// `min_rate_scale`, so adaptation settles as evidence accumulates. Every step is kept
+/// in `adaptation_trajectory`.
+///
+/// `AxiomaticSelfHealer` feeds back each call's penalty and every violation it
+/// detects, and a `HealAbove` or `HealAtOrAbove` threshold policy follows the adapted
+/// threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedbackPolicy {
+    /// Feedback passed to `update_weights` per outcome, scaled by the learning rate
+    pub weight_step: f64,
+    /// Fraction of the distance to its target the threshold moves per outcome
+    pub decay: f64,
+    pub min_threshold: f64,
+    /// The threshold never adapts above this; it is finite by default so adaptation
+    /// alone can't disable healing
+    pub max_threshold: f64,
+    pub recurrence_window: Duration,
+    pub recurrence_limit: usize,
+    /// Factor an axiom's step is multiplied by after each of its outcomes; 1 keeps it
+    pub rate_decay: f64,
+    pub min_rate_scale: f64,
+    /// Weight of the newest penalty in the running average
+    pub penalty_smoothing: f64,
+    /// How far above the running average a penalty must be to count as rising
+    pub penalty_rise: f64,
+    /// Adaptation steps kept for `adaptation_trajectory`, oldest dropped first
+    pub trajectory_capacity: usize,
+}
+
+impl Default for FeedbackPolicy {
+    fn default() -> Self {
+        Self {
+            weight_step: 1.0,
+            decay: 0.1,
+            min_threshold: 0.1,
+            max_threshold: 10.0,
+            recurrence_window: Duration::from_secs(60),
+            recurrence_limit: 3,
+            rate_decay: 1.0,
+            min_rate_scale: 0.1,
+            penalty_smoothing: 0.2,
+            penalty_rise: 1.5,
+            trajectory_capacity: 1024,
+        }
+    }
+}
+
+/// Why an outcome adapted weights the way it did, most serious first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptationCause {
+    Unhealed,
+    Recurred,
+    PenaltyRising,
+    Healed,
+}
+
+impl AdaptationCause {
+    pub fn adverse(self) -> bool {
+        self != Self::Healed
+    }
+}
+
+/// One outcome fed back under a `FeedbackPolicy`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptationStep {
+    pub timestamp: u64,
+    pub axiom: Axiom,
+    pub cause: AdaptationCause,
+    /// `None` when the axiom has no weight to adapt
+    pub weight: Option<Change<f64>>,
+    pub threshold: Change<f64>,
+    /// The axiom's step scale after `rate_decay` was applied
+    pub rate_scale: f64,
+}
+
+/// An active `FeedbackPolicy` and what it has observed
+#[derive(Debug, Clone)]
+struct FeedbackState {
+    policy: FeedbackPolicy,
+    /// The threshold when the policy was set, which healed outcomes return toward
+    baseline: f64,
+    /// Timestamps of recent outcomes per axiom, within the recurrence window
+    recent: HashMap<Axiom, VecDeque<u64>>,
+    /// Current `rate_decay` scale per axiom; absent means 1
+    rate_scales: HashMap<Axiom, f64>,
+    /// Running average of the penalties passed to `record_penalty`
+    penalty_average: Option<f64>,
+    penalty_rising: bool,
+    trajectory: VecDeque<AdaptationStep>,
+}
+
+/// Stretch of recent history that `statistics` reports rates over by default
+pub const DEFAULT_STATISTICS_WINDOW: Duration = Duration::from_secs(300);
+
+/// Filters over a regularizer's buffered history, started with `history_query`.
+///
+/// Only violations of enabled axioms are ever returned, as with `statistics`.
+#[derive(Clone)]
+pub struct HistoryQuery<'a> {
+    regularizer: &'a AdaptiveAxiomaticRegularizer,
+    axiom: Option<Axiom>,
+    min_severity: Option<Severity>,
+    since: Option<u64>,
+    until: Option<u64>,
+    limit: Option<usize>,
+}
+
+impl<'a> HistoryQuery<'a> {
+    fn new(regularizer: &'a AdaptiveAxiomaticRegularizer) -> Self {
+        Self { regularizer, axiom: None, min_severity: None, since: None, until: None, limit: None }
+    }
+
+    pub fn axiom(mut self, axiom: Axiom) -> Self {
+        self.axiom = Some(axiom);
+        self
+    }
+
+    pub fn severity_at_least(mut self, severity: Severity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Only violations timestamped at or after `timestamp`
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.since = Some(timestamp);
+        self
+    }
+
+    /// Only violations timestamped before `timestamp`
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.until = Some(timestamp);
+        self
+    }
+
+    /// Only the `count` most recently recorded matches
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    fn matches(&self, violation: &Violation) -> bool {
+        self.axiom.as_ref().is_none_or(|axiom| violation.axiom == *axiom)
+            && self.min_severity.is_none_or(|severity| violation.severity >= severity)
+            && self.since.is_none_or(|since| violation.timestamp >= since)
+            && self.until.is_none_or(|until| violation.timestamp < until)
+            && self.regularizer.is_enabled(&violation.axiom)
+    }
+
+    /// The matching violations, oldest recorded first
+    pub fn violations(&self) -> Vec<Violation> {
+        let Ok(history) = self.regularizer.violation_history.lock() else {
+            return Vec::new();
+        };
+        let newest = history.entries.iter().rev().filter(|v| self.matches(v));
+        let mut found: Vec<Violation> =
+            newest.take(self.limit.unwrap_or(usize::MAX)).cloned().collect();
+        found.reverse();
+        found
+    }
+
+    pub fn count(&self) -> usize {
+        let Ok(history) = self.regularizer.violation_history.lock() else {
+            return 0;
+        };
+        let matching = history.entries.iter().filter(|v| self.matches(v)).count();
+        matching.min(self.limit.unwrap_or(usize::MAX))
+    }
+
+    pub fn statistics(&self) -> ViolationStatistics {
+        let mut statistics = ViolationStatistics::default();
+        for violation in self.violations() {
+            statistics.record(&violation);
+        }
+        statistics
+    }
+}
+
+/// Adaptive Axiomatic Regularizer - monitors and enforces axioms.
+///
+/// Its substring rules are the built-in detectors: each is named `rule:<pattern>` and
+/// can be removed or disabled by that name like any registered `ViolationDetector`.
+pub struct AdaptiveAxiomaticRegularizer {
+    axiom_weights: HashMap<Axiom, f64>,
+    rules: Vec<DetectionRule>,
+    detectors: Vec<Box<dyn ViolationDetector>>,
+    /// Names of rules and detectors that are registered but not run
+    disabled_detectors: BTreeSet<String>,
+    /// Priorities set with `set_detector_priority`, overriding the detectors' own
+    detector_priorities: HashMap<String, i32>,
+    violation_history: Arc<Mutex<ViolationHistory>>,
+    learning_rate: f64,
+    threshold: f64,
+    detection_tally: Mutex<DetectionTally>,
+    clock: Arc<dyn Clock>,
+    disabled: BTreeSet<Axiom>,
+    disabled_policy: DisabledAxiomPolicy,
+    ignored: Mutex<HashMap<Axiom, u64>>,
+    /// When each weight last reached a clamp bound, while it stays there
+    pinned_since: HashMap<Axiom, u64>,
+    /// Bumped whenever what detection can find changes
+    generation: u64,
+    tiering: TieringPolicy,
+    tier_rng: Mutex<SplitMix64>,
+    tiering_stats: Mutex<TieringStats>,
+    severity_multipliers: SeverityMultipliers,
+    /// Per-axiom replacements for `severity_multipliers`
+    axiom_multipliers: HashMap<Axiom, SeverityMultipliers>,
+    /// Per-axiom replacements for `learning_rate`
+    axiom_learning_rates: HashMap<Axiom, f64>,
+    feedback: Option<FeedbackState>,
+    statistics_window: Duration,
+}
+
+/// How `record_violation` treats violations of a disabled axiom
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisabledAxiomPolicy {
+    /// Drop them, counting them in `ignored_counts`
+    #[default]
+    Ignore,
+    /// Drop them; `try_record_violation` reports `DisabledAxiom`
+    Reject,
+}
+
+/// A violation was recorded for an axiom that is disabled
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisabledAxiom {
+    pub axiom: Axiom,
+}
+
+impl fmt::Display for DisabledAxiom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "axiom {:?} is disabled", self.axiom)
+    }
+}
+
+impl std::error::Error for DisabledAxiom {}
+
+/// Counts of evaluated contexts, used to derive observed violation rates
+#[derive(Debug, Default)]
+struct DetectionTally {
+    contexts: u64,
+    by_axiom: HashMap<Axiom, u64>,
+}
+
+/// Per-axiom aggregate of a batch penalty
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AxiomPenalty {
+    /// Number of violations of the axiom across the batch
+    pub violations: usize,
+    /// Penalty contributed by the axiom
+    pub penalty: f64,
+    /// Partial derivative of the total penalty with respect to the axiom weight
+    pub gradient: f64,
+}
+
+/// Penalty of a batch of contexts, for training-loop integration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchPenalty {
+    pub total: f64,
+    pub per_context: Vec<f64>,
+    pub per_axiom: HashMap<Axiom, AxiomPenalty>,
+}
+
+impl BatchPenalty {
+    fn merge_context(&mut self, penalty: f64, axioms: HashMap<Axiom, AxiomPenalty>) {
+        self.total += penalty;
+        self.per_context.push(penalty);
+        for (axiom, part) in axioms {
+            let entry = self.per_axiom.entry(axiom).or_default();
+            entry.violations += part.violations;
+            entry.penalty += part.penalty;
+            entry.gradient += part.gradient;
+        }
+    }
+}
+
+impl Default for AdaptiveAxiomaticRegularizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveAxiomaticRegularizer {
+    /// Penalty weight of violations whose axiom has no weight, such as an
+    /// unregistered custom axiom
+    pub const UNREGISTERED_AXIOM_WEIGHT: f64 = 1.0;
+
+    pub fn new() -> Self {
+        let mut axiom_weights = HashMap::new();
+        axiom_weights.insert(Axiom::Consistency, 1.0);
+        axiom_weights.insert(Axiom::Completeness, 1.0);
+        axiom_weights.insert(Axiom::Transparency, 1.0);
+        axiom_weights.insert(Axiom::Safety, 1.5);
+        axiom_weights.insert(Axiom::Fairness, 1.0);
+
+        let rules = vec![
+            DetectionRule::new("inconsistent", Axiom::Consistency, Severity::High),
+            DetectionRule::new("unsafe", Axiom::Safety, Severity::Critical),
+        ];
+
+        Self {
+            axiom_weights,
+            rules,
+            detectors: Vec::new(),
+            disabled_detectors: BTreeSet::new(),
+            detector_priorities: HashMap::new(),
+            violation_history: Arc::new(Mutex::new(ViolationHistory::new(
+                DEFAULT_HISTORY_CAPACITY,
+            ))),
+            learning_rate: 0.01,
+            threshold: 0.5,
+            detection_tally: Mutex::new(DetectionTally::default()),
+            clock: Arc::new(SystemClock),
+            disabled: BTreeSet::new(),
+            disabled_policy: DisabledAxiomPolicy::default(),
+            ignored: Mutex::new(HashMap::new()),
+            pinned_since: HashMap::new(),
+            generation: 0,
+            tiering: TieringPolicy::default(),
+            tier_rng: Mutex::new(SplitMix64(0)),
+            tiering_stats: Mutex::new(TieringStats::default()),
+            severity_multipliers: SeverityMultipliers::default(),
+            axiom_multipliers: HashMap::new(),
+            axiom_learning_rates: HashMap::new(),
+            feedback: None,
+            statistics_window: DEFAULT_STATISTICS_WINDOW,
+        }
+    }
+
+    /// Replace the clock used to timestamp violations
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Arc::new(clock);
+    }
+
+    /// Choose when thorough rules run; resets the sampling generator and counts
+    pub fn set_tiering_policy(&mut self, policy: TieringPolicy) {
+        let seed = match policy {
+            TieringPolicy::EscalateOnHitPlusSample { seed, .. } => seed,
+            _ => 0,
+        };
+        self.tiering = policy;
+        self.tier_rng = Mutex::new(SplitMix64(seed));
+        self.tiering_stats = Mutex::new(TieringStats::default());
+    }
+
+    pub fn tiering_policy(&self) -> TieringPolicy {
+        self.tiering
+    }
+
+    pub fn tiering_statistics(&self) -> TieringStats {
+        self.tiering_stats.lock().map(|stats| *stats).unwrap_or_default()
+    }
+
+    /// Detect violations in the given context
+    pub fn detect_violations(&self, context: &str) -> Vec<Violation> {
+        self.detect_tiered(context).0
+    }
+
+    /// `detect_violations`, also saying which detection passes ran
+    pub fn detect_tiered(&self, context: &str) -> (Vec<Violation>, TiersRun) {
+        let (violations, tiers) = self.scan_tiers(context, true);
+
+        if let Ok(mut tally) = self.detection_tally.lock() {
+            tally.contexts += 1;
+            let mut seen: Vec<&Axiom> = violations.iter().map(|v| &v.axiom).collect();
+            seen.sort();
+            seen.dedup();
+            for axiom in seen {
+                *tally.by_axiom.entry(axiom.clone()).or_insert(0) += 1;
+            }
+        }
+
+        (violations, tiers)
+    }
+
+    /// Run detection without counting the context as an observation; never samples
+    fn scan(&self, context: &str) -> Vec<Violation> {
+        self.scan_tiers(context, false).0
+    }
+
+    /// Run the passes the tiering policy calls for, sampling and counting them
+    /// only for observed contexts
+    fn scan_tiers(&self, context: &str, observed: bool) -> (Vec<Violation>, TiersRun) {
+        if self.tiering == TieringPolicy::AlwaysBoth {
+            let tiers = TiersRun { fast: true, thorough: true, sampled: false };
+            return (self.scan_tier(context, None), tiers);
+        }
+        let mut violations = self.scan_tier(context, Some(DetectionTier::Fast));
+        let mut tiers = TiersRun { fast: true, ..TiersRun::default() };
+        let hit = !violations.is_empty();
+        tiers.sampled = match self.tiering {
+            TieringPolicy::EscalateOnHitPlusSample { rate, .. } if observed && !hit => {
+                let draw = self.tier_rng.lock().map(|mut rng| rng.next_f64()).unwrap_or(1.0);
+                draw < rate
+            }
+            _ => false,
+        };
+        tiers.thorough = hit || tiers.sampled;
+        if tiers.thorough {
+            violations.extend(self.scan_tier(context, Some(DetectionTier::Thorough)));
+        }
+        if observed {
+            if let Ok(mut stats) = self.tiering_stats.lock() {
+                stats.fast_passes += 1;
+                stats.thorough_passes += tiers.thorough as u64;
+                stats.escalated_on_hit += hit as u64;
+                stats.sampled += tiers.sampled as u64;
+                stats.fast_misses += (tiers.sampled && !violations.is_empty()) as u64;
+            }
+        }
+        (violations, tiers)
+    }
+
+    /// Run the rules and detectors of one tier, or all of them, keeping one of each
+    /// set of identical hits (same axiom, severity and span)
+    fn scan_tier(&self, context: &str, tier: Option<DetectionTier>) -> Vec<Violation> {
+        let in_tier = |t: DetectionTier| tier.is_none_or(|tier| t == tier);
+        let now = self.current_timestamp();
+        let mut seen = HashSet::new();
+        self.scanners()
+            .into_iter()
+            .filter(|(name, _)| !self.disabled_detectors.contains(name))
+            .flat_map(|(name, scanner)| match scanner {
+                Scanner::Rule(rule) if self.is_enabled(&rule.axiom) && in_tier(rule.tier) => {
+                    let Some(found) = rule.find(context) else {
+                        return Vec::new();
+                    };
+                    let span = format!("{}..{}", found.start, found.end);
+                    let metadata =
+                        BTreeMap::from([("rule".to_string(), name), ("span".to_string(), span)]);
+                    vec![Violation {
+                        axiom: rule.axiom.clone(),
+                        severity: rule.severity_of(context, found, &metadata),
+                        context: context.to_string(),
+                        timestamp: now,
+                        metadata,
+                    }]
+                }
+                Scanner::Rule(_) => Vec::new(),
+                Scanner::Detector(detector) if in_tier(detector.tier()) => detector
+                    .detect(context)
+                    .into_iter()
+                    .map(|mut violation| {
+                        violation.context = context.to_string();
+                        violation.timestamp = now;
+                        violation.metadata.entry("rule".to_string()).or_insert(name.clone());
+                        violation
+                    })
+                    .collect(),
+                Scanner::Detector(_) => Vec::new(),
+            })
+            .filter(|violation| self.is_enabled(&violation.axiom))
+            .filter(|v| seen.insert((v.axiom.clone(), v.severity, v.metadata.get("span").cloned())))
+            .collect()
+    }
+
+    /// Rules and detectors by descending priority; rules come before detectors of the
+    /// same priority, and each in the order they were added
+    fn scanners(&self) -> Vec<(String, Scanner<'_>)> {
+        let rules = self.rules.iter().map(|rule| (rule.name(), Scanner::Rule(rule), 0));
+        let detectors = self.detectors.iter().map(|detector| {
+            let name = detector.name().to_string();
+            (name, Scanner::Detector(detector.as_ref()), detector.priority())
+        });
+        let mut scanners: Vec<(i32, String, Scanner<'_>)> = rules
+            .chain(detectors)
+            .map(|(name, scanner, priority)| {
+                let priority = self.detector_priorities.get(&name).copied().unwrap_or(priority);
+                (priority, name, scanner)
+            })
+            .collect();
+        scanners.sort_by_key(|(priority, _, _)| std::cmp::Reverse(*priority));
+        scanners.into_iter().map(|(_, name, scanner)| (name, scanner)).collect()
+    }
+
+    /// Run the rules or detector called `name` at `priority` instead of their own;
+    /// false if there are none
+    pub fn set_detector_priority(&mut self, name: &str, priority: i32) -> bool {
+        if !self.detector_names().any(|n| n == name) {
+            return false;
+        }
+        self.detector_priorities.insert(name.to_string(), priority);
+        self.generation += 1;
+        true
+    }
+
+    /// Register an additional detection rule
+    pub fn add_rule(&mut self, rule: DetectionRule) {
+        self.rules.push(rule);
+        self.generation += 1;
+    }
+
+    /// Run `detector` on every context, replacing any registered detector of the
+    /// same name
+    pub fn register_detector(&mut self, detector: impl ViolationDetector + 'static) {
+        let detector: Box<dyn ViolationDetector> = Box::new(detector);
+        match self.detectors.iter_mut().find(|d| d.name() == detector.name()) {
+            Some(existing) => *existing = detector,
+            None => self.detectors.push(detector),
+        }
+        self.generation += 1;
+    }
+
+    /// Unregister the detector or rules called `name`; false if there were none
+    pub fn remove_detector(&mut self, name: &str) -> bool {
+        let before = self.detectors.len() + self.rules.len();
+        self.detectors.retain(|d| d.name() != name);
+        self.rules.retain(|rule| rule.name() != name);
+        self.disabled_detectors.remove(name);
+        self.detector_priorities.remove(name);
+        let removed = self.detectors.len() + self.rules.len() < before;
+        if removed {
+            self.generation += 1;
+        }
+        removed
+    }
+
+    /// Stop running the detector or rules called `name` until `enable_detector`;
+    /// false if there are none
+    pub fn disable_detector(&mut self, name: &str) -> bool {
+        if !self.detector_names().any(|n| n == name) {
+            return false;
+        }
+        if self.disabled_detectors.insert(name.to_string()) {
+            self.generation += 1;
+        }
+        true
+    }
+
+    pub fn enable_detector(&mut self, name: &str) -> bool {
+        let enabled = self.disabled_detectors.remove(name);
+        if enabled {
+            self.generation += 1;
+        }
+        enabled
+    }
+
+    pub fn is_detector_enabled(&self, name: &str) -> bool {
+        self.detector_names().any(|n| n == name) && !self.disabled_detectors.contains(name)
+    }
+
+    /// Names of the rules and registered detectors, in evaluation order
+    pub fn detector_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.scanners().into_iter().map(|(name, _)| name)
+    }
+
+    /// Changes whenever rules or detectors change or axioms are enabled or disabled, so
+    /// results that depend on the rule set can tell they are stale
+    pub fn rules_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Registered detection rules, in evaluation order
+    pub fn rules(&self) -> &[DetectionRule] {
+        &self.rules
+    }
+
+    /// Each enabled rule and detector and the axioms it can produce
+    pub fn detector_coverage(&self) -> Vec<(String, Vec<Axiom>)> {
+        self.scanners()
+            .into_iter()
+            .filter(|(name, _)| !self.disabled_detectors.contains(name))
+            .map(|(name, scanner)| match scanner {
+                Scanner::Rule(rule) => (name, rule.covers().to_vec()),
+                Scanner::Detector(detector) => (name, detector.covers()),
+            })
+            .collect()
+    }
+
+    /// Every enabled axiom at least one enabled rule or detector can produce
+    pub fn covered_axioms(&self) -> BTreeSet<Axiom> {
+        self.detector_coverage()
+            .into_iter()
+            .flat_map(|(_, axioms)| axioms)
+            .filter(|axiom| self.is_enabled(axiom))
+            .collect()
+    }
+
+    /// Stop detecting, healing and reporting `axiom`.
+    ///
+    /// Its weight is kept, so `enable_axiom` restores it unchanged.
+    pub fn disable_axiom(&mut self, axiom: Axiom) {
+        self.disabled.insert(axiom);
+        self.generation += 1;
+    }
+
+    pub fn enable_axiom(&mut self, axiom: &Axiom) {
+        self.disabled.remove(axiom);
+        self.generation += 1;
+    }
+
+    pub fn is_enabled(&self, axiom: &Axiom) -> bool {
+        !self.disabled.contains(axiom)
+    }
+
+    /// Built-in and registered custom axioms not disabled
+    pub fn enabled_axioms(&self) -> BTreeSet<Axiom> {
+        Axiom::ALL
+            .into_iter()
+            .chain(self.custom_axioms())
+            .filter(|axiom| self.is_enabled(axiom))
+            .collect()
+    }
+
+    /// Register a custom axiom with its starting weight, or reset the weight of one
+    /// already registered.
+    ///
+    /// Violations of a custom axiom that was never registered are still counted and
+    /// penalised, at `UNREGISTERED_AXIOM_WEIGHT`, but `update_weights` ignores them.
+    pub fn register_axiom(&mut self, name: impl Into<String>, initial_weight: f64) -> Axiom {
+        let axiom = Axiom::Custom(name.into());
+        self.axiom_weights.insert(axiom.clone(), initial_weight.clamp(0.1, 10.0));
+        self.pinned_since.remove(&axiom);
+        self.generation += 1;
+        axiom
+    }
+
+    /// Custom axioms with a weight, in order
+    pub fn custom_axioms(&self) -> BTreeSet<Axiom> {
+        self.axiom_weights.keys().filter(|axiom| axiom.is_custom()).cloned().collect()
+    }
+
+    /// What `record_violation` does with violations of disabled axioms
+    pub fn set_disabled_axiom_policy(&mut self, policy: DisabledAxiomPolicy) {
+        self.disabled_policy = policy;
+    }
+
+    /// Violations of disabled axioms dropped under `DisabledAxiomPolicy::Ignore`
+    pub fn ignored_counts(&self) -> HashMap<Axiom, u64> {
+        self.ignored.lock().map(|ignored| ignored.clone()).unwrap_or_default()
+    }
+
+    /// Calculate regularization penalty for violations
+    pub fn calculate_penalty(&self, violations: &[Violation]) -> f64 {
+        violations.iter().map(|v| {
+            let weight = self
+                .axiom_weights
+                .get(&v.axiom)
+                .unwrap_or(&Self::UNREGISTERED_AXIOM_WEIGHT);
+            weight * self.severity_multiplier_for(&v.axiom, v.severity)
+        }).sum()
+    }
+
+    /// Penalty multiplier applied for a severity level
+    pub fn severity_multiplier(&self, severity: Severity) -> f64 {
+        self.severity_multipliers.get(severity)
+    }
+
+    /// Multiplier for a violation of `axiom` at `severity`, honouring the axiom's
+    /// own multipliers if it has them
+    pub fn severity_multiplier_for(&self, axiom: &Axiom, severity: Severity) -> f64 {
+        self.axiom_multipliers
+            .get(axiom)
+            .unwrap_or(&self.severity_multipliers)
+            .get(severity)
+    }
+
+    /// Replace the severity multipliers used by `calculate_penalty`
+    pub fn set_severity_multipliers(&mut self, multipliers: SeverityMultipliers) {
+        self.severity_multipliers = multipliers;
+    }
+
+    /// Score `axiom`'s violations with their own multipliers; `None` goes back to
+    /// the regularizer-wide ones
+    pub fn set_axiom_severity_multipliers(
+        &mut self,
+        axiom: Axiom,
+        multipliers: Option<SeverityMultipliers>,
+    ) {
+        match multipliers {
+            Some(multipliers) => self.axiom_multipliers.insert(axiom, multipliers),
+            None => self.axiom_multipliers.remove(&axiom),
+        };
+    }
+
+    /// Adapt weights and the threshold from healing outcomes; `None` stops adapting
+    /// and leaves both where they are
+    pub fn set_feedback_policy(&mut self, policy: Option<FeedbackPolicy>) {
+        self.feedback = policy.map(|policy| FeedbackState {
+            policy,
+            baseline: self.threshold.clamp(policy.min_threshold, policy.max_threshold),
+            recent: HashMap::new(),
+            rate_scales: HashMap::new(),
+            penalty_average: None,
+            penalty_rising: false,
+            trajectory: VecDeque::new(),
+        });
+    }
+
+    pub fn feedback_policy(&self) -> Option<FeedbackPolicy> {
+        self.feedback.as_ref().map(|state| state.policy)
+    }
+
+    /// Feed back whether `violation` was healed, under the feedback policy; does
+    /// nothing without one
+    pub fn record_outcome(&mut self, violation: &Violation, healed: bool) {
+        let Some(state) = self.feedback.as_mut() else {
+            return;
+        };
+        let policy = state.policy;
+        let now = violation.timestamp;
+        let window = policy.recurrence_window.as_millis() as u64;
+        let recent = state.recent.entry(violation.axiom.clone()).or_default();
+        recent.push_back(now);
+        while recent.front().is_some_and(|&seen| seen.saturating_add(window) < now) {
+            recent.pop_front();
+        }
+        let cause = if !healed {
+            AdaptationCause::Unhealed
+        } else if recent.len() >= policy.recurrence_limit {
+            AdaptationCause::Recurred
+        } else if state.penalty_rising {
+            AdaptationCause::PenaltyRising
+        } else {
+            AdaptationCause::Healed
+        };
+        let scale = state.rate_scales.entry(violation.axiom.clone()).or_insert(1.0);
+        let step = policy.weight_step * *scale;
+        *scale = (*scale * policy.rate_decay).max(policy.min_rate_scale);
+        let rate_scale = *scale;
+
+        let before = self.threshold;
+        let target = if cause.adverse() { policy.min_threshold } else { state.baseline };
+        let threshold = self.threshold + (target - self.threshold) * policy.decay;
+        self.threshold = threshold.clamp(policy.min_threshold, policy.max_threshold);
+        let weight_before = self.weight(&violation.axiom);
+        self.update_weights(violation.axiom.clone(), if cause.adverse() { step } else { -step });
+
+        let weight = weight_before.zip(self.weight(&violation.axiom));
+        let adaptation = AdaptationStep {
+            timestamp: now,
+            axiom: violation.axiom.clone(),
+            cause,
+            weight: weight.map(|(before, after)| Change { before, after }),
+            threshold: Change { before, after: self.threshold },
+            rate_scale,
+        };
+        if let Some(state) = self.feedback.as_mut() {
+            if state.trajectory.len() >= policy.trajectory_capacity.max(1) {
+                state.trajectory.pop_front();
+            }
+            state.trajectory.push_back(adaptation);
+        }
+    }
+
+    /// Fold a context's penalty into the running average under the feedback policy;
+    /// until the next call, outcomes count as adverse if it rose too far above it
+    pub fn record_penalty(&mut self, penalty: f64) {
+        let Some(state) = self.feedback.as_mut() else {
+            return;
+        };
+        let policy = state.policy;
+        let average = state.penalty_average.unwrap_or(penalty);
+        state.penalty_rising = penalty > 0.0 && penalty > average * policy.penalty_rise;
+        let alpha = policy.penalty_smoothing.clamp(0.0, 1.0);
+        state.penalty_average = Some(average + (penalty - average) * alpha);
+    }
+
+    /// Running average of the penalties fed back, once there has been one
+    pub fn penalty_trend(&self) -> Option<f64> {
+        self.feedback.as_ref().and_then(|state| state.penalty_average)
+    }
+
+    /// Adaptation steps under the current feedback policy, oldest first
+    pub fn adaptation_trajectory(&self) -> impl Iterator<Item = &AdaptationStep> + '_ {
+        self.feedback.iter().flat_map(|state| state.trajectory.iter())
+    }
+
+    /// Adapt `axiom`'s weight at `rate` instead of the regularizer-wide learning rate;
+    /// `None` goes back to it
+    pub fn set_axiom_learning_rate(&mut self, axiom: Axiom, rate: Option<f64>) {
+        match rate {
+            Some(rate) => self.axiom_learning_rates.insert(axiom, rate),
+            None => self.axiom_learning_rates.remove(&axiom),
+        };
+    }
+
+    /// Learning rate `update_weights` applies to `axiom`
+    pub fn learning_rate_for(&self, axiom: &Axiom) -> f64 {
+        self.axiom_learning_rates.get(axiom).copied().unwrap_or(self.learning_rate)
+    }
+
+    /// Axioms with their own learning rate
+    pub fn learning_rates(&self) -> BTreeMap<Axiom, f64> {
+        self.axiom_learning_rates.iter().map(|(a, rate)| (a.clone(), *rate)).collect()
+    }
+
+    /// The feedback policy's current `rate_decay` scale per axiom that has decayed
+    pub fn rate_scales(&self) -> BTreeMap<Axiom, f64> {
+        let scales = self.feedback.iter().flat_map(|state| &state.rate_scales);
+        scales.map(|(a, scale)| (a.clone(), *scale)).collect()
+    }
+
+    /// Replace the rate scales of the feedback policy; without one there is nothing
+    /// to scale and they are dropped
+    fn set_rate_scales(&mut self, scales: BTreeMap<Axiom, f64>) {
+        if let Some(state) = self.feedback.as_mut() {
+            state.rate_scales = scales.into_iter().collect();
+        }
+    }
+
+    /// Compute the penalty of a batch of contexts in one pass
+    pub fn penalty_for_batch(&self, contexts: &[&str]) -> BatchPenalty {
+        self.penalty_for_batch_with(contexts, |_, _| {})
+    }
+
+    /// Like `penalty_for_batch`, invoking `on_context` with each context's index and penalty
+    pub fn penalty_for_batch_with<F>(&self, contexts: &[&str], mut on_context: F) -> BatchPenalty
+    where
+        F: FnMut(usize, f64),
+    {
+        let mut batch = BatchPenalty::default();
+        for (index, context) in contexts.iter().enumerate() {
+            let (penalty, axioms) = self.context_penalty(context);
+            on_context(index, penalty);
+            batch.merge_context(penalty, axioms);
+        }
+        batch
+    }
+
+    /// Like `penalty_for_batch`, splitting the batch across `workers` threads
+    pub fn penalty_for_batch_parallel(&self, contexts: &[&str], workers: usize) -> BatchPenalty {
+        let chunk_size = contexts.len().div_ceil(workers.max(1)).max(1);
+
+        let partials: Vec<Vec<_>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = contexts
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk.iter().map(|c| self.context_penalty(c)).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("batch penalty worker panicked"))
+                .collect()
+        });
+
+        let mut batch = BatchPenalty::default();
+        for (penalty, axioms) in partials.into_iter().flatten() {
+            batch.merge_context(penalty, axioms);
+        }
+        batch
+    }
+
+    fn context_penalty(&self, context: &str) -> (f64, HashMap<Axiom, AxiomPenalty>) {
+        let violations = self.detect_violations(context);
+        let mut axioms: HashMap<Axiom, AxiomPenalty> = HashMap::new();
+
+        for v in &violations {
+            let weight = self.weight(&v.axiom).unwrap_or(Self::UNREGISTERED_AXIOM_WEIGHT);
+            let multiplier = self.severity_multiplier_for(&v.axiom, v.severity);
+            let entry = axioms.entry(v.axiom.clone()).or_default();
+            entry.violations += 1;
+            entry.penalty += weight * multiplier;
+            entry.gradient += multiplier;
+        }
+
+        (self.calculate_penalty(&violations), axioms)
+    }
+
+    /// Fraction of evaluated contexts that violated each axiom
+    pub fn observed_violation_rates(&self) -> HashMap<Axiom, f64> {
+        let tally = match self.detection_tally.lock() {
+            Ok(tally) => tally,
+            Err(_) => return HashMap::new(),
+        };
+        if tally.contexts == 0 {
+            return HashMap::new();
+        }
+
+        tally
+            .by_axiom
+            .iter()
+            .filter(|(axiom, _)| self.is_enabled(axiom))
+            .map(|(axiom, count)| (axiom.clone(), *count as f64 / tally.contexts as f64))
+            .collect()
+    }
+
+    /// Suggest weight feedback that moves observed violation rates toward the targets.
+    ///
+    /// Feedback is the relative error `(observed - target) / target`, clamped to
+    /// `[-1.0, 1.0]`: positive when an axiom is violated more often than desired, so
+    /// its weight should rise. Returns nothing until at least one context was evaluated.
+    pub fn suggest_weight_feedback(&self, target_rates: &HashMap<Axiom, f64>) -> Vec<(Axiom, f64)> {
+        let evaluated = self.detection_tally.lock().map(|t| t.contexts).unwrap_or(0);
+        if evaluated == 0 {
+            return Vec::new();
+        }
+
+        let observed = self.observed_violation_rates();
+        let mut feedback: Vec<(Axiom, f64)> = target_rates
+            .iter()
+            .map(|(axiom, target)| {
+                let rate = observed.get(axiom).copied().unwrap_or(0.0);
+                let error = (rate - target) / target.max(f64::EPSILON);
+                (axiom.clone(), error.clamp(-1.0, 1.0))
+            })
+            .collect();
+        feedback.sort_by(|a, b| a.0.cmp(&b.0));
+        feedback
+    }
+
+    /// Update axiom weights based on feedback
+    pub fn update_weights(&mut self, axiom: Axiom, feedback: f64) {
+        let now = self.current_timestamp();
+        let rate = self.learning_rate_for(&axiom);
+        if let Some(weight) = self.axiom_weights.get_mut(&axiom) {
+            *weight += rate * feedback;
+            *weight = weight.clamp(0.1, 10.0);
+            if *weight == 0.1 || *weight == 10.0 {
+                self.pinned_since.entry(axiom).or_insert(now);
+            } else {
+                self.pinned_since.remove(&axiom);
+            }
+        }
+    }
+
+    /// When the axiom's weight reached the clamp bound it still sits at, if it does
+    pub fn weight_pinned_since(&self, axiom: &Axiom) -> Option<u64> {
+        self.pinned_since.get(axiom).copied()
+    }
+
+    /// Apply several weight updates at once
+    pub fn update_weights_batch(&mut self, feedback: &[(Axiom, f64)]) {
+        for (axiom, value) in feedback {
+            self.update_weights(axiom.clone(), *value);
+        }
+    }
+
+    /// Current weight of an axiom, if it has one
+    pub fn weight(&self, axiom: &Axiom) -> Option<f64> {
+        self.axiom_weights.get(axiom).copied()
+    }
+
+    /// Record a violation in history
+    pub fn record_violation(&self, violation: Violation) {
+        let _ = self.try_record_violation(violation);
+    }
+
+    /// Record a violation, applying the disabled-axiom policy if its axiom is disabled
+    pub fn try_record_violation(&self, violation: Violation) -> Result<(), DisabledAxiom> {
+        if !self.is_enabled(&violation.axiom) {
+            if self.disabled_policy == DisabledAxiomPolicy::Reject {
+                return Err(DisabledAxiom { axiom: violation.axiom });
+            }
+            if let Ok(mut ignored) = self.ignored.lock() {
+                *ignored.entry(violation.axiom).or_insert(0) += 1;
+            }
+            return Ok(());
+        }
+        if let Ok(mut history) = self.violation_history.lock() {
+            history.push(violation);
+        }
+        Ok(())
+    }
+
+    /// Keep at most `capacity` violations in history (at least one), evicting the
+    /// oldest first; `DEFAULT_HISTORY_CAPACITY` otherwise
+    pub fn with_history_capacity(self, capacity: usize) -> Self {
+        if let Ok(mut history) = self.violation_history.lock() {
+            history.set_capacity(capacity);
+        }
+        self
+    }
+
+    /// Violations currently held in history
+    pub fn history_len(&self) -> usize {
+        self.violation_history.lock().map_or(0, |h| h.entries.len())
+    }
+
+    /// Violations evicted from history to stay within its capacity, since creation
+    pub fn history_evicted(&self) -> u64 {
+        self.violation_history.lock().map_or(0, |h| h.evicted)
+    }
+
+    /// Statistics of the buffered violations of enabled axioms, from counters kept as
+    /// violations are recorded and evicted rather than by walking the history, with
+    /// rates over the statistics window.
+    ///
+    /// The window walks back from the newest violation to the first one older than
+    /// it, so its cost follows the window's violations rather than the history's.
+    pub fn statistics(&self) -> ViolationStatistics {
+        let Ok(history) = self.violation_history.lock() else {
+            return ViolationStatistics::default();
+        };
+        let mut statistics = history.statistics(|axiom| self.is_enabled(axiom));
+        statistics.window = Some(self.windowed_statistics(&history));
+        statistics
+    }
+
+    fn windowed_statistics(&self, history: &ViolationHistory) -> WindowedStatistics {
+        let window = self.statistics_window;
+        let cutoff = self.clock.now_millis().saturating_sub(window.as_millis() as u64);
+        let mut windowed = WindowedStatistics { window, ..WindowedStatistics::default() };
+        let mut penalty = 0.0;
+        let recent = history.entries.iter().rev().take_while(|v| v.timestamp >= cutoff);
+        for violation in recent.filter(|v| self.is_enabled(&v.axiom)) {
+            windowed.violations += 1;
+            *windowed.per_minute_by_axiom.entry(violation.axiom.clone()).or_insert(0.0) += 1.0;
+            penalty += self.calculate_penalty(std::slice::from_ref(violation));
+        }
+        let minutes = window.as_secs_f64() / 60.0;
+        if windowed.violations > 0 && minutes > 0.0 {
+            windowed.per_minute = windowed.violations as f64 / minutes;
+            windowed.per_minute_by_axiom.values_mut().for_each(|count| *count /= minutes);
+            windowed.average_penalty = penalty / windowed.violations as f64;
+        }
+        windowed
+    }
+
+    /// Report rates in `statistics` over the last `window`; `DEFAULT_STATISTICS_WINDOW`
+    /// otherwise
+    pub fn with_statistics_window(mut self, window: Duration) -> Self {
+        self.statistics_window = window;
+        self
+    }
+
+    /// Filter the buffered history, e.g.
+    /// `history_query().axiom(Axiom::Safety).severity_at_least(Severity::High).since(ts)`
+    pub fn history_query(&self) -> HistoryQuery<'_> {
+        HistoryQuery::new(self)
+    }
+
+    /// Statistics of buffered violations of enabled axioms timestamped at or after `since`
+    pub fn statistics_since(&self, since: u64) -> ViolationStatistics {
+        let mut statistics = ViolationStatistics::default();
+        for violation in self.violations_in_range(since, u64::MAX) {
+            statistics.record(&violation);
+        }
+        statistics
+    }
+
+    /// Buffered violations of enabled axioms with `start <= timestamp < end`, oldest
+    /// recorded first
+    pub fn violations_in_range(&self, start: u64, end: u64) -> Vec<Violation> {
+        let Ok(history) = self.violation_history.lock() else {
+            return Vec::new();
+        };
+        history
+            .entries
+            .iter()
+            .filter(|v| (start..end).contains(&v.timestamp) && self.is_enabled(&v.axiom))
+            .cloned()
+            .collect()
+    }
+
+    /// Share of the last `entries` recorded violations of enabled axioms belonging to
+    /// each axiom, for driving alerts; empty when nothing is recorded
+    pub fn recent_axiom_rates(&self, entries: usize) -> BTreeMap<Axiom, f64> {
+        let Ok(history) = self.violation_history.lock() else {
+            return BTreeMap::new();
+        };
+        let enabled = history.entries.iter().rev().filter(|v| self.is_enabled(&v.axiom));
+        let recent: Vec<&Violation> = enabled.take(entries).collect();
+        let mut rates = BTreeMap::new();
+        for violation in &recent {
+            *rates.entry(violation.axiom.clone()).or_insert(0.0) += 1.0;
+        }
+        for rate in rates.values_mut() {
+            *rate /= recent.len() as f64;
+        }
+        rates
+    }
+
+    /// Write the violation history as JSON lines, returning how many were written
+    pub fn export_history(&self, mut writer: impl Write) -> std::io::Result<usize> {
+        let history = match self.violation_history.lock() {
+            Ok(history) => history.to_vec(),
+            Err(_) => return Ok(0),
+        };
+        for violation in &history {
+            writeln!(writer, "{}", violation.to_json())?;
+        }
+        Ok(history.len())
+    }
+
+    /// Write the weights, learning rates, feedback rate scales, threshold and violation
+    /// history as JSON lines:
+    /// a `schema::STATE` header, then one `schema::VIOLATION` line per violation
+    pub fn save_state(&self, writer: impl Write) -> std::io::Result<()> {
+        self.write_state(writer, None)
+    }
+
+    /// Replace the weights, learning rates, threshold and history with state written
+    /// by `save_state`, returning how many violations were restored. Rate scales are
+    /// restored into the feedback policy, so set it first.
+    ///
+    /// Nothing changes unless the whole file reads, so a state naming an axiom this
+    /// build doesn't know fails with the offending line instead of loading partially.
+    /// A history longer than the capacity keeps its newest violations.
+    pub fn load_state(&mut self, reader: impl BufRead) -> Result<usize, StateError> {
+        let (header, history) = Self::read_state(reader)?;
+        Ok(self.apply_state(header, history))
+    }
+
+    /// The state `save_state` writes, as a value for other formats (it implements
+    /// serde's traits with the `serde` feature)
+    pub fn state(&self) -> RegularizerState {
+        RegularizerState {
+            version: schema::STATE.version,
+            learning_rate: self.learning_rate,
+            threshold: self.threshold,
+            weights: self.axiom_weights.iter().map(|(a, w)| (a.clone(), *w)).collect(),
+            learning_rates: self.learning_rates(),
+            rate_scales: self.rate_scales(),
+            history: self.violation_history.lock().map(|h| h.to_vec()).unwrap_or_default(),
+            healer: None,
+        }
+    }
+
+    /// Like `load_state`, from a value taken by `state`; a state from another
+    /// schema version is refused rather than guessed at
+    pub fn restore_state(&mut self, state: RegularizerState) -> Result<usize, StateError> {
+        let (header, history) = state.into_parts()?;
+        Ok(self.apply_state(header, history))
+    }
+
+    fn write_state(&self, mut writer: impl Write, healer: Option<String>) -> std::io::Result<()> {
+        let history = self.violation_history.lock().map(|h| h.to_vec()).unwrap_or_default();
+        let header = import::StateHeader {
+            learning_rate: self.learning_rate,
+            threshold: self.threshold,
+            weights: self.axiom_weights.iter().map(|(a, w)| (a.clone(), *w)).collect(),
+            learning_rates: self.learning_rates(),
+            rate_scales: self.rate_scales(),
+            history: history.len(),
+            healer,
+        };
+        writeln!(writer, "{}", import::state_header_to_json(&header))?;
+        for violation in &history {
+            writeln!(writer, "{}", violation.to_json())?;
+        }
+        Ok(())
+    }
+
+    fn read_state(
+        reader: impl BufRead,
+    ) -> Result<(import::StateHeader, Vec<Violation>), StateError> {
+        let migrations = schema::Migrations::builtin();
+        let mut header = None;
+        let mut history = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if header.is_none() {
+                let parsed = import::state_header_from_json(&line, &migrations);
+                header = Some(parsed.map_err(StateError::Header)?);
+                continue;
+            }
+            let violation = import::violation_from_json(&line, &migrations)
+                .map_err(|error| StateError::History { line: index + 1, error })?;
+            history.push(violation);
+        }
+        let header = header.ok_or_else(|| {
+            StateError::Header(schema::ArtifactError::Malformed("empty state".to_string()))
+        })?;
+        if history.len() != header.history {
+            return Err(StateError::Truncated { expected: header.history, found: history.len() });
+        }
+        Ok((header, history))
+    }
+
+    fn apply_state(&mut self, header: import::StateHeader, history: Vec<Violation>) -> usize {
+        // Custom axioms registered here but absent from the state are dropped too
+        self.axiom_weights = header.weights.into_iter().collect();
+        self.generation += 1;
+        self.axiom_learning_rates = header.learning_rates.into_iter().collect();
+        self.set_rate_scales(header.rate_scales);
+        self.learning_rate = header.learning_rate;
+        self.threshold = header.threshold;
+        match self.violation_history.lock() {
+            Ok(mut current) => {
+                current.replace(history);
+                current.entries.len()
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Remove and return the recorded violation history
+    pub fn drain_history(&self) -> Vec<Violation> {
+        self.violation_history
+            .lock()
+            .map(|mut history| history.take())
+            .unwrap_or_default()
+    }
+
+    /// Default penalty threshold used to seed the healer's threshold policy
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Current time in unix epoch millis, according to the regularizer's clock
+    pub fn current_timestamp(&self) -> u64 {
+        self.clock.now_millis()
+    }
+}
+
+/// A contiguous part of a context, such as one turn of a chat transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Speaker or section label, if the segmenter found one
+    pub role: Option<String>,
+    /// Byte range of the segment's text within the context
+    pub span: Range<usize>,
+}
+
+/// Splits a context into segments that are detected and healed independently.
+///
+/// Text outside every segment span (such as role prefixes) is kept verbatim.
+pub trait Segmenter: Send + Sync {
+    fn segment(&self, context: &str) -> Vec<Segment>;
+}
+
+/// Segments role-prefixed transcripts (`user: ...`, `assistant: ...`) into turns.
+///
+/// A line starting with a known role followed by `:` begins a new turn; following
+/// lines belong to it until the next prefixed line. A turn's span covers its text
+/// after the prefix, excluding the final line break. Lines before the first prefix
+/// form a segment without a role.
+#[derive(Debug, Clone)]
+pub struct RolePrefixSegmenter {
+    roles: Vec<String>,
+}
+
+impl Default for RolePrefixSegmenter {
+    fn default() -> Self {
+        Self::new(["system", "user", "assistant"])
+    }
+}
+
+impl RolePrefixSegmenter {
+    pub fn new<I, S>(roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            roles: roles.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// The role prefixing `line` and the length of the prefix including `:` and one space
+    fn prefix<'a>(&'a self, line: &str) -> Option<(&'a str, usize)> {
+        self.roles.iter().find_map(|role| {
+            let rest = line.strip_prefix(role.as_str())?.strip_prefix(':')?;
+            let space = usize::from(rest.starts_with(' '));
+            Some((role.as_str(), role.len() + 1 + space))
+        })
+    }
+}
+
+impl Segmenter for RolePrefixSegmenter {
+    fn segment(&self, context: &str) -> Vec<Segment> {
+        fn close(segments: &mut Vec<Segment>, current: Option<Segment>, context: &str) {
+            if let Some(mut segment) = current {
+                let text = &context[segment.span.clone()];
+                let trimmed = text.strip_suffix('\n').unwrap_or(text);
+                let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+                segment.span.end = segment.span.start + trimmed.len();
+                segments.push(segment);
+            }
+        }
+
+        let mut segments = Vec::new();
+        let mut current: Option<Segment> = None;
+        let mut offset = 0;
+
+        for line in context.split_inclusive('\n') {
+            let end = offset + line.len();
+            match self.prefix(line) {
+                Some((role, prefix_len)) => {
+                    close(&mut segments, current.take(), context);
+                    current = Some(Segment {
+                        role: Some(role.to_string()),
+                        span: offset + prefix_len..end,
+                    });
+                }
+                None => match current.as_mut() {
+                    Some(segment) => segment.span.end = end,
+                    None => {
+                        current = Some(Segment {
+                            role: None,
+                            span: offset..end,
+                        })
+                    }
+                },
+            }
+            offset = end;
+        }
+        close(&mut segments, current, context);
+        segments
+    }
+}
+
+/// Rebuild a context, substituting the text of segments present in `replacements`.
+///
+/// Segments are taken in span order; overlapping or out-of-bounds spans are ignored,
+/// so unreplaced text always round-trips exactly.
+fn reassemble(
+    context: &str,
+    segments: &[Segment],
+    replacements: &HashMap<usize, String>,
+) -> String {
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by_key(|&i| segments[i].span.start);
+
+    let mut out = String::with_capacity(context.len());
+    let mut cursor = 0;
+    for index in order {
+        let span = &segments[index].span;
+        if span.start < cursor || span.end > context.len() || span.start > span.end {
+            continue;
+        }
+        out.push_str(&context[cursor..span.start]);
+        match replacements.get(&index) {
+            Some(text) => out.push_str(text),
+            None => out.push_str(&context[span.clone()]),
+        }
+        cursor = span.end;
+    }
+    out.push_str(&context[cursor..]);
+    out
+}
+
+/// Decides whether an aggregate penalty is large enough to trigger healing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdPolicy {
+    /// Heal when the penalty is strictly greater than the value
+    HealAbove(f64),
+    /// Heal when the penalty is greater than or equal to the value
+    HealAtOrAbove(f64),
+    /// Heal whenever at least one violation was detected, regardless of penalty
+    HealOnAnyViolation,
+    /// Never heal; violations are only recorded
+    Never,
+}
+
+impl ThresholdPolicy {
+    /// The comparison value, if the policy has one
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            ThresholdPolicy::HealAbove(v) | ThresholdPolicy::HealAtOrAbove(v) => Some(*v),
+            ThresholdPolicy::HealOnAnyViolation | ThresholdPolicy::Never => None,
+        }
+    }
+
+    /// Evaluate the policy for a penalty produced by `violation_count` violations
+    pub fn decide(&self, penalty: f64, violation_count: usize) -> ThresholdDecision {
+        let heal = violation_count > 0
+            && match self {
+                ThresholdPolicy::HealAbove(v) => penalty > *v,
+                ThresholdPolicy::HealAtOrAbove(v) => penalty >= *v,
+                ThresholdPolicy::HealOnAnyViolation => true,
+                ThresholdPolicy::Never => false,
+            };
+
+        ThresholdDecision {
+            policy: *self,
+            penalty,
+            heal,
+        }
+    }
+}
+
+impl fmt::Display for ThresholdPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThresholdPolicy::HealAbove(v) => write!(f, "heal when penalty > {}", v),
+            ThresholdPolicy::HealAtOrAbove(v) => write!(f, "heal when penalty >= {}", v),
+            ThresholdPolicy::HealOnAnyViolation => write!(f, "heal on any violation"),
+            ThresholdPolicy::Never => write!(f, "never heal"),
+        }
+    }
+}
+
+/// Records which policy gated a healing decision and the penalty it was given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdDecision {
+    pub policy: ThresholdPolicy,
+    pub penalty: f64,
+    pub heal: bool,
+}
+
+impl fmt::Display for ThresholdDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.heal { "healing" } else { "not healing" };
+        write!(f, "penalty {} under policy '{}': {}", self.penalty, self.policy, verdict)
+    }
+}
+
+/// What `monitor_and_heal` ended up doing with a context
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealOutcome {
+    /// No violations were detected
+    Clean,
+    /// Violations were recorded but healing was not attempted
+    Recorded,
+    /// Violations were healed
+    Healed,
+    /// Healing was attempted but no strategy succeeded for any violation
+    Unhealed,
+    /// Healing made the penalty worse, so the original context was kept
+    HealRegressed { before: f64, after: f64 },
+}
+
+/// Why a detected violation was left unhealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnhealedReason {
+    /// Every registered strategy failed (or none was registered)
+    StrategiesFailed,
+    /// The call's deadline expired before the violation was attempted
+    DeadlineExceeded,
+    /// The call was cancelled before the violation was attempted
+    Cancelled,
+    /// Healing was not attempted (below threshold or auto-heal disabled)
+    NotAttempted,
+    /// A heal was produced but discarded because it increased the penalty
+    Regressed,
+    /// A heal was produced but discarded because another violation in the context
+    /// could not be healed, under `ExhaustionAction::KeepOriginal`
+    Reverted,
+    /// Strategies improved the context but none reported the violation healed; the
+    /// improvement is kept
+    PartiallyHealed,
+}
+
+/// What to do once a violation budget is used up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetAction {
+    /// Only track consumption
+    RecordOnly,
+    /// Queue a `BudgetAlert` when the budget becomes exhausted
+    RaiseAlert,
+    /// Fail `monitor_and_heal` with `HealError::BudgetExhausted` while exhausted
+    ReturnError,
+}
+
+/// Current consumption of a violation budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetStatus {
+    pub used: usize,
+    pub max: usize,
+    pub window: Duration,
+    pub action: BudgetAction,
+}
+
+impl BudgetStatus {
+    pub fn exhausted(&self) -> bool {
+        self.used >= self.max
+    }
+}
+
+/// Raised when a budget with `BudgetAction::RaiseAlert` becomes exhausted
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetAlert {
+    pub axiom: Axiom,
+    pub status: BudgetStatus,
+    pub timestamp: u64,
+}
+
+/// Windowed allowance of unhealed violations for one axiom
+#[derive(Debug, Clone)]
+struct ViolationBudget {
+    max: usize,
+    window: Duration,
+    action: BudgetAction,
+    trend: TrendBuckets,
+}
+
+impl ViolationBudget {
+    /// Buckets are 1/60th of the window, so expiry is accurate to that granularity
+    const BUCKETS_PER_WINDOW: u32 = 60;
+
+    fn new(max: usize, window: Duration, action: BudgetAction) -> Self {
+        let width = window / Self::BUCKETS_PER_WINDOW;
+        Self {
+            max,
+            window,
+            action,
+            trend: TrendBuckets::new(width, Self::BUCKETS_PER_WINDOW as usize + 1),
+        }
+    }
+
+    fn status(&self, now: u64) -> BudgetStatus {
+        BudgetStatus {
+            used: self.trend.count_within(now, self.window),
+            max: self.max,
+            window: self.window,
+            action: self.action,
+        }
+    }
+}
+
+/// Projected violation count for an axiom, from `AxiomaticSelfHealer::forecast`.
+///
+/// This is a heuristic: an ordinary least-squares line through the complete trend
+/// buckets, extrapolated over the horizon, with a band of two residual standard
+/// deviations scaled by the square root of the number of buckets projected. It assumes
+/// the recent trend continues and knows nothing about seasonality.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Forecast {
+    /// Fewer than three complete buckets have been observed
+    InsufficientData { buckets: usize },
+    Projected {
+        /// Expected number of violations over the horizon
+        expected: f64,
+        /// Lower edge of the band, never below zero
+        low: f64,
+        high: f64,
+        /// Fitted change in violations per bucket from one bucket to the next
+        slope_per_bucket: f64,
+        /// Buckets the fit was made over
+        buckets: usize,
+    },
+}
+
+/// Least-squares fit of `series` against its index: `(intercept, slope, residual sd)`
+fn fit_linear(series: &[f64]) -> (f64, f64, f64) {
+    let n = series.len() as f64;
+    let mean_t = (n - 1.0) / 2.0;
+    let mean_y = series.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (t, y) in series.iter().enumerate() {
+        let dt = t as f64 - mean_t;
+        cov += dt * (y - mean_y);
+        var += dt * dt;
+    }
+    let slope = if var > 0.0 { cov / var } else { 0.0 };
+    let intercept = mean_y - slope * mean_t;
+    let residual: f64 = series
+        .iter()
+        .enumerate()
+        .map(|(t, y)| (y - intercept - slope * t as f64).powi(2))
+        .sum();
+    let sd = (residual / (n - 2.0).max(1.0)).sqrt();
+    (intercept, slope, sd)
+}
+
+/// Cooperative cancellation shared between a caller and in-flight heal calls.
+///
+/// A token may also carry a deadline, after which it reports itself as expired;
+/// this is how `HealOptions::deadline` is enforced.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every call observing this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.interruption().is_some()
+    }
+
+    /// A token sharing this one's cancellation flag that also expires at `deadline`
+    /// (or at its existing deadline, whichever is earlier)
+    pub fn with_deadline(&self, deadline: Instant) -> Self {
+        let deadline = self.deadline.map_or(deadline, |d| d.min(deadline));
+        Self {
+            cancelled: Arc::clone(&self.cancelled),
+            deadline: Some(deadline),
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn interruption(&self) -> Option<UnhealedReason> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            Some(UnhealedReason::Cancelled)
+        } else if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            Some(UnhealedReason::DeadlineExceeded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Per-call options for `monitor_and_heal_with`
+#[derive(Debug, Clone, Default)]
+pub struct HealOptions {
+    /// Bound on the whole call; falls back to the healer's default deadline
+    pub deadline: Option<Instant>,
+    /// Token the caller can use to abandon the call early
+    pub cancel: Option<CancellationToken>,
+    /// Axioms that must be covered by at least one registered detector
+    pub required_axioms: Vec<Axiom>,
+    /// Fail with `HealError::CoverageGap` instead of reporting a gap as a violation
+    pub strict_coverage: bool,
+    /// Fail with `HealError::StrategiesExhausted` or `UserInterventionRequired`
+    /// instead of returning a `HealOutcome::Unhealed` report
+    pub strict_healing: bool,
+}
+
+/// Errors returned by the healer
+#[derive(Debug, Clone)]
+pub enum HealError {
+    /// The deadline expired; the report holds whatever was healed before expiry
+    DeadlineExceeded { partial: Box<HealReport> },
+    /// The call was cancelled; the report holds whatever was healed beforehand
+    Cancelled { partial: Box<HealReport> },
+    /// The axiom's unhealed-violation budget for the window is used up
+    BudgetExhausted { axiom: Axiom, window: Duration },
+    /// Required axioms that no registered detector can produce
+    CoverageGap { missing: Vec<Axiom> },
+    /// Under `HealOptions::strict_healing`, no strategy succeeded for any violation;
+    /// under `ExhaustionAction::ReturnError`, for at least one. The report's
+    /// attempts say why each failed.
+    StrategiesExhausted { partial: Box<HealReport> },
+    /// As `StrategiesExhausted`, where the chains for `axioms` ended by asking the user
+    UserInterventionRequired { axioms: Vec<Axiom>, partial: Box<HealReport> },
+}
+
+impl HealError {
+    /// The partial report, for errors that carry one
+    pub fn partial(&self) -> Option<&HealReport> {
+        match self {
+            HealError::DeadlineExceeded { partial }
+            | HealError::Cancelled { partial }
+            | HealError::StrategiesExhausted { partial }
+            | HealError::UserInterventionRequired { partial, .. } => Some(partial),
+            HealError::BudgetExhausted { .. } | HealError::CoverageGap { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for HealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealError::DeadlineExceeded { partial } => write!(
+                f,
+                "Heal deadline exceeded with {} violation(s) unhealed",
+                partial.unhealed.len()
+            ),
+            HealError::Cancelled { partial } => write!(
+                f,
+                "Heal cancelled with {} violation(s) unhealed",
+                partial.unhealed.len()
+            ),
+            HealError::BudgetExhausted { axiom, window } => write!(
+                f,
+                "Violation budget for {:?} exhausted within {:?} window",
+                axiom, window
+            ),
+            HealError::CoverageGap { missing } => {
+                write!(f, "No detector covers required axiom(s) {:?}", missing)
+            }
+            HealError::StrategiesExhausted { partial } => write!(
+                f,
+                "Every strategy failed for {} violation(s)",
+                partial.unhealed.len()
+            ),
+            HealError::UserInterventionRequired { axioms, .. } => {
+                write!(f, "User intervention required for {:?}", axioms)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HealError {}
+
+/// How text from contexts is quoted in explanations and other diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotePolicy {
+    /// Longer quotes keep their start and end and elide the middle
+    pub max_len: Option<usize>,
+    /// Replace quoted text with its length only
+    pub redact: bool,
+}
+
+impl Default for QuotePolicy {
+    fn default() -> Self {
+        Self {
+            max_len: Some(64),
+            redact: false,
+        }
+    }
+}
+
+impl QuotePolicy {
+    pub fn quote(&self, text: &str) -> String {
+        if self.redact {
+            return format!("<redacted {} chars>", text.chars().count());
+        }
+        match self.max_len {
+            Some(max) => elide_middle(text, max),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Shorten `text` to at most `max` chars by replacing its middle with an ellipsis
+fn elide_middle(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_string();
+    }
+    let keep = max.saturating_sub(1);
+    let head = keep - keep / 2;
+    let tail = keep / 2;
+    let mut out: String = text.chars().take(head).collect();
+    out.push('…');
+    out.extend(text.chars().skip(len - tail));
+    out
+}
+
+/// The minimal edit turning one text into another, with quotes already shortened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChange {
+    /// Byte offset of the edit in the original text
+    pub offset: usize,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl TextChange {
+    fn between(before: &str, after: &str, quotes: &QuotePolicy) -> Option<Self> {
+        if before == after {
+            return None;
+        }
+        let prefix = before
+            .char_indices()
+            .zip(after.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((i, c), _)| i + c.len_utf8());
+        let (before_rest, after_rest) = (&before[prefix..], &after[prefix..]);
+        let suffix = before_rest
+            .chars()
+            .rev()
+            .zip(after_rest.chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum::<usize>();
+        let suffix = suffix.min(before_rest.len()).min(after_rest.len());
+
+        Some(Self {
+            offset: prefix,
+            removed: quotes.quote(&before_rest[..before_rest.len() - suffix]),
+            inserted: quotes.quote(&after_rest[..after_rest.len() - suffix]),
+        })
+    }
+}
+
+impl fmt::Display for TextChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.removed.is_empty(), self.inserted.is_empty()) {
+            (true, _) => write!(f, "inserted {:?} at byte {}", self.inserted, self.offset),
+            (false, true) => write!(f, "removed {:?} at byte {}", self.removed, self.offset),
+            (false, false) => write!(
+                f,
+                "replaced {:?} with {:?} at byte {}",
+                self.removed, self.inserted, self.offset
+            ),
+        }
+    }
+}
+
+/// One strategy tried against one violation
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyAttempt {
+    pub strategy: &'static str,
+    /// `Err` carries the reason the strategy gave for failing
+    pub result: Result<(), String>,
+}
+
+/// Why a strategy was (or was not) chosen for a violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionReason {
+    /// The first strategy in the axiom's chain order that succeeded
+    FirstInChainOrder,
+    /// No strategy is registered for the axiom
+    NoStrategies,
+    /// Every strategy in the chain failed
+    AllFailed,
+    /// The call was cancelled or ran out of time first
+    Interrupted,
+    /// A strategy asked for escalation, so the rest of the chain was skipped
+    Escalated,
+    /// Strategies improved the context without any reporting the violation healed
+    PartiallyHealed,
+}
+
+/// The strategies tried for one violation and what came of them
+#[derive(Clone, PartialEq)]
+pub struct ViolationAttempts {
+    pub violation: Violation,
+    pub attempts: Vec<StrategyAttempt>,
+    pub chosen: Option<&'static str>,
+    pub selection: SelectionReason,
+    pub change: Option<TextChange>,
+}
+
+impl fmt::Debug for ViolationAttempts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ContextDebug(self, DEBUG_CONTEXT_CHARS).fmt(f)
+    }
+}
+
+/// Structured account of how one violation was detected and handled
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub axiom: Axiom,
+    pub severity: Severity,
+    /// Rule or check that produced the violation
+    pub detected_by: String,
+    pub attempts: Vec<StrategyAttempt>,
+    pub chosen: Option<&'static str>,
+    pub selection: SelectionReason,
+    pub change: Option<TextChange>,
+    /// Penalty before and after, when the heal was discarded as a regression
+    pub regressed: Option<(f64, f64)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} violation ({:?}) detected by {}, which assigns that severity.",
+            self.axiom, self.severity, self.detected_by
+        )?;
+        if !self.attempts.is_empty() {
+            let tried: Vec<String> = self
+                .attempts
+                .iter()
+                .map(|a| match &a.result {
+                    Ok(()) => format!("{} (succeeded)", a.strategy),
+                    Err(reason) => format!("{} (failed: {})", a.strategy, reason),
+                })
+                .collect();
+            write!(f, " Tried {}.", tried.join(", "))?;
+        }
+        match (self.selection, self.chosen) {
+            (SelectionReason::FirstInChainOrder, Some(name)) => write!(
+                f,
+                " Chose {} as the first strategy in chain order to succeed.",
+                name
+            )?,
+            (SelectionReason::NoStrategies, _) => {
+                write!(f, " No strategy is registered for this axiom.")?
+            }
+            (SelectionReason::Interrupted, _) => {
+                write!(f, " Stopped before a strategy succeeded: the call was interrupted.")?
+            }
+            (SelectionReason::Escalated, _) => {
+                write!(f, " Escalated for intervention without trying further strategies.")?
+            }
+            (SelectionReason::PartiallyHealed, Some(name)) => {
+                write!(f, " Only partially healed, last by {}.", name)?
+            }
+            _ => write!(f, " No strategy succeeded.")?,
+        }
+        if let Some(change) = &self.change {
+            write!(f, " Change: {}.", change)?;
+        }
+        if let Some((before, after)) = self.regressed {
+            write!(
+                f,
+                " The heal was discarded because it raised the penalty from {} to {}.",
+                before, after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-strategy bookkeeping of healing attempts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyStats {
+    pub attempts: usize,
+    pub successes: usize,
+    pub failures: usize,
+    /// Heals this strategy contributed to that verification rejected as regressions
+    pub regressions: usize,
+    /// Failed attempts whose output broke the strategy's contract
+    pub contract_violations: usize,
+}
+
+/// Why applying a strategy produced no usable output
+#[derive(Debug, Clone, PartialEq)]
+enum StrategyFailure {
+    /// The strategy itself reported failure
+    Failed(String),
+    Contract(ContractViolation),
+}
+
+impl fmt::Display for StrategyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrategyFailure::Failed(reason) => f.write_str(reason),
+            StrategyFailure::Contract(broken) => write!(f, "contract violation: {}", broken),
+        }
+    }
+}
+
+impl StrategyStats {
+    pub fn merge(&mut self, other: &StrategyStats) {
+        self.attempts += other.attempts;
+        self.successes += other.successes;
+        self.failures += other.failures;
+        self.regressions += other.regressions;
+        self.contract_violations += other.contract_violations;
+    }
+}
+
+/// Detailed result of a `monitor_and_heal` call.
+///
+/// Like `Violation`, `Debug` shortens contexts; see `debug_full`.
+#[derive(Clone)]
+pub struct HealReport {
+    /// The (possibly healed) context
+    pub context: String,
+    /// Violations detected in the original context
+    pub violations: Vec<Violation>,
+    /// Aggregate penalty of the detected violations
+    pub penalty: f64,
+    /// The threshold decision that gated healing
+    pub decision: ThresholdDecision,
+    pub outcome: HealOutcome,
+    /// Violations that were not healed, and why
+    pub unhealed: Vec<(Violation, UnhealedReason)>,
+    /// Strategies tried for each violation that healing was attempted on
+    pub attempts: Vec<ViolationAttempts>,
+    /// Detection was skipped because the context was known to be clean
+    pub skipped_known_clean: bool,
+    /// Detection passes run on the original context
+    pub tiers: TiersRun,
+}
+
+impl fmt::Debug for ContextDebug<'_, ViolationAttempts> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ContextDebug(a, max) = *self;
+        f.debug_struct("ViolationAttempts")
+            .field("violation", &ContextDebug(&a.violation, max))
+            .field("attempts", &a.attempts)
+            .field("chosen", &a.chosen)
+            .field("selection", &a.selection)
+            .field("change", &a.change)
+            .finish()
+    }
+}
+
+impl fmt::Debug for ContextDebug<'_, HealReport> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ContextDebug(r, max) = *self;
+        let violations: Vec<_> = r.violations.iter().map(|v| ContextDebug(v, max)).collect();
+        let unhealed: Vec<_> = r
+            .unhealed
+            .iter()
+            .map(|(v, reason)| (ContextDebug(v, max), reason))
+            .collect();
+        let attempts: Vec<_> = r.attempts.iter().map(|a| ContextDebug(a, max)).collect();
+        f.debug_struct("HealReport")
+            .field("context", &elide_middle(&r.context, max))
+            .field("violations", &violations)
+            .field("penalty", &r.penalty)
+            .field("decision", &r.decision)
+            .field("outcome", &r.outcome)
+            .field("unhealed", &unhealed)
+            .field("attempts", &attempts)
+            .field("skipped_known_clean", &r.skipped_known_clean)
+            .field("tiers", &r.tiers)
+            .finish()
+    }
+}
+
+impl fmt::Debug for HealReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ContextDebug(self, DEBUG_CONTEXT_CHARS).fmt(f)
+    }
+}
+
+impl HealReport {
+    /// `Debug` rendering with every context in full
+    pub fn debug_full(&self) -> impl fmt::Debug + '_ {
+        ContextDebug(self, usize::MAX)
+    }
+
+    /// Explain each violation healing was attempted on
+    pub fn explanations(&self) -> Vec<Explanation> {
+        let regressed = match self.outcome {
+            HealOutcome::HealRegressed { before, after } => Some((before, after)),
+            _ => None,
+        };
+        self.attempts
+            .iter()
+            .map(|entry| Explanation {
+                axiom: entry.violation.axiom.clone(),
+                severity: entry.violation.severity,
+                detected_by: entry
+                    .violation
+                    .metadata
+                    .get("rule")
+                    .cloned()
+                    .unwrap_or_else(|| "an unnamed detector".to_string()),
+                attempts: entry.attempts.clone(),
+                chosen: entry.chosen,
+                selection: entry.selection,
+                change: entry.change.clone(),
+                regressed: regressed.filter(|_| entry.chosen.is_some()),
+            })
+            .collect()
+    }
+
+    /// Replace every copy of the context text with its redacted quote
+    fn redact(&mut self) {
+        let quotes = QuotePolicy { max_len: None, redact: true };
+        let redact_violation = |violation: &mut Violation| {
+            violation.context = quotes.quote(&violation.context);
+            violation.metadata.remove("span");
+        };
+        self.context = quotes.quote(&self.context);
+        self.violations.iter_mut().for_each(redact_violation);
+        self.unhealed.iter_mut().for_each(|(violation, _)| redact_violation(violation));
+        for entry in &mut self.attempts {
+            redact_violation(&mut entry.violation);
+            if let Some(change) = &mut entry.change {
+                change.removed = quotes.quote(&change.removed);
+                change.inserted = quotes.quote(&change.inserted);
+            }
+        }
+    }
+}
+
+/// Intermediate result of applying strategies to a set of violations
+struct HealPass {
+    context: String,
+    applied: Vec<&'static str>,
+    unhealed: Vec<(Violation, UnhealedReason)>,
+    attempts: Vec<ViolationAttempts>,
+    interrupted: Option<UnhealedReason>,
+}
+
+/// One violation's walk along its strategy chain
+struct ChainRun {
+    entry: ViolationAttempts,
+    /// The context after every strategy that applied
+    context: String,
+    applied: Vec<&'static str>,
+    interrupted: Option<UnhealedReason>,
+    /// Strategies whose output broke their contract, once per try
+    contracts_broken: Vec<&'static str>,
+}
+
+/// Prometheus' default histogram buckets, in seconds
+pub const DEFAULT_LATENCY_BUCKETS: [f64; 11] =
+    [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Fixed-bucket latency histogram; recording is a few relaxed atomic increments
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    /// Inclusive upper bounds, ascending; an implicit `+Inf` bucket follows
+    bounds: Arc<[Duration]>,
+    counts: Box<[AtomicU64]>,
+    sum_nanos: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn new(bounds: Arc<[Duration]>) -> Self {
+        let counts = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+        Self {
+            bounds,
+            counts,
+            sum_nanos: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, elapsed: Duration) {
+        let bucket = self.bounds.partition_point(|bound| *bound < elapsed);
+        self.counts[bucket].fetch_add(1, Ordering::Relaxed);
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.sum_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        let mut cumulative = 0;
+        let counts = self
+            .counts
+            .iter()
+            .map(|count| {
+                cumulative += count.load(Ordering::Relaxed);
+                cumulative
+            })
+            .collect();
+        HistogramSnapshot {
+            bounds: self.bounds.to_vec(),
+            cumulative_counts: counts,
+            sum: Duration::from_nanos(self.sum_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Point-in-time copy of a `LatencyHistogram`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramSnapshot {
+    pub bounds: Vec<Duration>,
+    /// Observations at or below each bound, then the total (`+Inf`)
+    pub cumulative_counts: Vec<u64>,
+    pub sum: Duration,
+}
+
+impl HistogramSnapshot {
+    pub fn count(&self) -> u64 {
+        self.cumulative_counts.last().copied().unwrap_or(0)
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        use std::fmt::Write as _;
+        let sep = if labels.is_empty() { "" } else { "," };
+        let les = self.bounds.iter().map(|b| b.as_secs_f64().to_string());
+        for (le, count) in les.chain(["+Inf".to_string()]).zip(&self.cumulative_counts) {
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, sep, le, count);
+        }
+        let braced = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, braced, self.sum.as_secs_f64());
+        let _ = writeln!(out, "{}_count{} {}", name, braced, self.count());
+    }
+}
+
+/// Bucket bounds for the aggregate penalty of violating contexts
+pub const DEFAULT_PENALTY_BUCKETS: [f64; 8] = [0.5, 1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0];
+
+/// Aggregate penalties of heal calls that found violations
+#[derive(Debug, Clone, PartialEq)]
+pub struct PenaltyHistogram {
+    /// Inclusive upper bounds, ascending
+    pub bounds: Vec<f64>,
+    /// Observations at or below each bound, then the total (`+Inf`)
+    pub cumulative_counts: Vec<u64>,
+    pub sum: f64,
+}
+
+impl PenaltyHistogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|b| b.is_finite()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        Self { cumulative_counts: vec![0; bounds.len() + 1], bounds, sum: 0.0 }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.cumulative_counts.last().copied().unwrap_or(0)
+    }
+
+    fn record(&mut self, penalty: f64) {
+        let bucket = self.bounds.partition_point(|bound| *bound < penalty);
+        for count in &mut self.cumulative_counts[bucket..] {
+            *count += 1;
+        }
+        self.sum += penalty;
+    }
+
+    fn render(&self, out: &mut String, name: &str) {
+        use std::fmt::Write as _;
+        let les = self.bounds.iter().map(|b| b.to_string());
+        for (le, count) in les.chain(["+Inf".to_string()]).zip(&self.cumulative_counts) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, le, count);
+        }
+        let _ = writeln!(out, "{}_sum {}", name, self.sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count());
+    }
+}
+
+/// A Prometheus label value, escaped so custom axiom names can't break the line
+fn prometheus_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Heal-call latency, by the axiom of the most severe violation present, with
+/// violation counts and penalties
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealMetrics {
+    pub by_axiom: BTreeMap<Axiom, HistogramSnapshot>,
+    /// Calls that found no violations
+    pub clean: HistogramSnapshot,
+    /// Violations detected so far, by axiom and severity
+    pub violations: BTreeMap<(Axiom, Severity), u64>,
+    pub penalty: PenaltyHistogram,
+    /// `None` unless the known-clean filter is enabled
+    pub known_clean: Option<KnownCleanStats>,
+    /// `None` while every rule runs on every context
+    pub tiering: Option<TieringStats>,
+}
+
+impl HealMetrics {
+    /// Render in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str(
+            "# HELP selfheal_heal_duration_seconds Heal call latency by the axiom of the most \
+             severe violation.\n# TYPE selfheal_heal_duration_seconds histogram\n",
+        );
+        for (axiom, histogram) in &self.by_axiom {
+            let labels = format!("axiom=\"{}\"", prometheus_label(axiom.name()));
+            histogram.render(&mut out, "selfheal_heal_duration_seconds", &labels);
+        }
+        out.push_str(
+            "# HELP selfheal_clean_duration_seconds Latency of calls that found no \
+             violations.\n# TYPE selfheal_clean_duration_seconds histogram\n",
+        );
+        self.clean.render(&mut out, "selfheal_clean_duration_seconds", "");
+        out.push_str(
+            "# HELP selfheal_violations_total Violations detected by axiom and severity.\n\
+             # TYPE selfheal_violations_total counter\n",
+        );
+        for ((axiom, severity), count) in &self.violations {
+            out.push_str(&format!(
+                "selfheal_violations_total{{axiom=\"{}\",severity=\"{:?}\"}} {}\n",
+                prometheus_label(axiom.name()),
+                severity,
+                count
+            ));
+        }
+        out.push_str(
+            "# HELP selfheal_penalty Aggregate penalty of calls that found violations.\n\
+             # TYPE selfheal_penalty histogram\n",
+        );
+        self.penalty.render(&mut out, "selfheal_penalty");
+        if let Some(stats) = &self.known_clean {
+            out.push_str(
+                "# HELP selfheal_known_clean_total Known-clean filter activity by kind.\n\
+                 # TYPE selfheal_known_clean_total counter\n",
+            );
+            for (kind, count) in [
+                ("hit", stats.hits),
+                ("skip", stats.skipped),
+                ("recheck", stats.rechecked),
+                ("false_positive", stats.false_positives),
+                ("invalidation", stats.invalidations),
+            ] {
+                let line = format!("selfheal_known_clean_total{{kind=\"{}\"}} {}\n", kind, count);
+                out.push_str(&line);
+            }
+        }
+        if let Some(stats) = &self.tiering {
+            out.push_str(
+                "# HELP selfheal_detection_passes_total Detection passes run by tier and \
+                 reason.\n# TYPE selfheal_detection_passes_total counter\n",
+            );
+            for (tier, reason, count) in [
+                ("fast", "always", stats.fast_passes),
+                ("thorough", "hit", stats.escalated_on_hit),
+                ("thorough", "sample", stats.sampled),
+            ] {
+                out.push_str(&format!(
+                    "selfheal_detection_passes_total{{tier=\"{}\",reason=\"{}\"}} {}\n",
+                    tier, reason, count
+                ));
+            }
+            out.push_str(
+                "# HELP selfheal_fast_tier_misses_total Sampled contexts the fast pass found \
+                 clean and the thorough pass did not.\n\
+                 # TYPE selfheal_fast_tier_misses_total counter\n",
+            );
+            out.push_str(&format!("selfheal_fast_tier_misses_total {}\n", stats.fast_misses));
+        }
+        out
+    }
+}
+
+/// The healer's heal-call metrics; latency histograms all share one set of bounds
+#[derive(Debug)]
+struct LatencyMetrics {
+    bounds: Arc<[Duration]>,
+    by_axiom: HashMap<Axiom, LatencyHistogram>,
+    clean: LatencyHistogram,
+    violations: HashMap<(Axiom, Severity), u64>,
+    penalty: PenaltyHistogram,
+}
+
+impl LatencyMetrics {
+    fn new(bounds: &[Duration]) -> Self {
+        let mut sorted = bounds.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        let bounds: Arc<[Duration]> = sorted.into();
+        Self {
+            clean: LatencyHistogram::new(Arc::clone(&bounds)),
+            by_axiom: HashMap::new(),
+            bounds,
+            violations: HashMap::new(),
+            penalty: PenaltyHistogram::new(&DEFAULT_PENALTY_BUCKETS),
+        }
+    }
+
+    fn record(&mut self, report: &HealReport, elapsed: Duration) {
+        for violation in &report.violations {
+            let key = (violation.axiom.clone(), violation.severity);
+            *self.violations.entry(key).or_insert(0) += 1;
+        }
+        if !report.violations.is_empty() {
+            self.penalty.record(report.penalty);
+        }
+        let worst = report.violations.iter().reduce(|worst, v| {
+            if v.severity > worst.severity { v } else { worst }
+        });
+        match worst {
+            None => self.clean.record(elapsed),
+            Some(violation) => self
+                .by_axiom
+                .entry(violation.axiom.clone())
+                .or_insert_with(|| LatencyHistogram::new(Arc::clone(&self.bounds)))
+                .record(elapsed),
+        }
+    }
+}
+
+/// Where a tracked context is in its healing lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContextState {
+    /// Last seen without violations, and never healed since
+    Clean,
+    Violating,
+    /// A heal was attempted; only visible in transitions
+    Healing,
+    /// Healed, and clean whenever seen since
+    Healed,
+    /// Violating again after having been healed; healing isn't sticking
+    Regressed,
+}
+
+impl ContextState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContextState::Clean => "clean",
+            ContextState::Violating => "violating",
+            ContextState::Healing => "healing",
+            ContextState::Healed => "healed",
+            ContextState::Regressed => "regressed",
+        }
+    }
+
+    /// Inverse of `name`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clean" => Some(ContextState::Clean),
+            "violating" => Some(ContextState::Violating),
+            "healing" => Some(ContextState::Healing),
+            "healed" => Some(ContextState::Healed),
+            "regressed" => Some(ContextState::Regressed),
+            _ => None,
+        }
+    }
+}
+
+/// A context text together with the caller's key for the document it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Context<'a> {
+    pub key: &'a str,
+    pub text: &'a str,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(key: &'a str, text: &'a str) -> Self {
+        Self { key, text }
+    }
+}
+
+/// Structured state whose text fields can be healed one by one, so violations point
+/// at a field and strategies only ever rewrite that field
+pub trait Healable {
+    /// Each text field with the path that names it, e.g. `answer` or `steps.2`
+    fn fields(&self) -> Vec<(String, String)>;
+
+    /// Replace the field at `path`; only paths returned by `fields` are passed
+    fn set_field(&mut self, path: &str, value: String) -> Result<(), String>;
+
+    /// Whether the field's text is kept out of the reports `monitor_and_heal_fields`
+    /// returns; the healer's history and sinks still see it
+    fn redacted(&self, _path: &str) -> bool {
+        false
+    }
+}
+
+impl Healable for BTreeMap<String, String> {
+    fn fields(&self) -> Vec<(String, String)> {
+        self.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    fn set_field(&mut self, path: &str, value: String) -> Result<(), String> {
+        match self.get_mut(path) {
+            Some(field) => {
+                *field = value;
+                Ok(())
+            }
+            None => Err(format!("no field '{}'", path)),
+        }
+    }
+}
+
+impl Healable for Vec<String> {
+    fn fields(&self) -> Vec<(String, String)> {
+        self.iter().enumerate().map(|(i, text)| (i.to_string(), text.clone())).collect()
+    }
+
+    fn set_field(&mut self, path: &str, value: String) -> Result<(), String> {
+        let field = path.parse::<usize>().ok().and_then(|i| self.get_mut(i));
+        match field {
+            Some(field) => {
+                *field = value;
+                Ok(())
+            }
+            None => Err(format!("no field '{}'", path)),
+        }
+    }
+}
+
+/// Outcome of `monitor_and_heal_fields`: a report per field, in `fields` order.
+///
+/// Each report's violations carry the field's path as `field` metadata.
+#[derive(Debug, Clone)]
+pub struct FieldReports {
+    pub fields: Vec<(String, HealReport)>,
+}
+
+impl FieldReports {
+    /// Violations of every field
+    pub fn violations(&self) -> impl Iterator<Item = &Violation> {
+        self.fields.iter().flat_map(|(_, report)| &report.violations)
+    }
+
+    /// Paths of the fields healing rewrote
+    pub fn changed(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(_, report)| report.outcome == HealOutcome::Healed)
+            .map(|(path, _)| path.as_str())
+            .collect()
+    }
+}
+
+/// Why `monitor_and_heal_fields` left the value unchanged
+#[derive(Debug, Clone)]
+pub enum FieldHealError {
+    /// Healing the field at `path` failed
+    Heal { path: String, error: HealError },
+    /// The value refused the healed text for the field at `path`
+    SetField { path: String, reason: String },
+}
+
+impl fmt::Display for FieldHealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldHealError::Heal { path, error } => write!(f, "Field '{}': {}", path, error),
+            FieldHealError::SetField { path, reason } => {
+                write!(f, "Field '{}' could not be updated: {}", path, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FieldHealError {}
+
+/// One lifecycle transition of a tracked context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextTransition {
+    pub key: String,
+    pub from: ContextState,
+    pub to: ContextState,
+    pub timestamp: u64,
+}
+
+/// Lifecycle state of one tracked context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextRecord {
+    pub state: ContextState,
+    /// Timestamp of the last observation, used for eviction
+    pub last_seen: u64,
+    /// Most recent transitions, oldest first
+    pub history: VecDeque<ContextTransition>,
+}
+
+/// Lifecycle state per caller-supplied context key, updated by `monitor_and_heal_ctx`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextTracker {
+    records: HashMap<String, ContextRecord>,
+    history_capacity: usize,
+}
+
+impl Default for ContextTracker {
+    fn default() -> Self {
+        Self {
+            records: HashMap::new(),
+            history_capacity: 16,
+        }
+    }
+}
+
+impl ContextTracker {
+    /// Keep at most `capacity` transitions per key
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity.max(1);
+        for record in self.records.values_mut() {
+            while record.history.len() > self.history_capacity {
+                record.history.pop_front();
+            }
+        }
+    }
+
+    /// Current state of `key`, or `None` if it isn't tracked
+    pub fn state(&self, key: &str) -> Option<ContextState> {
+        self.records.get(key).map(|record| record.state)
+    }
+
+    /// Recent transitions of `key`, oldest first
+    pub fn history(&self, key: &str) -> Vec<ContextTransition> {
+        self.records
+            .get(key)
+            .map(|record| record.history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Keys currently in `state`, sorted
+    pub fn keys_in(&self, state: ContextState) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .records
+            .iter()
+            .filter(|(_, record)| record.state == state)
+            .map(|(key, _)| key.as_str())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Forget keys not observed since `cutoff`, returning how many were evicted
+    pub fn evict_seen_before(&mut self, cutoff: u64) -> usize {
+        let before = self.records.len();
+        self.records.retain(|_, record| record.last_seen >= cutoff);
+        before - self.records.len()
+    }
+
+    /// Every tracked record, sorted by key
+    pub fn records(&self) -> BTreeMap<String, ContextRecord> {
+        self.records.iter().map(|(k, r)| (k.clone(), r.clone())).collect()
+    }
+
+    /// Replace the tracked records, e.g. from a snapshot
+    pub fn set_records(&mut self, records: BTreeMap<String, ContextRecord>) {
+        self.records = records.into_iter().collect();
+        self.set_history_capacity(self.history_capacity);
+    }
+
+    /// Advance `key`'s lifecycle from a heal report, returning the transitions made
+    fn observe(&mut self, key: &str, report: &HealReport, now: u64) -> Vec<ContextTransition> {
+        let capacity = self.history_capacity;
+        let record = self.records.entry(key.to_string()).or_insert_with(|| ContextRecord {
+            state: ContextState::Clean,
+            last_seen: now,
+            history: VecDeque::new(),
+        });
+        record.last_seen = now;
+
+        let previous = record.state;
+        let mut path = Vec::new();
+        if report.violations.is_empty() {
+            path.push(match previous {
+                ContextState::Healed => ContextState::Healed,
+                _ => ContextState::Clean,
+            });
+        } else {
+            let detected = match previous {
+                ContextState::Healed | ContextState::Regressed => ContextState::Regressed,
+                _ => ContextState::Violating,
+            };
+            path.push(detected);
+            match report.outcome {
+                HealOutcome::Healed => path.extend([ContextState::Healing, ContextState::Healed]),
+                HealOutcome::HealRegressed { .. } | HealOutcome::Unhealed => {
+                    path.extend([ContextState::Healing, detected])
+                }
+                HealOutcome::Clean | HealOutcome::Recorded => {}
+            }
+        }
+
+        let mut transitions = Vec::new();
+        for to in path {
+            if to == record.state {
+                continue;
+            }
+            let transition = ContextTransition {
+                key: key.to_string(),
+                from: record.state,
+                to,
+                timestamp: now,
+            };
+            record.state = to;
+            if record.history.len() >= capacity {
+                record.history.pop_front();
+            }
+            record.history.push_back(transition.clone());
+            transitions.push(transition);
+        }
+        transitions
+    }
+}
+
+/// Escape a key so it fits on one snapshot line
+fn escape_line(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Inverse of `escape_line`
+fn unescape_line(text: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            _ => return Err(format!("bad escape in '{}'", text)),
+        }
+    }
+    Ok(out)
+}
+
+/// How `AxiomaticSelfHealer::audit` picks which messages to run detection on
+#[derive(Debug, Clone, PartialEq)]
+pub enum DetectionSampling {
+    /// Check each message with probability `rate`, from a generator seeded with `seed`
+    Probabilistic { rate: f64, seed: u64 },
+    /// Check the first message and every `n`th one after it
+    EveryNth(u64),
+    Adaptive(AdaptiveSampling),
+}
+
+/// Parameters of adaptive sampling.
+///
+/// After each sampled message, if more than `threshold` of the last `window` samples
+/// were violating, the rate doubles (up to `max_rate`); otherwise it decays by `decay`
+/// back towards `base_rate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveSampling {
+    pub base_rate: f64,
+    pub max_rate: f64,
+    pub threshold: f64,
+    pub window: usize,
+    pub decay: f64,
+    pub seed: u64,
+}
+
+/// Counts kept by a `DetectionSampler`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SamplingStats {
+    pub seen: u64,
+    /// Messages detection ran on, including bypasses
+    pub checked: u64,
+    pub sampled_out: u64,
+    /// Checked regardless of sampling because their fingerprint was known to violate
+    pub bypassed: u64,
+    /// Checked messages that had violations
+    pub violating: u64,
+    /// Violating messages extrapolated to all traffic, weighting each sample by the
+    /// inverse of the rate it was sampled at
+    pub estimated_violating: f64,
+}
+
+/// SplitMix64, good enough for sampling decisions and reproducible from a seed
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Bloom filter over context fingerprints
+#[derive(Debug, Clone)]
+struct FingerprintBloom {
+    bits: Vec<u64>,
+    hashes: u64,
+}
+
+impl FingerprintBloom {
+    const DEFAULT_BITS: u64 = 1 << 16;
+
+    fn new() -> Self {
+        Self { bits: vec![0; (Self::DEFAULT_BITS / 64) as usize], hashes: 3 }
+    }
+
+    /// Sized for `expected` entries at roughly `false_positive_rate`
+    fn with_capacity(expected: usize, false_positive_rate: f64) -> Self {
+        let expected = expected.max(1) as f64;
+        let rate = false_positive_rate.clamp(1e-9, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-expected * rate.ln() / (ln2 * ln2)).ceil().max(64.0);
+        let hashes = (bits / expected * ln2).round().clamp(1.0, 16.0);
+        Self { bits: vec![0; (bits as usize).div_ceil(64)], hashes: hashes as u64 }
+    }
+
+    fn positions(&self, fingerprint: u64) -> impl Iterator<Item = u64> {
+        // Double hashing from the two halves of the fingerprint
+        let (h1, h2) = (fingerprint & 0xffff_ffff, (fingerprint >> 32) | 1);
+        let len = self.bits.len() as u64 * 64;
+        (0..self.hashes).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % len)
+    }
+
+    fn insert(&mut self, fingerprint: u64) {
+        for bit in self.positions(fingerprint).collect::<Vec<_>>() {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    fn contains(&self, fingerprint: u64) -> bool {
+        self.positions(fingerprint)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+    }
+}
+
+/// Configuration of the healer's known-clean filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownCleanConfig {
+    /// Distinct clean contexts the filter is sized for
+    pub expected_contexts: usize,
+    pub false_positive_rate: f64,
+    /// Fraction of filter hits that are detected anyway, to catch false positives
+    pub verify_sample_rate: f64,
+    pub seed: u64,
+}
+
+impl Default for KnownCleanConfig {
+    fn default() -> Self {
+        Self {
+            expected_contexts: 100_000,
+            false_positive_rate: 0.001,
+            verify_sample_rate: 0.01,
+            seed: 0,
+        }
+    }
+}
+
+/// Counters of the known-clean filter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KnownCleanStats {
+    /// Calls whose context was in the filter
+    pub hits: u64,
+    /// Hits that skipped detection
+    pub skipped: u64,
+    /// Hits detected anyway under `verify_sample_rate`
+    pub rechecked: u64,
+    /// Rechecks that found violations, i.e. observed false positives
+    pub false_positives: u64,
+    pub inserted: u64,
+    /// Times the filter was cleared because rules or configuration changed
+    pub invalidations: u64,
+}
+
+/// Fingerprints of contexts already found clean under a given rule generation
+#[derive(Debug, Clone)]
+struct KnownCleanFilter {
+    config: KnownCleanConfig,
+    bloom: FingerprintBloom,
+    rng: SplitMix64,
+    generation: u64,
+    stats: KnownCleanStats,
+}
+
+/// What the known-clean filter says about an incoming context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KnownClean {
+    Unknown,
+    Skip,
+    Recheck,
+}
+
+impl KnownCleanFilter {
+    fn new(config: KnownCleanConfig, generation: u64) -> Self {
+        Self {
+            bloom: FingerprintBloom::with_capacity(
+                config.expected_contexts,
+                config.false_positive_rate,
+            ),
+            rng: SplitMix64(config.seed),
+            config,
+            generation,
+            stats: KnownCleanStats::default(),
+        }
+    }
+
+    /// Forget every entry if the rules changed since they were inserted
+    fn sync(&mut self, generation: u64) {
+        if generation != self.generation {
+            self.invalidate();
+            self.generation = generation;
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.bloom.clear();
+        self.stats.invalidations += 1;
+    }
+
+    fn check(&mut self, fingerprint: u64) -> KnownClean {
+        if !self.bloom.contains(fingerprint) {
+            return KnownClean::Unknown;
+        }
+        self.stats.hits += 1;
+        if self.rng.next_f64() < self.config.verify_sample_rate {
+            self.stats.rechecked += 1;
+            KnownClean::Recheck
+        } else {
+            self.stats.skipped += 1;
+            KnownClean::Skip
+        }
+    }
+}
+
+/// Whether a message should go through detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleDecision {
+    /// Sampled, at the given rate
+    Check { rate: f64 },
+    /// Its fingerprint was seen violating before, so it is always checked
+    Bypass,
+    Skip,
+}
+
+/// Sampling state for audit-mode detection
+#[derive(Debug, Clone)]
+pub struct DetectionSampler {
+    policy: DetectionSampling,
+    rng: SplitMix64,
+    rate: f64,
+    recent: VecDeque<bool>,
+    known_violating: FingerprintBloom,
+    stats: SamplingStats,
+}
+
+impl DetectionSampler {
+    pub fn new(policy: DetectionSampling) -> Self {
+        let (seed, rate) = match &policy {
+            DetectionSampling::Probabilistic { rate, seed } => (*seed, *rate),
+            DetectionSampling::EveryNth(n) => (0, 1.0 / (*n).max(1) as f64),
+            DetectionSampling::Adaptive(adaptive) => (adaptive.seed, adaptive.base_rate),
+        };
+        Self {
+            policy,
+            rng: SplitMix64(seed),
+            rate: rate.clamp(0.0, 1.0),
+            recent: VecDeque::new(),
+            known_violating: FingerprintBloom::new(),
+            stats: SamplingStats::default(),
+        }
+    }
+
+    /// Current sampling rate; fixed except under adaptive sampling
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn stats(&self) -> &SamplingStats {
+        &self.stats
+    }
+
+    /// Decide whether to check `context`, counting it as seen
+    pub fn decide(&mut self, context: &str) -> SampleDecision {
+        let index = self.stats.seen;
+        self.stats.seen += 1;
+        let decision = if self.known_violating.contains(fingerprint(context)) {
+            SampleDecision::Bypass
+        } else {
+            let sampled = match &self.policy {
+                DetectionSampling::EveryNth(n) => index.is_multiple_of((*n).max(1)),
+                _ => self.rng.next_f64() < self.rate,
+            };
+            if sampled {
+                SampleDecision::Check { rate: self.rate }
+            } else {
+                SampleDecision::Skip
+            }
+        };
+        match decision {
+            SampleDecision::Skip => self.stats.sampled_out += 1,
+            SampleDecision::Bypass => {
+                self.stats.checked += 1;
+                self.stats.bypassed += 1;
+            }
+            SampleDecision::Check { .. } => self.stats.checked += 1,
+        }
+        decision
+    }
+
+    /// Feed back what detection found for a message `decide` chose to check
+    pub fn record(&mut self, context: &str, decision: SampleDecision, violating: bool) {
+        if violating {
+            self.stats.violating += 1;
+            self.known_violating.insert(fingerprint(context));
+        }
+        let rate = match decision {
+            SampleDecision::Check { rate } => rate,
+            SampleDecision::Bypass => {
+                // Known violators were always going to be checked, so they count once
+                self.stats.estimated_violating += f64::from(u8::from(violating));
+                return;
+            }
+            SampleDecision::Skip => return,
+        };
+        if violating && rate > 0.0 {
+            self.stats.estimated_violating += 1.0 / rate;
+        }
+
+        if let DetectionSampling::Adaptive(adaptive) = &self.policy {
+            self.recent.push_back(violating);
+            while self.recent.len() > adaptive.window.max(1) {
+                self.recent.pop_front();
+            }
+            let violating = self.recent.iter().filter(|v| **v).count();
+            let observed = violating as f64 / self.recent.len() as f64;
+            self.rate = if observed > adaptive.threshold {
+                (self.rate * 2.0).min(adaptive.max_rate)
+            } else {
+                (self.rate * adaptive.decay).max(adaptive.base_rate)
+            };
+        }
+    }
+
+    /// Forget which contexts were seen violating
+    pub fn clear_known_violating(&mut self) {
+        self.known_violating.clear();
+    }
+}
+
+/// A violation as paired by `compare`: detections with the same axiom, severity
+/// and rule share a fingerprint regardless of the surrounding text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparedViolation {
+    pub axiom: Axiom,
+    pub severity: Severity,
+    pub rule: String,
+    pub fingerprint: u64,
+}
+
+impl ComparedViolation {
+    fn new(violation: &Violation) -> Self {
+        let rule = violation.metadata.get("rule").cloned().unwrap_or_default();
+        let key = format!("{:?}|{:?}|{}", violation.axiom, violation.severity, rule);
+        Self {
+            axiom: violation.axiom.clone(),
+            severity: violation.severity,
+            rule,
+            fingerprint: fingerprint(&key),
+        }
+    }
+}
+
+/// Penalty attributed to one axiom before and after
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AxiomDelta {
+    pub before: f64,
+    pub after: f64,
+}
+
+impl AxiomDelta {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Result of `AxiomaticSelfHealer::compare`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComparisonReport {
+    /// In `before` only
+    pub fixed: Vec<ComparedViolation>,
+    pub persisting: Vec<ComparedViolation>,
+    /// In `after` only
+    pub introduced: Vec<ComparedViolation>,
+    pub penalty_before: f64,
+    pub penalty_after: f64,
+    pub per_axiom: BTreeMap<Axiom, AxiomDelta>,
+}
+
+impl ComparisonReport {
+    /// Negative when `after` is better
+    pub fn penalty_delta(&self) -> f64 {
+        self.penalty_after - self.penalty_before
+    }
+
+    pub fn is_improvement(&self) -> bool {
+        self.introduced.is_empty() && self.penalty_after < self.penalty_before
+    }
+
+    /// Serialize as one line of `schema::COMPARISON` JSON
+    pub fn to_json(&self) -> String {
+        import::comparison_to_json(self)
+    }
+}
+
+/// What `AxiomaticSelfHealer::dry_run` found and would do with a context.
+///
+/// Everything but `would_heal` and `blocked_by` describes healing as if it ran, so a
+/// plan is just as useful with `auto_heal` off, a threshold the context doesn't reach
+/// or a budget that is used up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealingPlan {
+    pub violations: Vec<Violation>,
+    pub penalty: f64,
+    pub decision: ThresholdDecision,
+    /// Whether `monitor_and_heal` would heal at all under the current configuration
+    pub would_heal: bool,
+    /// Why `monitor_and_heal` would fail before healing, such as an exhausted
+    /// `ReturnError` budget
+    pub blocked_by: Option<String>,
+    /// Strategies each violation would try, and which would be chosen
+    pub corrections: Vec<ViolationAttempts>,
+    pub outcome: HealOutcome,
+    /// The healed context, or the original when healing would change nothing
+    pub proposed: String,
+    /// Penalty of `proposed`
+    pub predicted_penalty: f64,
+}
+
+impl HealingPlan {
+    /// Positive when healing would help
+    pub fn penalty_reduction(&self) -> f64 {
+        self.penalty - self.predicted_penalty
+    }
+}
+
+/// Plans for a run of contexts, from `AxiomaticSelfHealer::replay`.
+///
+/// Replaying the same contexts under two configurations and comparing the reports
+/// shows which one heals more, and with which strategies.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    /// In replay order
+    pub plans: Vec<HealingPlan>,
+}
+
+impl ReplayReport {
+    pub fn violations(&self) -> usize {
+        self.plans.iter().map(|plan| plan.violations.len()).sum()
+    }
+
+    /// Contexts the configuration would heal, rather than only record
+    pub fn would_heal(&self) -> usize {
+        self.plans.iter().filter(|plan| plan.would_heal).count()
+    }
+
+    /// Contexts that healing would fix, in full or in part
+    pub fn healed(&self) -> usize {
+        self.plans.iter().filter(|plan| plan.outcome == HealOutcome::Healed).count()
+    }
+
+    pub fn penalty_before(&self) -> f64 {
+        self.plans.iter().map(|plan| plan.penalty).sum()
+    }
+
+    pub fn penalty_after(&self) -> f64 {
+        self.plans.iter().map(|plan| plan.predicted_penalty).sum()
+    }
+
+    pub fn penalty_reduction(&self) -> f64 {
+        self.penalty_before() - self.penalty_after()
+    }
+
+    /// How many violations each strategy would be chosen for
+    pub fn chosen_strategies(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for plan in &self.plans {
+            for chosen in plan.corrections.iter().filter_map(|c| c.chosen) {
+                *counts.entry(chosen).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn group(
+            f: &mut fmt::Formatter<'_>,
+            label: &str,
+            items: &[ComparedViolation],
+        ) -> fmt::Result {
+            write!(f, "{} {}", label, items.len())?;
+            let axioms: BTreeSet<&Axiom> = items.iter().map(|v| &v.axiom).collect();
+            if !axioms.is_empty() {
+                let names: Vec<String> =
+                    axioms.iter().map(|a| format!("{:?}", a).to_lowercase()).collect();
+                write!(f, " ({})", names.join(", "))?;
+            }
+            write!(f, ", ")
+        }
+        group(f, "fixed", &self.fixed)?;
+        group(f, "introduced", &self.introduced)?;
+        if !self.persisting.is_empty() {
+            group(f, "persisting", &self.persisting)?;
+        }
+        write!(f, "penalty {:.1} → {:.1}", self.penalty_before, self.penalty_after)
+    }
+}
+
+/// Healing attempts and successes for one axiom.
+///
+/// Violations left unhealed because healing was never attempted are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HealTally {
+    pub attempted: u64,
+    pub healed: u64,
+}
+
+impl HealTally {
+    /// Fraction healed; `None` before any attempt
+    pub fn rate(&self) -> Option<f64> {
+        (self.attempted > 0).then(|| self.healed as f64 / self.attempted as f64)
+    }
+
+    fn since(&self, earlier: &HealTally) -> HealTally {
+        HealTally {
+            attempted: self.attempted.saturating_sub(earlier.attempted),
+            healed: self.healed.saturating_sub(earlier.healed),
+        }
+    }
+}
+
+/// Limits past which the healer considers its own state worrying.
+///
+/// The one definition of "worrying", used by `AxiomaticSelfHealer::concerns` and
+/// therefore by self-reports.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcernThresholds {
+    /// Heal success rate below which an axiom is a concern
+    pub min_heal_rate: f64,
+    /// Attempts needed before the heal rate is judged
+    pub min_heal_attempts: u64,
+    /// How long a weight may sit at a clamp bound
+    pub weight_pinned_for: Duration,
+}
+
+impl Default for ConcernThresholds {
+    fn default() -> Self {
+        Self {
+            min_heal_rate: 0.5,
+            min_heal_attempts: 10,
+            weight_pinned_for: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// Something the healer found worrying about itself
+#[derive(Debug, Clone, PartialEq)]
+pub enum Concern {
+    /// The axiom's violation budget is used up; the condition `BudgetAlert`s report
+    BudgetExhausted { axiom: Axiom, status: BudgetStatus },
+    LowHealRate { axiom: Axiom, tally: HealTally },
+    /// The weight has been at a clamp bound since `since`
+    WeightPinned { axiom: Axiom, weight: f64, since: u64 },
+    /// Buffered events were discarded before anyone drained them
+    EventsDropped { count: u64 },
+}
+
+impl Concern {
+    /// Stable identifier, for filtering and serialized reports
+    pub fn code(&self) -> &'static str {
+        match self {
+            Concern::BudgetExhausted { .. } => "budget_exhausted",
+            Concern::LowHealRate { .. } => "low_heal_rate",
+            Concern::WeightPinned { .. } => "weight_pinned",
+            Concern::EventsDropped { .. } => "events_dropped",
+        }
+    }
+}
+
+impl fmt::Display for Concern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Concern::BudgetExhausted { axiom, status } => write!(
+                f,
+                "{:?} budget exhausted: {} of {} in {:?}",
+                axiom, status.used, status.max, status.window
+            ),
+            Concern::LowHealRate { axiom, tally } => write!(
+                f,
+                "{:?} heal rate {:.0}% over {} attempts",
+                axiom,
+                tally.rate().unwrap_or(0.0) * 100.0,
+                tally.attempted
+            ),
+            Concern::WeightPinned { axiom, weight, since } => {
+                write!(f, "{:?} weight pinned at {} since {}", axiom, weight, since)
+            }
+            Concern::EventsDropped { count } => write!(f, "{} events dropped undrained", count),
+        }
+    }
+}
+
+/// When the healer publishes a `SelfReport` as a `HealerEvent::SelfReport`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelfReportSchedule {
+    EveryCalls(u64),
+    /// Checked on each heal call, against the healer's clock
+    Every(Duration),
+}
+
+/// A weight and how it moved since the previous report
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WeightChange {
+    pub weight: f64,
+    pub delta: f64,
+}
+
+/// Heal effectiveness overall and since the previous report
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HealTrend {
+    pub total: HealTally,
+    pub recent: HealTally,
+}
+
+/// Buffers and history that grow or shed data over a long run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryPressure {
+    pub history_len: usize,
+    pub tracked_contexts: usize,
+    pub events_dropped: u64,
+    pub sink_overflows: u64,
+    /// Changes in the two counters above since the previous report
+    pub events_dropped_delta: u64,
+    pub sink_overflows_delta: u64,
+}
+
+/// The healer's periodic evaluation of its own drift; see `AxiomaticSelfHealer::self_report`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfReport {
+    pub generated_at: u64,
+    /// Time of the previous report, which the deltas are relative to; `None` for
+    /// the first report, whose deltas cover the healer's whole life
+    pub since: Option<u64>,
+    pub heal_calls: u64,
+    pub heal_calls_delta: u64,
+    pub weights: BTreeMap<Axiom, WeightChange>,
+    pub heal_rates: BTreeMap<Axiom, HealTrend>,
+    /// Strategy statistics accumulated since the previous report
+    pub strategy_deltas: BTreeMap<String, StrategyStats>,
+    pub pressure: HistoryPressure,
+    pub concerns: Vec<Concern>,
+}
+
+impl SelfReport {
+    /// Serialize as one line of `schema::SELF_REPORT` JSON
+    pub fn to_json(&self) -> String {
+        use import::json_string;
+        let map = |entries: Vec<String>| format!("{{{}}}", entries.join(","));
+        let tally =
+            |t: &HealTally| format!("{{\"attempted\":{},\"healed\":{}}}", t.attempted, t.healed);
+        let weights = self
+            .weights
+            .iter()
+            .map(|(axiom, w)| {
+                let key = json_string(&format!("{:?}", axiom));
+                format!("{}:{{\"weight\":{},\"delta\":{}}}", key, w.weight, w.delta)
+            })
+            .collect();
+        let heal_rates = self
+            .heal_rates
+            .iter()
+            .map(|(axiom, t)| {
+                let key = json_string(&format!("{:?}", axiom));
+                format!("{}:{{\"total\":{},\"recent\":{}}}", key, tally(&t.total), tally(&t.recent))
+            })
+            .collect();
+        let strategies = self
+            .strategy_deltas
+            .iter()
+            .map(|(name, s)| {
+                format!(
+                    "{}:{{\"attempts\":{},\"successes\":{},\"failures\":{},\"regressions\":{},\
+                     \"contract_violations\":{}}}",
+                    json_string(name),
+                    s.attempts,
+                    s.successes,
+                    s.failures,
+                    s.regressions,
+                    s.contract_violations
+                )
+            })
+            .collect();
+        let pressure = &self.pressure;
+        let concerns: Vec<String> = self
+            .concerns
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{\"code\":{},\"message\":{}}}",
+                    json_string(c.code()),
+                    json_string(&c.to_string())
+                )
+            })
+            .collect();
+        format!(
+            "{{\"schema\":{},\"generated_at\":{},\"since\":{},\"heal_calls\":{},\
+             \"heal_calls_delta\":{},\"weights\":{},\"heal_rates\":{},\"strategy_deltas\":{},\
+             \"pressure\":{{\"history_len\":{},\"tracked_contexts\":{},\"events_dropped\":{},\
+             \"sink_overflows\":{},\"events_dropped_delta\":{},\"sink_overflows_delta\":{}}},\
+             \"concerns\":[{}]}}",
+            json_string(&schema::SELF_REPORT.to_string()),
+            self.generated_at,
+            self.since.map_or("null".to_string(), |since| since.to_string()),
+            self.heal_calls,
+            self.heal_calls_delta,
+            map(weights),
+            map(heal_rates),
+            map(strategies),
+            pressure.history_len,
+            pressure.tracked_contexts,
+            pressure.events_dropped,
+            pressure.sink_overflows,
+            pressure.events_dropped_delta,
+            pressure.sink_overflows_delta,
+            concerns.join(",")
+        )
+    }
+}
+
+impl fmt::Display for SelfReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.since {
+            Some(since) => writeln!(f, "self-report at {} (since {})", self.generated_at, since)?,
+            None => writeln!(f, "self-report at {} (since start)", self.generated_at)?,
+        }
+        writeln!(f, "  heal calls: {} (+{})", self.heal_calls, self.heal_calls_delta)?;
+        for (axiom, change) in &self.weights {
+            writeln!(f, "  weight {:?}: {:.3} ({:+.3})", axiom, change.weight, change.delta)?;
+        }
+        for (axiom, trend) in &self.heal_rates {
+            let percent = |t: &HealTally| match t.rate() {
+                Some(rate) => format!("{:.0}% of {}", rate * 100.0, t.attempted),
+                None => "no attempts".to_string(),
+            };
+            writeln!(
+                f,
+                "  heal rate {:?}: {} (recent {})",
+                axiom,
+                percent(&trend.total),
+                percent(&trend.recent)
+            )?;
+        }
+        for (name, stats) in &self.strategy_deltas {
+            writeln!(
+                f,
+                "  strategy {}: +{} attempts, +{} successes, +{} failures",
+                name, stats.attempts, stats.successes, stats.failures
+            )?;
+        }
+        let pressure = &self.pressure;
+        writeln!(
+            f,
+            "  pressure: history {}, contexts {}, events dropped {} (+{}), sink overflows {} (+{})",
+            pressure.history_len,
+            pressure.tracked_contexts,
+            pressure.events_dropped,
+            pressure.events_dropped_delta,
+            pressure.sink_overflows,
+            pressure.sink_overflows_delta
+        )?;
+        if self.concerns.is_empty() {
+            return write!(f, "  concerns: none");
+        }
+        write!(f, "  concerns:")?;
+        for concern in &self.concerns {
+            write!(f, "\n    [{}] {}", concern.code(), concern)?;
+        }
+        Ok(())
+    }
+}
+
+/// Counters as of the last `self_report`, which the next one is diffed against
+#[derive(Debug, Clone, Default)]
+struct ReportBaseline {
+    timestamp: u64,
+    heal_calls: u64,
+    weights: HashMap<Axiom, f64>,
+    heal_tallies: HashMap<Axiom, HealTally>,
+    strategy_stats: HashMap<String, StrategyStats>,
+    events_dropped: u64,
+    sink_overflows: u64,
+}
+
+/// Probability of sending a heal of an `axiom` violation at `severity` for review
+pub type AuditRate = dyn Fn(&Axiom, Severity) -> f64 + Send + Sync;
+
+/// Chooses successful heals for human review, weighted by what they healed
+pub struct AuditSampler {
+    rate: Box<AuditRate>,
+    rng: SplitMix64,
+}
+
+impl AuditSampler {
+    /// `rate` is evaluated for each healed violation; a report is sampled with the
+    /// highest of its violations' rates
+    pub fn new(seed: u64, rate: impl Fn(&Axiom, Severity) -> f64 + Send + Sync + 'static) -> Self {
+        Self { rate: Box::new(rate), rng: SplitMix64(seed) }
+    }
+
+    /// The probability `report` was sampled with, if it was
+    fn sample(&mut self, report: &HealReport) -> Option<f64> {
+        let rate = report
+            .violations
+            .iter()
+            .filter(|v| !report.unhealed.iter().any(|(unhealed, _)| unhealed == *v))
+            .map(|v| (self.rate)(&v.axiom, v.severity).clamp(0.0, 1.0))
+            .fold(0.0, f64::max);
+        (rate > 0.0 && self.rng.next_f64() < rate).then_some(rate)
+    }
+}
+
+/// A heal queued for review
+#[derive(Debug, Clone)]
+pub struct AuditItem {
+    pub id: u64,
+    pub report: HealReport,
+    /// Probability the heal was sampled with, for reweighting review results
+    pub sampled_rate: f64,
+}
+
+impl AuditItem {
+    /// Serialize as one line of `schema::AUDIT` JSON
+    pub fn to_json(&self) -> String {
+        let violations: Vec<String> =
+            self.report.violations.iter().map(Violation::to_json).collect();
+        format!(
+            "{{\"schema\":{},\"id\":{},\"sampled_rate\":{},\"context\":{},\"penalty\":{},\
+             \"violations\":[{}]}}",
+            import::json_string(&schema::AUDIT.to_string()),
+            self.id,
+            self.sampled_rate,
+            import::json_string(&self.report.context),
+            self.report.penalty,
+            violations.join(",")
+        )
+    }
+}
+
+/// What the audit queue remembers about a heal that left it, until it is reviewed
+#[derive(Debug, Clone, PartialEq)]
+struct AuditRecord {
+    axioms: Vec<Axiom>,
+    original: String,
+    healed: String,
+}
+
+impl AuditRecord {
+    fn new(item: &AuditItem) -> Self {
+        let violations = &item.report.violations;
+        Self {
+            axioms: violations.iter().map(|v| v.axiom.clone()).collect(),
+            original: violations.first().map(|v| v.context.clone()).unwrap_or_default(),
+            healed: item.report.context.clone(),
+        }
+    }
+}
+
+/// A reviewer's judgement of an audited heal
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditVerdict {
+    Approved,
+    Rejected { reason: String },
+}
+
+/// A verdict together with what the reviewed heal was about
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewedAudit {
+    pub id: u64,
+    /// Axioms of the violations the heal addressed
+    pub axioms: Vec<Axiom>,
+    pub verdict: AuditVerdict,
+}
+
+/// Bounded queue of heals awaiting review.
+///
+/// When full, the oldest item is written to the spill sink as a JSON line, or
+/// counted in `dropped` if there is none or writing fails.
+pub struct AuditQueue {
+    items: VecDeque<AuditItem>,
+    capacity: usize,
+    spill: Option<Box<dyn Write + Send>>,
+    next_id: u64,
+    /// Items drained or spilled but not yet reviewed, by id
+    outstanding: HashMap<u64, AuditRecord>,
+    reviewed: HashSet<u64>,
+    reviews: Vec<ReviewedAudit>,
+    spilled: usize,
+    dropped: usize,
+}
+
+impl Default for AuditQueue {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl AuditQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: VecDeque::new(),
+            capacity: capacity.max(1),
+            spill: None,
+            next_id: 0,
+            outstanding: HashMap::new(),
+            reviewed: HashSet::new(),
+            reviews: Vec::new(),
+            spilled: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+    }
+
+    /// Append overflow to a JSONL file
+    pub fn spill_to_file(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        self.spill = Some(Box::new(std::io::BufWriter::new(file)));
+        Ok(())
+    }
+
+    pub fn spill_to(&mut self, sink: impl Write + Send + 'static) {
+        self.spill = Some(Box::new(sink));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items written to the spill sink because the queue was full
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    /// Items lost because the queue was full and could not spill
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    fn push(&mut self, report: HealReport, sampled_rate: f64) {
+        if self.items.len() >= self.capacity {
+            if let Some(oldest) = self.items.pop_front() {
+                let written = self.spill.as_mut().map(|sink| {
+                    writeln!(sink, "{}", oldest.to_json()).and_then(|()| sink.flush())
+                });
+                match written {
+                    Some(Ok(())) => {
+                        self.outstanding.insert(oldest.id, AuditRecord::new(&oldest));
+                        self.spilled += 1;
+                    }
+                    _ => self.dropped += 1,
+                }
+            }
+        }
+        self.items.push_back(AuditItem { id: self.next_id, report, sampled_rate });
+        self.next_id += 1;
+    }
+
+    /// Take up to `n` of the oldest items for review
+    pub fn drain(&mut self, n: usize) -> Vec<AuditItem> {
+        let n = n.min(self.items.len());
+        let drained: Vec<AuditItem> = self.items.drain(..n).collect();
+        for item in &drained {
+            self.outstanding.insert(item.id, AuditRecord::new(item));
+        }
+        drained
+    }
+
+    /// Record the verdict on a drained or spilled item; false if `id` is not
+    /// awaiting review
+    pub fn mark_reviewed(&mut self, id: u64, verdict: AuditVerdict) -> bool {
+        match self.outstanding.remove(&id) {
+            Some(record) => {
+                self.reviewed.insert(id);
+                self.reviews.push(ReviewedAudit { id, axioms: record.axioms, verdict });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take the verdicts recorded since the last call
+    pub fn take_reviews(&mut self) -> Vec<ReviewedAudit> {
+        std::mem::take(&mut self.reviews)
+    }
+}
+
+/// Source format of reviewer verdicts for `AxiomaticSelfHealer::ingest_feedback`.
+///
+/// Either way rows have a `heal_id` (an `AuditItem` id), a `verdict` (`good`,
+/// `approved` or `accept`; `bad`, `rejected` or `reject`) and optionally `reason`
+/// and `corrected_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackFormat {
+    JsonLines,
+    /// With a header row naming the fields
+    Csv,
+}
+
+/// Weight feedback applied per axiom of a reviewed heal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedbackMagnitudes {
+    pub good: f64,
+    pub bad: f64,
+}
+
+impl Default for FeedbackMagnitudes {
+    fn default() -> Self {
+        Self { good: 1.0, bad: -1.0 }
+    }
+}
+
+/// A reviewer's correction of a heal, kept for strategy tuning
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabeledExample {
+    pub heal_id: u64,
+    pub axioms: Vec<Axiom>,
+    /// The context before healing
+    pub original: String,
+    pub healed: String,
+    pub corrected: String,
+}
+
+/// What `ingest_feedback` did with each row
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedbackSummary {
+    pub applied: usize,
+    /// Lines and ids that no audited heal is waiting on
+    pub unknown_ids: Vec<(usize, u64)>,
+    /// Lines and ids already given a verdict, earlier in the source or before
+    pub duplicates: Vec<(usize, u64)>,
+    /// Rows that could not be read
+    pub errors: Vec<import::RowError>,
+    /// Net weight feedback per axiom, as passed to `update_weights_batch`
+    pub feedback: BTreeMap<Axiom, f64>,
+    pub labeled: usize,
+}
+
+/// Answers a `CorrectionStrategy::QueryUser` request with the corrected context
+pub type UserQuery = dyn Fn(&str, &Violation) -> Result<String, String> + Send + Sync;
+
+/// How strategies are retried, and what happens to a context once a violation's
+/// chain is exhausted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Tries of each strategy before it counts as failed; 0 is treated as 1
+    pub max_attempts: u32,
+    /// Move on to the next strategy in the chain once one has failed; otherwise
+    /// only the first strategy is tried
+    pub escalate: bool,
+    pub on_exhausted: ExhaustionAction,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            escalate: true,
+            on_exhausted: ExhaustionAction::default(),
+        }
+    }
+}
+
+/// What `monitor_and_heal` does when no strategy heals a violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExhaustionAction {
+    /// Report the violation as unhealed, keeping heals of the others
+    #[default]
+    RecordUnhealed,
+    /// Fail with `HealError::StrategiesExhausted` or `UserInterventionRequired`
+    ReturnError,
+    /// Discard the heals of the other violations too and keep the original context
+    KeepOriginal,
+}
+
+/// How many checkpoints a `CheckpointStore` keeps, and for how long; pinned
+/// checkpoints are kept regardless
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckpointRetention {
+    /// Unpinned checkpoints kept, newest first
+    pub max_count: usize,
+    /// Unpinned checkpoints older than this are pruned
+    pub max_age: Option<Duration>,
+    /// A context identical to the newest checkpoint refreshes it instead of adding one
+    pub dedup: bool,
+}
+
+impl Default for CheckpointRetention {
+    fn default() -> Self {
+        Self { max_count: 16, max_age: None, dedup: true }
+    }
+}
+
+/// A context as it was before a monitor cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub id: u64,
+    /// When the checkpoint was taken, or last refreshed by an identical context
+    pub timestamp: u64,
+    pub context: String,
+    /// The context was found free of violations; only these are rolled back to
+    pub validated: bool,
+    /// Kept through pruning until unpinned
+    pub pinned: bool,
+    fingerprint: u64,
+}
+
+/// Checkpoints of monitored contexts, which `CorrectionStrategy::Rollback` restores
+/// once the store is set with `AxiomaticSelfHealer::set_checkpoints`.
+///
+/// Checkpoints hold whole contexts, so rolling back in segmented mode replaces the
+/// violating segment with the whole checkpoint.
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointStore {
+    retention: CheckpointRetention,
+    /// Oldest first
+    checkpoints: VecDeque<Checkpoint>,
+    next_id: u64,
+}
+
+impl CheckpointStore {
+    pub fn new(retention: CheckpointRetention) -> Self {
+        Self { retention, ..Self::default() }
+    }
+
+    pub fn retention(&self) -> CheckpointRetention {
+        self.retention
+    }
+
+    /// Change the retention; it applies from the next prune
+    pub fn set_retention(&mut self, retention: CheckpointRetention) {
+        self.retention = retention;
+    }
+
+    /// Every checkpoint kept, oldest first
+    pub fn checkpoints(&self) -> impl Iterator<Item = &Checkpoint> {
+        self.checkpoints.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Checkpoint> {
+        self.checkpoints.iter().find(|c| c.id == id)
+    }
+
+    /// The checkpoint `Rollback` restores
+    pub fn latest_validated(&self) -> Option<&Checkpoint> {
+        self.checkpoints.iter().rev().find(|c| c.validated)
+    }
+
+    /// Keep a checkpoint through pruning, returning whether it exists
+    pub fn pin(&mut self, id: u64) -> bool {
+        self.set_pinned(id, true)
+    }
+
+    pub fn unpin(&mut self, id: u64) -> bool {
+        self.set_pinned(id, false)
+    }
+
+    fn set_pinned(&mut self, id: u64, pinned: bool) -> bool {
+        match self.checkpoints.iter_mut().find(|c| c.id == id) {
+            Some(checkpoint) => {
+                checkpoint.pinned = pinned;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop a checkpoint, pinned or not
+    pub fn remove(&mut self, id: u64) -> Option<Checkpoint> {
+        let index = self.checkpoints.iter().position(|c| c.id == id)?;
+        self.checkpoints.remove(index)
+    }
+
+    /// Drop unpinned checkpoints the retention no longer covers at `now` (unix
+    /// millis), returning how many were dropped
+    pub fn prune(&mut self, now: u64) -> usize {
+        let before = self.checkpoints.len();
+        if let Some(max_age) = self.retention.max_age {
+            let cutoff = now.saturating_sub(max_age.as_millis() as u64);
+            self.checkpoints.retain(|c| c.pinned || c.timestamp >= cutoff);
+        }
+        let mut excess = self.checkpoints.iter().filter(|c| !c.pinned).count();
+        excess = excess.saturating_sub(self.retention.max_count);
+        self.checkpoints.retain(|c| {
+            let evict = excess > 0 && !c.pinned;
+            excess -= evict as usize;
+            !evict
+        });
+        before - self.checkpoints.len()
+    }
+
+    /// Checkpoint `context`, returning the id to validate it under
+    fn record(&mut self, context: &str, now: u64) -> u64 {
+        let fingerprint = fingerprint(context);
+        if self.retention.dedup {
+            if let Some(newest) = self.checkpoints.back_mut() {
+                if newest.fingerprint == fingerprint && newest.context == context {
+                    newest.timestamp = now;
+                    return newest.id;
+                }
+            }
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.checkpoints.push_back(Checkpoint {
+            id,
+            timestamp: now,
+            context: context.to_string(),
+            validated: false,
+            pinned: false,
+            fingerprint,
+        });
+        id
+    }
+
+    fn validate(&mut self, id: u64, validated: bool) {
+        if let Some(checkpoint) = self.checkpoints.iter_mut().find(|c| c.id == id) {
+            checkpoint.validated = validated;
+        }
+    }
+}
+
+/// Self-healing system that automatically corrects violations
+pub struct AxiomaticSelfHealer {
+    regularizer: AdaptiveAxiomaticRegularizer,
+    correction_strategies: HashMap<Axiom, Vec<CorrectionStrategy>>,
+    auto_heal: bool,
+    threshold_policy: ThresholdPolicy,
+    verify_after_heal: bool,
+    accepted_regressions: HashSet<Axiom>,
+    strategy_stats: HashMap<String, StrategyStats>,
+    default_deadline: Option<Duration>,
+    unhealed_counts: HashMap<UnhealedReason, usize>,
+    budgets: HashMap<Axiom, ViolationBudget>,
+    budget_alerts: Vec<BudgetAlert>,
+    segmenter: Option<Box<dyn Segmenter>>,
+    segment_roles: HashMap<Axiom, Vec<String>>,
+    persistence: Option<Persistence>,
+    events: Vec<HealerEvent>,
+    quote_policy: QuotePolicy,
+    trends: HashMap<Axiom, TrendBuckets>,
+    trend_resolution: (Duration, usize),
+    record_only: HashSet<Axiom>,
+    latency: LatencyMetrics,
+    contract_enforcement: ContractEnforcement,
+    contexts: ContextTracker,
+    sampler: Option<DetectionSampler>,
+    sinks: Vec<QueuedSink>,
+    known_clean: Option<KnownCleanFilter>,
+    audit_sampler: Option<AuditSampler>,
+    audit_queue: AuditQueue,
+    feedback_magnitudes: FeedbackMagnitudes,
+    labeled_examples: Vec<LabeledExample>,
+    heal_calls: u64,
+    heal_tallies: HashMap<Axiom, HealTally>,
+    events_dropped: u64,
+    sink_overflows: u64,
+    concern_thresholds: ConcernThresholds,
+    report_baseline: Mutex<Option<ReportBaseline>>,
+    self_report_schedule: Option<SelfReportSchedule>,
+    /// Heal calls and timestamp at the last scheduled self-report
+    last_published: (u64, u64),
+    retry_policy: RetryPolicy,
+    user_query: Option<Box<UserQuery>>,
+    observers: Vec<Box<dyn HealObserver>>,
+    checkpoints: Option<CheckpointStore>,
+    escalation: escalation::Escalation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CorrectionStrategy {
+    Rollback,
+    Recompute,
+    Interpolate,
+    QueryUser,
+    ApplyDefault,
+    /// Remove the sentence containing the violation; fails if it has no span
+    ExciseSentence,
+    /// Replace the sentence containing the violation with a template, in which
+    /// `{axiom}` stands for the violated axiom; fails if it has no span
+    ReplaceSentence { template: String },
+    /// Application-supplied strategy; code rather than data, so serializing it fails
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(SharedStrategy),
+}
+
+impl CorrectionStrategy {
+    /// Stable, human-readable name of the strategy
+    pub fn name(&self) -> &'static str {
+        match self {
+            CorrectionStrategy::Rollback => "rollback",
+            CorrectionStrategy::Recompute => "recompute",
+            CorrectionStrategy::Interpolate => "interpolate",
+            CorrectionStrategy::QueryUser => "query_user",
+            CorrectionStrategy::ApplyDefault => "apply_default",
+            CorrectionStrategy::ExciseSentence => "excise_sentence",
+            CorrectionStrategy::ReplaceSentence { .. } => "replace_sentence",
+            CorrectionStrategy::Custom(custom) => custom.0.name(),
+        }
+    }
+
+    /// Wrap an application-supplied strategy
+    pub fn custom(strategy: impl CustomStrategy + 'static) -> Self {
+        CorrectionStrategy::Custom(SharedStrategy(Arc::new(strategy)))
+    }
+
+    /// Wrap a handler as an application-supplied strategy
+    pub fn handler(handler: impl CorrectionHandler + 'static) -> Self {
+        Self::custom(HandlerStrategy(handler))
+    }
+
+    /// Tries before falling back, for strategies that set their own limit
+    pub fn max_attempts(&self) -> Option<u32> {
+        match self {
+            CorrectionStrategy::Custom(custom) => custom.0.max_attempts(),
+            _ => None,
+        }
+    }
+
+    /// Wrap a closure as an application-supplied strategy with the default cost and
+    /// contract
+    pub fn from_fn<F>(name: &'static str, apply: F) -> Self
+    where
+        F: Fn(&str, &Violation) -> Result<String, String> + Send + Sync + 'static,
+    {
+        Self::custom(FnStrategy { name, apply })
+    }
+
+    /// Inverse of `name` for the built-in strategies that take no parameters
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rollback" => Some(CorrectionStrategy::Rollback),
+            "recompute" => Some(CorrectionStrategy::Recompute),
+            "interpolate" => Some(CorrectionStrategy::Interpolate),
+            "query_user" => Some(CorrectionStrategy::QueryUser),
+            "apply_default" => Some(CorrectionStrategy::ApplyDefault),
+            "excise_sentence" => Some(CorrectionStrategy::ExciseSentence),
+            _ => None,
+        }
+    }
+
+    /// Relative cost of applying the strategy (higher is more expensive)
+    pub fn cost(&self) -> u32 {
+        match self {
+            CorrectionStrategy::Rollback => 1,
+            CorrectionStrategy::ApplyDefault => 1,
+            CorrectionStrategy::Interpolate => 2,
+            CorrectionStrategy::ExciseSentence => 2,
+            CorrectionStrategy::ReplaceSentence { .. } => 2,
+            CorrectionStrategy::Recompute => 5,
+            CorrectionStrategy::QueryUser => 10,
+            CorrectionStrategy::Custom(custom) => custom.0.cost(),
+        }
+    }
+
+    /// The contract the strategy's output is checked against
+    pub fn contract(&self) -> StrategyContract {
+        match self {
+            CorrectionStrategy::Custom(custom) => custom.0.contract(),
+            _ => StrategyContract {
+                emits_markers: true,
+                ..StrategyContract::default()
+            },
+        }
+    }
+}
+
+/// A correction strategy supplied by the application
+pub trait CustomStrategy: Send + Sync {
+    /// Stable name used in statistics and reports; must not clash with a built-in
+    fn name(&self) -> &'static str;
+
+    fn apply(&self, context: &str, violation: &Violation) -> Result<String, String>;
+
+    /// Relative cost, as for `CorrectionStrategy::cost`
+    fn cost(&self) -> u32 {
+        5
+    }
+
+    /// Output checks the healer holds this strategy to
+    fn contract(&self) -> StrategyContract {
+        StrategyContract::default()
+    }
+
+    /// The outcome while healing; `apply`'s result as `Healed` or `Failed` unless
+    /// the strategy can say more
+    fn correct(&self, context: &str, violation: &Violation) -> Correction {
+        match self.apply(context, violation) {
+            Ok(text) => Correction::Healed(text),
+            Err(reason) => Correction::Failed(reason),
+        }
+    }
+
+    /// Tries before falling back, overriding `RetryPolicy::max_attempts`
+    fn max_attempts(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// What a `CorrectionHandler` made of a violation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Correction {
+    /// The violation is fixed in the returned context
+    Healed(String),
+    /// The returned context is better but may still violate; it is kept and the next
+    /// strategy in the chain carries on from it
+    Partial(String),
+    /// Retrying or falling back is pointless: the chain stops and the violation is
+    /// left for a person, as after a failed `QueryUser`
+    Escalate(String),
+    /// This attempt failed; it is retried, then the chain falls back to the next
+    /// strategy
+    Failed(String),
+}
+
+/// Application remediation logic that reports more than success or failure; wrap it
+/// with `CorrectionStrategy::handler` to put it in a chain
+pub trait CorrectionHandler: Send + Sync {
+    /// Stable name used in statistics and reports; must not clash with a built-in
+    fn name(&self) -> &'static str;
+
+    fn correct(&self, context: &str, violation: &Violation) -> Correction;
+
+    /// Tries before falling back, overriding `RetryPolicy::max_attempts`
+    fn max_attempts(&self) -> Option<u32> {
+        None
+    }
+
+    /// Relative cost, as for `CorrectionStrategy::cost`
+    fn cost(&self) -> u32 {
+        5
+    }
+
+    /// Output checks the healer holds this handler to
+    fn contract(&self) -> StrategyContract {
+        StrategyContract::default()
+    }
+}
+
+/// A `CorrectionHandler` wrapped by `CorrectionStrategy::handler`
+struct HandlerStrategy<H>(H);
+
+impl<H: CorrectionHandler> CustomStrategy for HandlerStrategy<H> {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn apply(&self, context: &str, violation: &Violation) -> Result<String, String> {
+        match self.0.correct(context, violation) {
+            Correction::Healed(text) | Correction::Partial(text) => Ok(text),
+            Correction::Escalate(reason) | Correction::Failed(reason) => Err(reason),
+        }
+    }
+
+    fn cost(&self) -> u32 {
+        self.0.cost()
+    }
+
+    fn contract(&self) -> StrategyContract {
+        self.0.contract()
+    }
+
+    fn correct(&self, context: &str, violation: &Violation) -> Correction {
+        self.0.correct(context, violation)
+    }
+
+    fn max_attempts(&self) -> Option<u32> {
+        self.0.max_attempts()
+    }
+}
+
+/// A closure wrapped by `CorrectionStrategy::from_fn`
+struct FnStrategy<F> {
+    name: &'static str,
+    apply: F,
+}
+
+impl<F> CustomStrategy for FnStrategy<F>
+where
+    F: Fn(&str, &Violation) -> Result<String, String> + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn apply(&self, context: &str, violation: &Violation) -> Result<String, String> {
+        (self.apply)(context, violation)
+    }
+}
+
+/// A shared `CustomStrategy`; strategies compare equal by name
+#[derive(Clone)]
+pub struct SharedStrategy(pub Arc<dyn CustomStrategy>);
+
+impl fmt::Debug for SharedStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedStrategy").field(&self.0.name()).finish()
+    }
+}
+
+impl PartialEq for SharedStrategy {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.name() == other.0.name()
+    }
+}
+
+/// What a strategy's output is checked against after every application.
+///
+/// The defaults are strict; a strategy relaxes the checks it legitimately needs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyContract {
+    /// Empty output for non-empty input is allowed for violations at or above this
+    /// severity; `None` never allows it
+    pub delete_at_or_above: Option<Severity>,
+    /// Bytes the output may grow beyond the input
+    pub growth_budget: usize,
+    /// The output may add `[UPPER_CASE]` markers like the ones built-in strategies emit
+    pub emits_markers: bool,
+    /// The output may contain NUL characters the input didn't
+    pub allow_nul: bool,
+}
+
+impl Default for StrategyContract {
+    fn default() -> Self {
+        Self {
+            delete_at_or_above: None,
+            growth_budget: 1024,
+            emits_markers: false,
+            allow_nul: false,
+        }
+    }
+}
+
+/// A way a strategy's output broke its `StrategyContract`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractViolation {
+    EmptyOutput,
+    InteriorNul,
+    GrowthExceeded { input_len: usize, output_len: usize, budget: usize },
+    UndeclaredMarker { marker: String },
+}
+
+impl fmt::Display for ContractViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractViolation::EmptyOutput => write!(f, "output is empty"),
+            ContractViolation::InteriorNul => write!(f, "output contains NUL"),
+            ContractViolation::GrowthExceeded { input_len, output_len, budget } => write!(
+                f,
+                "output grew from {} to {} bytes, over the budget of {}",
+                input_len, output_len, budget
+            ),
+            ContractViolation::UndeclaredMarker { marker } => {
+                write!(f, "output adds marker {} without declaring markers", marker)
+            }
+        }
+    }
+}
+
+impl StrategyContract {
+    /// Check one application of a strategy to `input`
+    pub fn check(
+        &self,
+        input: &str,
+        output: &str,
+        violation: &Violation,
+    ) -> Result<(), ContractViolation> {
+        let may_delete = self.delete_at_or_above.is_some_and(|min| violation.severity >= min);
+        if output.is_empty() && !input.is_empty() && !may_delete {
+            return Err(ContractViolation::EmptyOutput);
+        }
+        if !self.allow_nul && output.contains('\0') && !input.contains('\0') {
+            return Err(ContractViolation::InteriorNul);
+        }
+        if output.len() > input.len().saturating_add(self.growth_budget) {
+            return Err(ContractViolation::GrowthExceeded {
+                input_len: input.len(),
+                output_len: output.len(),
+                budget: self.growth_budget,
+            });
+        }
+        if !self.emits_markers {
+            let before = markers(input);
+            for (marker, count) in markers(output) {
+                if count > before.get(marker).copied().unwrap_or(0) {
+                    return Err(ContractViolation::UndeclaredMarker { marker: marker.to_string() });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Occurrences of `[UPPER_CASE]` marker syntax in `text`
+fn markers(text: &str) -> BTreeMap<&str, usize> {
+    let mut found = BTreeMap::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open..];
+        let name_len = rest[1..]
+            .bytes()
+            .take_while(|b| b.is_ascii_uppercase() || *b == b'_')
+            .count();
+        if name_len > 0 && rest.as_bytes().get(name_len + 1) == Some(&b']') {
+            *found.entry(&rest[..name_len + 2]).or_insert(0) += 1;
+        }
+        rest = &rest[1..];
+    }
+    found
+}
+
+/// Abbreviations whose trailing period does not end a sentence, without that period
+const ABBREVIATIONS: &[&str] =
+    &["e.g", "i.e", "cf", "vs", "mr", "mrs", "ms", "dr", "prof", "approx"];
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…' | '。' | '！' | '？' | '｡')
+}
+
+/// Closing quotes and brackets that stay with the sentence they follow
+fn is_closer(c: char) -> bool {
+    matches!(c, '"' | '\'' | ')' | ']' | '»' | '’' | '”' | '」' | '』' | '）')
+}
+
+/// Whether a period right after `sentence` (the text since the sentence began)
+/// belongs to an abbreviation such as "e.g." rather than ending the sentence
+fn ends_with_abbreviation(sentence: &str) -> bool {
+    let word = sentence.rsplit(char::is_whitespace).next().unwrap_or_default();
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    let mut chars = word.chars();
+    let single_lowercase =
+        matches!((chars.next(), chars.next()), (Some(c), None) if c.is_lowercase());
+    single_lowercase || ABBREVIATIONS.contains(&word.as_str())
+}
+
+/// Byte ranges of the sentences in `text`, without surrounding whitespace.
+///
+/// A sentence ends at terminal punctuation (plus any closing quotes or brackets)
+/// followed by whitespace or the end of the text, or straight after a full-width
+/// terminator as used in CJK text. Blank lines end a sentence regardless.
+fn sentence_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+    // Set after a terminator: whether it was full-width
+    let mut pending: Option<bool> = None;
+    for (i, c) in text.char_indices() {
+        if let Some(s) = start {
+            if !is_terminator(c) && !is_closer(c) {
+                if let Some(full_width) = pending.take() {
+                    if full_width || c.is_whitespace() {
+                        spans.push(s..i);
+                        start = None;
+                    }
+                }
+            }
+            let blank_line = c == '\n'
+                && text[i + 1..].trim_start_matches([' ', '\t', '\r']).starts_with('\n');
+            if start.is_some() && blank_line {
+                spans.push(s..s + text[s..i].trim_end().len());
+                start = None;
+            }
+        }
+        let s = match start {
+            Some(s) => s,
+            None if c.is_whitespace() => continue,
+            None => *start.insert(i),
+        };
+        let abbreviation = c == '.' && ends_with_abbreviation(&text[s..i]);
+        if is_terminator(c) && pending.is_none() && !abbreviation {
+            pending = Some(!c.is_ascii() && c != '…');
+        }
+    }
+    if let Some(s) = start {
+        spans.push(s..s + text[s..].trim_end().len());
+    }
+    spans
+}
+
+/// Where `violation`'s span is in `context`, which may have been changed since
+/// detection: the recorded offsets if they still hold the matched text, otherwise
+/// the first occurrence of that text
+fn locate_span(context: &str, violation: &Violation) -> Option<Range<usize>> {
+    let span = violation.span()?;
+    let matched = violation.context.get(span.clone())?;
+    if context.get(span.clone()) == Some(matched) {
+        return Some(span);
+    }
+    if matched.is_empty() {
+        return None;
+    }
+    context.find(matched).map(|start| start..start + matched.len())
+}
+
+/// Remove the sentences overlapping `violation`'s span, or replace them with
+/// `replacement`, leaving the surrounding whitespace and paragraphs intact
+fn rewrite_sentences(
+    context: &str,
+    violation: &Violation,
+    replacement: Option<&str>,
+) -> Result<String, String> {
+    let span = locate_span(context, violation)
+        .ok_or_else(|| "violation has no span in this context".to_string())?;
+    let sentences = sentence_spans(context);
+    let mut hit = sentences
+        .iter()
+        .filter(|s| s.start < span.end.max(span.start + 1) && span.start < s.end);
+    let first = hit.next().ok_or_else(|| "violation span is not inside a sentence".to_string())?;
+    let (mut from, mut to) = (first.start, hit.next_back().unwrap_or(first).end);
+
+    if let Some(replacement) = replacement {
+        return Ok(format!("{}{}{}", &context[..from], replacement, &context[to..]));
+    }
+    // Take the whitespace on one side along with the sentences: preferably the
+    // space before the next sentence of the same paragraph, never a paragraph break
+    // unless the sentences were the whole paragraph
+    let after = context[to..].len() - context[to..].trim_start().len();
+    let before = context[..from].len() - context[..from].trim_end().len();
+    let more_after = to + after < context.len();
+    let after_inline = after > 0 && !context[to..to + after].contains('\n');
+    let before_inline = before > 0 && !context[from - before..from].contains('\n');
+    if more_after && (after_inline || !before_inline) {
+        to += after;
+    } else {
+        from -= before;
+    }
+    let excised = format!("{}{}", &context[..from], &context[to..]);
+    if excised.trim().is_empty() {
+        return Err("excising the sentence would leave nothing".to_string());
+    }
+    Ok(excised)
+}
+
+/// How `detect_annotated` marks violations in place.
+///
+/// Spans are opened outermost first: by start, then the longer span, then the more
+/// severe violation, then by axiom and rule. A span that crosses the end of one it
+/// started inside is closed there and reopened right after, so marks always nest.
+/// Violations without a span in the text are listed after it instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationStyle {
+    /// `⟦safety:critical⟧unsafe op⟦/⟧`, with a literal `⟦` doubled; reversible with
+    /// `AxiomaticSelfHealer::strip_annotations`
+    Bracketed,
+    /// Bold spans with footnotes giving axiom, severity and rule
+    Markdown,
+    /// Spans colored by severity and tagged with the axiom, for terminals
+    Ansi,
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// `axiom:severity` in lowercase
+fn annotation_label(violation: &Violation) -> String {
+    format!("{:?}:{:?}", violation.axiom, violation.severity).to_lowercase()
+}
+
+fn annotate(context: &str, violations: &[Violation], style: AnnotationStyle) -> String {
+    let mut located = Vec::new();
+    let mut unlocated = Vec::new();
+    for violation in violations {
+        match locate_span(context, violation).filter(|span| !span.is_empty()) {
+            Some(span) => located.push((span, violation)),
+            None => unlocated.push(violation),
+        }
+    }
+    located.sort_by(|(a, x), (b, y)| {
+        (a.start, b.end, y.severity, &x.axiom, x.metadata.get("rule"))
+            .cmp(&(b.start, a.end, x.severity, &y.axiom, y.metadata.get("rule")))
+    });
+
+    let mut out = String::new();
+    let push_text = |out: &mut String, text: &str| match style {
+        AnnotationStyle::Bracketed => out.push_str(&text.replace('⟦', "⟦⟦")),
+        _ => out.push_str(text),
+    };
+    let color = |violation: &Violation| match violation.severity {
+        Severity::Critical => "\x1b[1;31m",
+        Severity::High => "\x1b[31m",
+        Severity::Medium => "\x1b[33m",
+        Severity::Low => "\x1b[36m",
+    };
+    let open = |out: &mut String, index: usize| match style {
+        AnnotationStyle::Bracketed => {
+            out.push_str(&format!("⟦{}⟧", annotation_label(located[index].1)))
+        }
+        // Bold marks the union of the spans, so it is toggled per boundary instead
+        AnnotationStyle::Markdown => {}
+        AnnotationStyle::Ansi => out.push_str(color(located[index].1)),
+    };
+    let close = |out: &mut String, stack: &[usize], index: usize, finished: bool| match style {
+        AnnotationStyle::Bracketed => out.push_str("⟦/⟧"),
+        AnnotationStyle::Markdown if finished => out.push_str(&format!("[^{}]", index + 1)),
+        AnnotationStyle::Markdown => {}
+        AnnotationStyle::Ansi => {
+            out.push_str(ANSI_RESET);
+            if finished {
+                let axiom = format!("{:?}", located[index].1.axiom).to_lowercase();
+                out.push_str(&format!("{}[{}]{}", ANSI_DIM, axiom, ANSI_RESET));
+            }
+            for &open in stack {
+                out.push_str(color(located[open].1));
+            }
+        }
+    };
+
+    let boundaries: BTreeSet<usize> =
+        located.iter().flat_map(|(span, _)| [span.start, span.end]).collect();
+    let (mut stack, mut next, mut position): (Vec<usize>, _, _) = (Vec::new(), 0, 0);
+    for boundary in boundaries {
+        push_text(&mut out, &context[position..boundary]);
+        position = boundary;
+        let (was_open, markers_at) = (!stack.is_empty(), out.len());
+        let mut interrupted = Vec::new();
+        while stack.iter().any(|&i| located[i].0.end == boundary) {
+            let Some(top) = stack.pop() else { break };
+            let finished = located[top].0.end == boundary;
+            close(&mut out, &stack, top, finished);
+            if !finished {
+                interrupted.push(top);
+            }
+        }
+        for index in interrupted.into_iter().rev() {
+            open(&mut out, index);
+            stack.push(index);
+        }
+        while next < located.len() && located[next].0.start == boundary {
+            open(&mut out, next);
+            stack.push(next);
+            next += 1;
+        }
+        if style == AnnotationStyle::Markdown && was_open == stack.is_empty() {
+            match was_open {
+                true => out.insert_str(markers_at, "**"),
+                false => out.push_str("**"),
+            }
+        }
+    }
+    push_text(&mut out, &context[position..]);
+
+    let rule = |violation: &Violation| violation.metadata.get("rule").cloned();
+    match style {
+        AnnotationStyle::Bracketed => {
+            for violation in unlocated {
+                let mut note = annotation_label(violation);
+                if let Some(rule) = rule(violation) {
+                    note.push(' ');
+                    note.extend(rule.chars().filter(|c| !matches!(c, '⟦' | '⟧')));
+                }
+                out.push_str(&format!("\n⟦^{}⟧", note));
+            }
+        }
+        AnnotationStyle::Markdown => {
+            let notes = located.iter().map(|(_, v)| (*v, "")).chain(
+                unlocated.iter().map(|v| (*v, " (not located in the text)")),
+            );
+            for (index, (violation, suffix)) in notes.enumerate() {
+                let mut fields =
+                    vec![format!("{:?}", violation.axiom), format!("{:?}", violation.severity)];
+                fields.extend(rule(violation));
+                let note = format!("[^{}]: {}{}", index + 1, fields.join(", "), suffix);
+                out.push_str(if index == 0 { "\n\n" } else { "\n" });
+                out.push_str(&note);
+            }
+        }
+        AnnotationStyle::Ansi => {
+            for violation in unlocated {
+                let rule = rule(violation).map(|r| format!(" {}", r)).unwrap_or_default();
+                let label = annotation_label(violation);
+                out.push_str(&format!("\n{}unlocated {}{}{}", ANSI_DIM, label, rule, ANSI_RESET));
+            }
+        }
+    }
+    out
+}
+
+/// What happens when a strategy breaks its contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractEnforcement {
+    /// Panic, so broken strategies are caught in development; the debug-build default
+    Panic,
+    /// Treat the attempt as failed and record why; the release-build default
+    Report,
+}
+
+impl Default for ContractEnforcement {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            ContractEnforcement::Panic
+        } else {
+            ContractEnforcement::Report
+        }
+    }
+}
+
+/// Options controlling which strategies `propose` evaluates
+#[derive(Debug, Clone, Default)]
+pub struct PreviewOptions {
+    /// Skip strategies whose cost exceeds this cap
+    pub max_cost: Option<u32>,
+}
+
+/// Result of re-running detection on a candidate correction
+#[derive(Debug, Clone)]
+pub struct Verification {
+    /// Violations still present in the candidate text
+    pub remaining: Vec<Violation>,
+    /// Penalty of the remaining violations
+    pub penalty: f64,
+    /// Whether the proposal's target axiom is no longer violated
+    pub resolved: bool,
+}
+
+/// A candidate correction computed by `propose` but not yet applied
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub strategy: CorrectionStrategy,
+    pub strategy_name: &'static str,
+    pub violation: Violation,
+    /// The corrected text, or the reason the strategy could not produce one
+    pub result: Result<String, String>,
+    /// Verification of the corrected text; `None` when the strategy failed
+    pub verification: Option<Verification>,
+    source_fingerprint: u64,
+}
+
+/// Stable 64-bit FNV-1a hash, used to fingerprint contexts
+fn fingerprint(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl AxiomaticSelfHealer {
+    /// Complete trend buckets needed before `forecast` will project anything
+    const MIN_FORECAST_BUCKETS: usize = 3;
+
+    pub fn new(regularizer: AdaptiveAxiomaticRegularizer) -> Self {
+        let mut correction_strategies = HashMap::new();
+        
+        correction_strategies.insert(
+            Axiom::Consistency,
+            vec![CorrectionStrategy::Rollback, CorrectionStrategy::Recompute],
+        );
+        correction_strategies.insert(
+            Axiom::Safety,
+            vec![CorrectionStrategy::Rollback, CorrectionStrategy::QueryUser],
+        );
+        correction_strategies.insert(
+            Axiom::Completeness,
+            vec![CorrectionStrategy::Interpolate, CorrectionStrategy::ApplyDefault],
+        );
+
+        let threshold_policy = ThresholdPolicy::HealAbove(regularizer.threshold);
+
+        Self {
+            regularizer,
+            correction_strategies,
+            auto_heal: true,
+            threshold_policy,
+            verify_after_heal: false,
+            accepted_regressions: HashSet::new(),
+            strategy_stats: HashMap::new(),
+            default_deadline: None,
+            unhealed_counts: HashMap::new(),
+            budgets: HashMap::new(),
+            budget_alerts: Vec::new(),
+            segmenter: None,
+            segment_roles: HashMap::new(),
+            persistence: None,
+            events: Vec::new(),
+            quote_policy: QuotePolicy::default(),
+            trends: HashMap::new(),
+            trend_resolution: (Duration::from_secs(60), 24 * 60),
+            record_only: HashSet::new(),
+            latency: LatencyMetrics::new(
+                &DEFAULT_LATENCY_BUCKETS.map(Duration::from_secs_f64),
+            ),
+            contract_enforcement: ContractEnforcement::default(),
+            contexts: ContextTracker::default(),
+            sampler: None,
+            sinks: Vec::new(),
+            known_clean: None,
+            audit_sampler: None,
+            audit_queue: AuditQueue::default(),
+            feedback_magnitudes: FeedbackMagnitudes::default(),
+            labeled_examples: Vec::new(),
+            heal_calls: 0,
+            heal_tallies: HashMap::new(),
+            events_dropped: 0,
+            sink_overflows: 0,
+            concern_thresholds: ConcernThresholds::default(),
+            report_baseline: Mutex::new(None),
+            self_report_schedule: None,
+            last_published: (0, 0),
+            retry_policy: RetryPolicy::default(),
+            user_query: None,
+            observers: Vec::new(),
+            checkpoints: None,
+            escalation: escalation::Escalation::default(),
+        }
+    }
+
+    /// How context text is quoted in reports and explanations
+    pub fn set_quote_policy(&mut self, policy: QuotePolicy) {
+        self.quote_policy = policy;
+    }
+
+    pub fn quote_policy(&self) -> QuotePolicy {
+        self.quote_policy
+    }
+
+    pub fn builder(regularizer: AdaptiveAxiomaticRegularizer) -> HealerBuilder {
+        HealerBuilder::new(regularizer)
+    }
+
+    /// Write the snapshot to `dir` at most once per `interval`, checked on each heal call
+    pub fn set_persistence(&mut self, dir: impl Into<PathBuf>, interval: Duration) {
+        self.persistence = Some(Persistence {
+            dir: dir.into(),
+            interval,
+            last_written: None,
+        });
+    }
+
+    /// Capture the tunable state of the healer and its regularizer
+    pub fn snapshot(&self) -> HealerSnapshot {
+        HealerSnapshot {
+            weights: self
+                .regularizer
+                .axiom_weights
+                .iter()
+                .map(|(a, w)| (a.clone(), *w))
+                .collect(),
+            learning_rate: self.regularizer.learning_rate,
+            threshold: self.regularizer.threshold,
+            threshold_policy: self.threshold_policy,
+            auto_heal: self.auto_heal,
+            verify_after_heal: self.verify_after_heal,
+            // Custom strategies are code rather than state, so they are not captured
+            strategies: self
+                .correction_strategies
+                .iter()
+                .map(|(a, chain)| {
+                    let builtin =
+                        chain.iter().filter(|s| !matches!(s, CorrectionStrategy::Custom(_)));
+                    (a.clone(), builtin.cloned().collect())
+                })
+                .collect(),
+            accepted_regressions: self.accepted_regressions.iter().cloned().collect(),
+            contexts: self.contexts.records(),
+            disabled_axioms: self.regularizer.disabled.clone(),
+            learning_rates: self.regularizer.learning_rates(),
+            rate_scales: self.regularizer.rate_scales(),
+            violation_counts: self.get_statistics().by_axiom.into_iter().collect(),
+            strategy_stats: self
+                .strategy_stats
+                .iter()
+                .map(|(name, stats)| (name.clone(), stats.clone()))
+                .collect(),
+        }
+    }
+
+    /// Overwrite the tunable state with a snapshot.
+    ///
+    /// Violation counts are not restored; they always follow the history. A chain
+    /// whose built-in strategies match the snapshot's keeps its custom strategies,
+    /// which the snapshot can't hold.
+    pub fn restore(&mut self, snapshot: &HealerSnapshot) {
+        self.regularizer.axiom_weights =
+            snapshot.weights.iter().map(|(a, w)| (a.clone(), *w)).collect();
+        self.regularizer.generation += 1;
+        self.regularizer.learning_rate = snapshot.learning_rate;
+        self.regularizer.threshold = snapshot.threshold;
+        self.threshold_policy = snapshot.threshold_policy;
+        self.auto_heal = snapshot.auto_heal;
+        self.verify_after_heal = snapshot.verify_after_heal;
+        let live = std::mem::take(&mut self.correction_strategies);
+        self.correction_strategies = snapshot
+            .strategies
+            .iter()
+            .map(|(a, chain)| {
+                let builtin = |s: &&CorrectionStrategy| !matches!(s, CorrectionStrategy::Custom(_));
+                let unchanged = live.get(a).filter(|live| live.iter().filter(builtin).eq(chain));
+                (a.clone(), unchanged.unwrap_or(chain).clone())
+            })
+            .collect();
+        self.accepted_regressions = snapshot.accepted_regressions.iter().cloned().collect();
+        self.contexts.set_records(snapshot.contexts.clone());
+        self.regularizer.axiom_learning_rates =
+            snapshot.learning_rates.iter().map(|(a, rate)| (a.clone(), *rate)).collect();
+        self.regularizer.set_rate_scales(snapshot.rate_scales.clone());
+        if self.regularizer.disabled != snapshot.disabled_axioms {
+            self.regularizer.disabled = snapshot.disabled_axioms.clone();
+            self.regularizer.generation += 1;
+        }
+        self.strategy_stats = snapshot
+            .strategy_stats
+            .iter()
+            .map(|(name, stats)| (name.clone(), stats.clone()))
+            .collect();
+    }
+
+    /// Like `AdaptiveAxiomaticRegularizer::save_state`, with the healer's `snapshot`
+    /// embedded in the header so strategy chains and policies are saved too
+    pub fn save_state(&self, writer: impl Write) -> std::io::Result<()> {
+        self.regularizer.write_state(writer, Some(self.snapshot().encode()))
+    }
+
+    /// Restore state written by `save_state`, returning how many violations were
+    /// restored. State saved by the regularizer alone restores just its part.
+    pub fn load_state(&mut self, reader: impl BufRead) -> Result<usize, StateError> {
+        let (header, history) = AdaptiveAxiomaticRegularizer::read_state(reader)?;
+        self.apply_state(header, history)
+    }
+
+    /// Like `AdaptiveAxiomaticRegularizer::state`, with the healer's `snapshot`
+    pub fn state(&self) -> RegularizerState {
+        RegularizerState { healer: Some(self.snapshot().encode()), ..self.regularizer.state() }
+    }
+
+    /// Like `load_state`, from a value taken by `state`
+    pub fn restore_state(&mut self, state: RegularizerState) -> Result<usize, StateError> {
+        let (header, history) = state.into_parts()?;
+        self.apply_state(header, history)
+    }
+
+    fn apply_state(
+        &mut self,
+        header: import::StateHeader,
+        history: Vec<Violation>,
+    ) -> Result<usize, StateError> {
+        let snapshot = header
+            .healer
+            .as_deref()
+            .map(HealerSnapshot::decode)
+            .transpose()
+            .map_err(StateError::Snapshot)?;
+        let count = self.regularizer.apply_state(header, history);
+        if let Some(snapshot) = snapshot {
+            self.restore(&snapshot);
+        }
+        Ok(count)
+    }
+
+    /// Build a healer from the latest valid snapshot in `dir`, starting from
+    /// `regularizer_defaults`. A corrupt or unreadable snapshot is reported as a
+    /// `HealerEvent` and the previous snapshot (or the defaults) is used instead.
+    ///
+    /// Persistence is not re-enabled; call `set_persistence` to keep writing to `dir`.
+    pub fn recover(
+        dir: impl AsRef<Path>,
+        regularizer_defaults: AdaptiveAxiomaticRegularizer,
+    ) -> Self {
+        let dir = dir.as_ref();
+        let mut healer = Self::new(regularizer_defaults);
+
+        for name in [Persistence::FILE, Persistence::PREVIOUS] {
+            let path = dir.join(name);
+            if !path.exists() {
+                continue;
+            }
+            let decoded = std::fs::read_to_string(&path)
+                .map_err(SnapshotError::from)
+                .and_then(|data| HealerSnapshot::decode(&data));
+            match decoded {
+                Ok(snapshot) => {
+                    healer.restore(&snapshot);
+                    return healer;
+                }
+                Err(e) => healer.push_event(HealerEvent::SnapshotRejected {
+                    path,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+
+        if !healer.events.is_empty() {
+            healer.push_event(HealerEvent::RecoveredWithDefaults);
+        }
+        healer
+    }
+
+    /// Write a snapshot now if persistence is configured
+    pub fn persist_now(&mut self) -> std::io::Result<()> {
+        let snapshot = self.snapshot();
+        let now = self.regularizer.current_timestamp();
+        match self.persistence.as_mut() {
+            Some(persistence) => {
+                persistence.write(&snapshot)?;
+                persistence.last_written = Some(now);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Checkpoint each monitored context, so `Rollback` restores the last clean one
+    /// rather than marking the context; `None` turns checkpoints off
+    pub fn set_checkpoints(&mut self, store: Option<CheckpointStore>) {
+        self.checkpoints = store;
+    }
+
+    pub fn checkpoints(&self) -> Option<&CheckpointStore> {
+        self.checkpoints.as_ref()
+    }
+
+    /// For pinning, pruning and removing checkpoints
+    pub fn checkpoints_mut(&mut self) -> Option<&mut CheckpointStore> {
+        self.checkpoints.as_mut()
+    }
+
+    /// Call `observer` with each subsequent healing decision
+    pub fn add_observer(&mut self, observer: impl HealObserver + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// The weights to diff against after a change, if anyone is listening
+    fn observed_weights(&self) -> Option<HashMap<Axiom, f64>> {
+        (!self.observers.is_empty()).then(|| self.regularizer.axiom_weights.clone())
+    }
+
+    fn notify_observers(&mut self, report: &HealReport, weights: Option<HashMap<Axiom, f64>>) {
+        for observer in &mut self.observers {
+            for violation in &report.violations {
+                observer.on_violation_detected(violation);
+            }
+            for attempts in &report.attempts {
+                observer.on_heal_attempted(attempts);
+            }
+            for (violation, reason) in &report.unhealed {
+                observer.on_heal_failed(violation, *reason);
+            }
+        }
+        self.notify_weight_changes(weights);
+    }
+
+    fn notify_weight_changes(&mut self, before: Option<HashMap<Axiom, f64>>) {
+        let Some(before) = before else {
+            return;
+        };
+        let mut changed: Vec<(&Axiom, f64, f64)> = self
+            .regularizer
+            .axiom_weights
+            .iter()
+            .filter(|(axiom, new)| before.get(*axiom) != Some(*new))
+            .map(|(axiom, new)| {
+                let unweighted = AdaptiveAxiomaticRegularizer::UNREGISTERED_AXIOM_WEIGHT;
+                (axiom, before.get(axiom).copied().unwrap_or(unweighted), *new)
+            })
+            .collect();
+        changed.sort_by(|a, b| a.0.cmp(b.0));
+        for observer in &mut self.observers {
+            for (axiom, old, new) in &changed {
+                observer.on_weights_updated(axiom, *old, *new);
+            }
+        }
+    }
+
+    /// Queue detected violations for `sink`, keeping at most `capacity` undelivered
+    pub fn add_sink(&mut self, sink: impl ViolationSink + 'static, capacity: usize) {
+        self.sinks.push(QueuedSink {
+            sink: Box::new(sink),
+            queue: VecDeque::new(),
+            capacity: capacity.max(1),
+            overflowed: 0,
+        });
+    }
+
+    fn enqueue_for_sinks(&mut self, violations: &[Violation]) {
+        for queued in &mut self.sinks {
+            for violation in violations {
+                if queued.queue.len() >= queued.capacity {
+                    queued.queue.pop_front();
+                    queued.overflowed += 1;
+                    self.sink_overflows += 1;
+                }
+                queued.queue.push_back(violation.clone());
+            }
+        }
+    }
+
+    /// Deliver queued violations until the queues are empty or `deadline` passes
+    pub fn flush_sinks(&mut self, deadline: Option<Instant>) -> Vec<SinkFlush> {
+        let mut flushes = Vec::with_capacity(self.sinks.len());
+        for queued in &mut self.sinks {
+            let mut flush = SinkFlush {
+                name: queued.sink.name().to_string(),
+                overflowed: std::mem::take(&mut queued.overflowed),
+                ..SinkFlush::default()
+            };
+            while deadline.is_none_or(|deadline| Instant::now() < deadline) {
+                let Some(violation) = queued.queue.pop_front() else {
+                    break;
+                };
+                match queued.sink.deliver(&violation) {
+                    Ok(()) => flush.delivered += 1,
+                    Err(_) => flush.failed += 1,
+                }
+            }
+            flush.pending = queued.queue.len();
+            flushes.push(flush);
+        }
+        flushes
+    }
+
+    /// Flush sinks and persist a final snapshot, giving up on sinks after `timeout`.
+    ///
+    /// Anything still queued at the deadline is dropped and counted in the report.
+    pub fn shutdown(mut self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let sinks = self.flush_sinks(Some(deadline));
+        let timed_out = sinks.iter().any(|sink| sink.pending > 0);
+        for queued in &mut self.sinks {
+            queued.queue.clear();
+        }
+        let snapshot = self
+            .persistence
+            .is_some()
+            .then(|| self.persist_now().map_err(|e| e.to_string()));
+        ShutdownReport {
+            sinks,
+            undrained_events: std::mem::take(&mut self.events).len(),
+            undrained_alerts: std::mem::take(&mut self.budget_alerts).len(),
+            snapshot,
+            timed_out,
+        }
+    }
+
+    /// Persist if the configured interval has elapsed since the last write
+    fn maybe_persist(&mut self) {
+        let now = self.regularizer.current_timestamp();
+        let due = self.persistence.as_ref().is_some_and(|p| {
+            p.last_written
+                .is_none_or(|last| now.saturating_sub(last) >= p.interval.as_millis() as u64)
+        });
+        if due {
+            if let Err(e) = self.persist_now() {
+                self.push_event(HealerEvent::PersistFailed {
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Take the events recorded since the last call
+    pub fn drain_events(&mut self) -> Vec<HealerEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn push_event(&mut self, event: HealerEvent) {
+        const MAX_BUFFERED_EVENTS: usize = 1024;
+        if self.events.len() >= MAX_BUFFERED_EVENTS {
+            self.events.remove(0);
+            self.events_dropped += 1;
+        }
+        self.events.push(event);
+    }
+
+    /// Detect and heal each segment separately; `None` treats contexts as a whole
+    pub fn set_segmenter(&mut self, segmenter: Option<Box<dyn Segmenter>>) {
+        self.segmenter = segmenter;
+        self.invalidate_known_clean();
+    }
+
+    /// In segmented mode, only report `axiom` violations found in segments with one
+    /// of these roles (e.g. Safety only for `assistant` turns)
+    pub fn restrict_axiom_to_roles<I, S>(&mut self, axiom: Axiom, roles: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.segment_roles
+            .insert(axiom, roles.into_iter().map(Into::into).collect());
+    }
+
+    /// Detection as `monitor_and_heal` sees it, honouring segmented mode.
+    /// `tracked` controls whether the regularizer counts the scan as an observation.
+    fn detect(
+        &self,
+        context: &str,
+        segments: Option<&[Segment]>,
+        tracked: bool,
+    ) -> (Vec<Violation>, TiersRun) {
+        let scan = |text: &str| {
+            if tracked {
+                self.regularizer.detect_tiered(text)
+            } else {
+                self.regularizer.scan_tiers(text, false)
+            }
+        };
+        let segments = match segments {
+            Some(segments) => segments,
+            None => return scan(context),
+        };
+
+        let mut violations = Vec::new();
+        let mut tiers = TiersRun::default();
+        for (index, segment) in segments.iter().enumerate() {
+            let text = match context.get(segment.span.clone()) {
+                Some(text) => text,
+                None => continue,
+            };
+            let (found, ran) = scan(text);
+            tiers = tiers.union(ran);
+            for mut violation in found {
+                if let Some(roles) = self.segment_roles.get(&violation.axiom) {
+                    let role = segment.role.as_deref().unwrap_or_default();
+                    if !roles.iter().any(|r| r == role) {
+                        continue;
+                    }
+                }
+                violation.metadata.insert("segment".to_string(), index.to_string());
+                if let Some(role) = &segment.role {
+                    violation.metadata.insert("role".to_string(), role.clone());
+                }
+                violations.push(violation);
+            }
+        }
+        (violations, tiers)
+    }
+
+    /// Allow at most `max` unhealed violations of `axiom` within a sliding `window`,
+    /// applying `action` once the allowance is used up. Replaces any existing budget.
+    pub fn set_violation_budget(
+        &mut self,
+        axiom: Axiom,
+        max: usize,
+        window: Duration,
+        action: BudgetAction,
+    ) {
+        self.budgets.insert(axiom, ViolationBudget::new(max, window, action));
+    }
+
+    pub fn remove_violation_budget(&mut self, axiom: &Axiom) {
+        self.budgets.remove(axiom);
+    }
+
+    /// Consumption of the budget configured for `axiom`, if any
+    pub fn budget_consumption(&self, axiom: &Axiom) -> Option<BudgetStatus> {
+        let now = self.regularizer.current_timestamp();
+        self.budgets.get(axiom).map(|budget| budget.status(now))
+    }
+
+    /// Take the budget alerts raised since the last call
+    pub fn take_budget_alerts(&mut self) -> Vec<BudgetAlert> {
+        std::mem::take(&mut self.budget_alerts)
+    }
+
+    /// Fail closed while any `ReturnError` budget is exhausted
+    fn check_budgets(&self) -> Result<(), HealError> {
+        let now = self.regularizer.current_timestamp();
+        let mut exhausted: Vec<_> = self
+            .budgets
+            .iter()
+            .filter(|(_, b)| b.action == BudgetAction::ReturnError && b.status(now).exhausted())
+            .collect();
+        exhausted.sort_by(|a, b| a.0.cmp(b.0));
+
+        match exhausted.first() {
+            Some((axiom, budget)) => Err(HealError::BudgetExhausted {
+                axiom: (*axiom).clone(),
+                window: budget.window,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Report each detected violation's outcome to the regularizer's feedback policy
+    fn feed_back_outcomes(&mut self, report: &HealReport) {
+        if self.regularizer.feedback.is_none() {
+            return;
+        }
+        self.regularizer.record_penalty(report.penalty);
+        let detected = report
+            .violations
+            .iter()
+            .filter(|v| !v.metadata.contains_key("coverage_gap"));
+        for violation in detected {
+            let healed = !report.unhealed.iter().any(|(unhealed, _)| unhealed == violation);
+            self.regularizer.record_outcome(violation, healed);
+        }
+        let threshold = self.regularizer.threshold;
+        self.threshold_policy = match self.threshold_policy {
+            ThresholdPolicy::HealAbove(_) => ThresholdPolicy::HealAbove(threshold),
+            ThresholdPolicy::HealAtOrAbove(_) => ThresholdPolicy::HealAtOrAbove(threshold),
+            other => other,
+        };
+    }
+
+    /// Count a report's unhealed violations and charge them to the axiom budgets
+    fn account_unhealed(&mut self, report: &HealReport) {
+        let now = self.regularizer.current_timestamp();
+        for violation in &report.violations {
+            let reason = report
+                .unhealed
+                .iter()
+                .find(|(unhealed, _)| unhealed == violation)
+                .map(|(_, reason)| *reason);
+            if reason != Some(UnhealedReason::NotAttempted) {
+                let tally = self.heal_tallies.entry(violation.axiom.clone()).or_default();
+                tally.attempted += 1;
+                tally.healed += u64::from(reason.is_none());
+            }
+        }
+        for (violation, reason) in &report.unhealed {
+            *self.unhealed_counts.entry(*reason).or_insert(0) += 1;
+
+            if let Some(budget) = self.budgets.get_mut(&violation.axiom) {
+                let was_exhausted = budget.status(now).exhausted();
+                budget.trend.record(now, 1);
+                let status = budget.status(now);
+                let newly_exhausted = status.exhausted() && !was_exhausted;
+                if budget.action == BudgetAction::RaiseAlert && newly_exhausted {
+                    self.budget_alerts.push(BudgetAlert {
+                        axiom: violation.axiom.clone(),
+                        status,
+                        timestamp: now,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn set_concern_thresholds(&mut self, thresholds: ConcernThresholds) {
+        self.concern_thresholds = thresholds;
+    }
+
+    /// Everything currently worrying under the configured `ConcernThresholds`
+    pub fn concerns(&self) -> Vec<Concern> {
+        let thresholds = &self.concern_thresholds;
+        let now = self.regularizer.current_timestamp();
+        let mut concerns = Vec::new();
+
+        let mut budgets: Vec<_> = self.budgets.iter().collect();
+        budgets.sort_by(|a, b| a.0.cmp(b.0));
+        for (axiom, budget) in budgets {
+            let status = budget.status(now);
+            if status.exhausted() {
+                concerns.push(Concern::BudgetExhausted { axiom: axiom.clone(), status });
+            }
+        }
+        let mut tallies: Vec<_> = self.heal_tallies.iter().collect();
+        tallies.sort_by(|a, b| a.0.cmp(b.0));
+        for (axiom, tally) in tallies {
+            let low = tally.rate().is_some_and(|rate| rate < thresholds.min_heal_rate);
+            if tally.attempted >= thresholds.min_heal_attempts && low {
+                concerns.push(Concern::LowHealRate { axiom: axiom.clone(), tally: *tally });
+            }
+        }
+        let pinned_for = thresholds.weight_pinned_for.as_millis() as u64;
+        let mut pinned: Vec<_> = self.regularizer.pinned_since.iter().collect();
+        pinned.sort_by(|a, b| a.0.cmp(b.0));
+        for (axiom, since) in pinned {
+            if now.saturating_sub(*since) > pinned_for {
+                concerns.push(Concern::WeightPinned {
+                    axiom: axiom.clone(),
+                    weight: self.regularizer.weight(axiom).unwrap_or_default(),
+                    since: *since,
+                });
+            }
+        }
+        if self.events_dropped > 0 {
+            concerns.push(Concern::EventsDropped { count: self.events_dropped });
+        }
+        concerns
+    }
+
+    /// Assemble a drift report from the healer's counters, with deltas since the
+    /// previous call.
+    pub fn self_report(&self) -> SelfReport {
+        let now = self.regularizer.current_timestamp();
+        let current = ReportBaseline {
+            timestamp: now,
+            heal_calls: self.heal_calls,
+            weights: self.regularizer.axiom_weights.clone(),
+            heal_tallies: self.heal_tallies.clone(),
+            strategy_stats: self.strategy_stats.clone(),
+            events_dropped: self.events_dropped,
+            sink_overflows: self.sink_overflows,
+        };
+        let previous = match self.report_baseline.lock() {
+            Ok(mut baseline) => baseline.replace(current.clone()),
+            Err(_) => None,
+        };
+        let since = previous.as_ref().map(|previous| previous.timestamp);
+        let previous = previous.unwrap_or_default();
+
+        let weights = current
+            .weights
+            .iter()
+            .filter(|(axiom, _)| self.regularizer.is_enabled(axiom))
+            .map(|(axiom, weight)| {
+                let delta = previous.weights.get(axiom).map_or(0.0, |before| weight - before);
+                (axiom.clone(), WeightChange { weight: *weight, delta })
+            })
+            .collect();
+        let heal_rates = current
+            .heal_tallies
+            .iter()
+            .map(|(axiom, total)| {
+                let before = previous.heal_tallies.get(axiom).copied().unwrap_or_default();
+                (axiom.clone(), HealTrend { total: *total, recent: total.since(&before) })
+            })
+            .collect();
+        let strategy_deltas = current
+            .strategy_stats
+            .iter()
+            .filter_map(|(name, stats)| {
+                let before = previous.strategy_stats.get(name).cloned().unwrap_or_default();
+                let delta = StrategyStats {
+                    attempts: stats.attempts - before.attempts,
+                    successes: stats.successes - before.successes,
+                    failures: stats.failures - before.failures,
+                    regressions: stats.regressions - before.regressions,
+                    contract_violations: stats.contract_violations - before.contract_violations,
+                };
+                (delta != StrategyStats::default()).then(|| (name.clone(), delta))
+            })
+            .collect();
+
+        SelfReport {
+            generated_at: now,
+            since,
+            heal_calls: current.heal_calls,
+            heal_calls_delta: current.heal_calls - previous.heal_calls,
+            weights,
+            heal_rates,
+            strategy_deltas,
+            pressure: HistoryPressure {
+                history_len: self.regularizer.history_len(),
+                tracked_contexts: self.contexts.len(),
+                events_dropped: current.events_dropped,
+                sink_overflows: current.sink_overflows,
+                events_dropped_delta: current.events_dropped - previous.events_dropped,
+                sink_overflows_delta: current.sink_overflows - previous.sink_overflows,
+            },
+            concerns: self.concerns(),
+        }
+    }
+
+    /// Publish a `SelfReport` on the event queue on a schedule; `None` stops it
+    pub fn set_self_report_schedule(&mut self, schedule: Option<SelfReportSchedule>) {
+        self.self_report_schedule = schedule;
+        self.last_published = (self.heal_calls, self.regularizer.current_timestamp());
+    }
+
+    fn maybe_publish_self_report(&mut self) {
+        let now = self.regularizer.current_timestamp();
+        let (calls, at) = self.last_published;
+        let due = match self.self_report_schedule {
+            None => false,
+            Some(SelfReportSchedule::EveryCalls(n)) => self.heal_calls - calls >= n.max(1),
+            Some(SelfReportSchedule::Every(interval)) => {
+                now.saturating_sub(at) >= interval.as_millis() as u64
+            }
+        };
+        if due {
+            self.last_published = (self.heal_calls, now);
+            let report = self.self_report();
+            self.push_event(HealerEvent::SelfReport(Box::new(report)));
+        }
+    }
+
+    /// Resolution of the per-axiom trends behind `forecast`; existing trend data is dropped
+    pub fn set_trend_resolution(&mut self, width: Duration, retain: usize) {
+        self.trend_resolution = (width, retain);
+        self.trends.clear();
+    }
+
+    /// Project how many violations of `axiom` will be detected over `horizon`.
+    ///
+    /// See `Forecast` for the model; at least three complete trend buckets are needed.
+    pub fn forecast(&self, axiom: Axiom, horizon: Duration) -> Forecast {
+        let (width, _) = self.trend_resolution;
+        let now = self.regularizer.current_timestamp();
+        let series: Vec<f64> = self
+            .trends
+            .get(&axiom)
+            .map(|trend| trend.complete_counts(now))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|n| n as f64)
+            .collect();
+        if series.len() < Self::MIN_FORECAST_BUCKETS {
+            return Forecast::InsufficientData { buckets: series.len() };
+        }
+
+        let (intercept, slope, sd) = fit_linear(&series);
+        let n = series.len() as f64;
+        let steps = horizon.as_secs_f64() / width.as_secs_f64();
+        // Mean of the fitted line over the projected buckets, times their number
+        let expected = (steps * (intercept + slope * (n + (steps - 1.0) / 2.0))).max(0.0);
+        let spread = 2.0 * sd * steps.sqrt();
+        Forecast::Projected {
+            expected,
+            low: (expected - spread).max(0.0),
+            high: expected + spread,
+            slope_per_bucket: slope,
+            buckets: series.len(),
+        }
+    }
+
+    /// Projected time until the budget for `axiom` is exhausted at the current trend.
+    ///
+    /// Uses the same heuristic as `forecast`, over the budget's own buckets of unhealed
+    /// violations. `Some(Duration::ZERO)` means the budget is already exhausted; `None`
+    /// means there is no budget, too little data, or no exhaustion is projected within
+    /// a hundred budget windows.
+    pub fn time_to_budget_exhaustion(&self, axiom: &Axiom) -> Option<Duration> {
+        const MAX_WINDOWS: usize = 100;
+        let budget = self.budgets.get(axiom)?;
+        let now = self.regularizer.current_timestamp();
+        if budget.status(now).exhausted() {
+            return Some(Duration::ZERO);
+        }
+
+        let counts = budget.trend.complete_counts(now);
+        if counts.len() < Self::MIN_FORECAST_BUCKETS {
+            return None;
+        }
+        let series: Vec<f64> = counts.iter().map(|&n| n as f64).collect();
+        let (intercept, slope, _) = fit_linear(&series);
+
+        // Slide the budget window forward one bucket at a time over projected counts
+        let per_window = ViolationBudget::BUCKETS_PER_WINDOW as usize;
+        let current_start = now - now % budget.trend.width_ms;
+        let current: f64 = budget
+            .trend
+            .buckets()
+            .filter(|(start, _)| *start == current_start)
+            .map(|(_, n)| n as f64)
+            .sum();
+        let mut window: VecDeque<f64> = series.iter().copied().collect();
+        window.push_back(current);
+        while window.len() > per_window {
+            window.pop_front();
+        }
+        let width = budget.trend.width();
+        for step in 1..=per_window * MAX_WINDOWS {
+            let t = (series.len() + step) as f64;
+            window.push_back((intercept + slope * t).max(0.0));
+            if window.len() > per_window {
+                window.pop_front();
+            }
+            if window.iter().sum::<f64>() >= budget.max as f64 {
+                return Some(width * step as u32);
+            }
+        }
+        None
+    }
+
+    fn record_trend(&mut self, report: &HealReport) {
+        let now = self.regularizer.current_timestamp();
+        let (width, retain) = self.trend_resolution;
+        for violation in &report.violations {
+            self.trends
+                .entry(violation.axiom.clone())
+                .or_insert_with(|| TrendBuckets::new(width, retain))
+                .record(now, 1);
+        }
+    }
+
+    /// Deadline applied to calls whose `HealOptions` don't set one
+    pub fn set_default_deadline(&mut self, deadline: Option<Duration>) {
+        self.default_deadline = deadline;
+    }
+
+    /// Number of violations left unhealed for each reason
+    pub fn unhealed_counts(&self) -> &HashMap<UnhealedReason, usize> {
+        &self.unhealed_counts
+    }
+
+    /// Re-run detection on healed output and reject heals that increase the penalty
+    pub fn set_verify_after_heal(&mut self, verify: bool) {
+        self.verify_after_heal = verify;
+    }
+
+    /// Register or redefine a custom axiom: its weight, severity multipliers and
+    /// correction chain all replace any earlier definition
+    pub fn define_axiom(&mut self, definition: AxiomDefinition) -> Axiom {
+        let AxiomDefinition { name, weight, severity_multipliers, strategies } = definition;
+        let axiom = self.regularizer.register_axiom(name, weight);
+        self.regularizer.set_axiom_severity_multipliers(axiom.clone(), severity_multipliers);
+        self.set_strategies(axiom.clone(), strategies);
+        axiom
+    }
+
+    /// Replace the correction chain tried for `axiom`; an empty chain removes it
+    pub fn set_strategies(&mut self, axiom: Axiom, strategies: Vec<CorrectionStrategy>) {
+        if strategies.is_empty() {
+            self.correction_strategies.remove(&axiom);
+        } else {
+            self.correction_strategies.insert(axiom, strategies);
+        }
+    }
+
+    /// Accept heals whose penalty increase comes only from this axiom (e.g. a
+    /// Transparency hit caused by redacting unsafe content)
+    pub fn accept_regressions_for(&mut self, axiom: Axiom) {
+        self.accepted_regressions.insert(axiom);
+    }
+
+    /// Acknowledge that `axiom` deliberately has no strategies, so `self_check` doesn't
+    /// flag it; its violations are recorded and left unhealed
+    pub fn mark_record_only(&mut self, axiom: Axiom) {
+        self.record_only.insert(axiom);
+    }
+
+    /// Cross-check the configuration for problems that would otherwise only surface
+    /// in production. An empty result means nothing was found.
+    pub fn self_check(&self) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+        let regularizer = &self.regularizer;
+
+        for row in self.coverage_matrix().rows {
+            for flag in row.flags {
+                let axiom = row.axiom.clone();
+                let warning = match flag {
+                    CoverageFlag::MissingWeight => ConfigWarning::MissingWeight { axiom },
+                    CoverageFlag::NonPositiveWeight => ConfigWarning::NonPositiveWeight {
+                        axiom,
+                        weight: row.weight.unwrap_or_default(),
+                    },
+                    CoverageFlag::NoStrategies => ConfigWarning::NoStrategies { axiom },
+                    // Shipping strategies ahead of the rules that need them is harmless
+                    CoverageFlag::NoDetectors => continue,
+                };
+                warnings.push(warning);
+            }
+        }
+
+        let enabled_rules =
+            || regularizer.rules.iter().filter(|rule| regularizer.is_enabled(&rule.axiom));
+        let all_matching: Vec<Violation> = enabled_rules()
+            .map(|rule| Violation {
+                axiom: rule.axiom.clone(),
+                severity: rule.severity,
+                context: String::new(),
+                timestamp: 0,
+                metadata: BTreeMap::new(),
+            })
+            .collect();
+        let max_penalty = regularizer.calculate_penalty(&all_matching);
+        let reachable = match self.threshold_policy {
+            ThresholdPolicy::Never => true,
+            _ if all_matching.is_empty() => true,
+            policy => policy.decide(max_penalty, all_matching.len()).heal,
+        };
+        if !reachable {
+            warnings.push(ConfigWarning::UnreachableThreshold {
+                policy: self.threshold_policy,
+                max_penalty,
+            });
+        }
+
+        for rule in enabled_rules().filter(|rule| rule.pattern.is_empty()) {
+            warnings.push(ConfigWarning::AlwaysMatchingRule { rule: rule.name() });
+        }
+
+        let first = regularizer.clock.now_millis();
+        let second = regularizer.clock.now_millis();
+        if second < first {
+            warnings.push(ConfigWarning::ClockWentBackwards { first, second });
+        }
+
+        warnings
+    }
+
+    /// Cross-reference, for every enabled axiom, the rules producing it, the chain
+    /// handling it, its weight and what currently suppresses it
+    pub fn coverage_matrix(&self) -> CoverageMatrix {
+        let regularizer = &self.regularizer;
+        let now = regularizer.current_timestamp();
+        let rows = regularizer
+            .enabled_axioms()
+            .into_iter()
+            .map(|axiom| {
+                let rules: Vec<&DetectionRule> = regularizer
+                    .rules
+                    .iter()
+                    .filter(|rule| rule.covers().contains(&axiom))
+                    .filter(|rule| !regularizer.disabled_detectors.contains(&rule.name()))
+                    .collect();
+                let detectors: Vec<String> = regularizer
+                    .detector_coverage()
+                    .into_iter()
+                    .filter(|(_, axioms)| axioms.contains(&axiom))
+                    .map(|(name, _)| name)
+                    .collect();
+                let strategies: Vec<&'static str> = self
+                    .correction_strategies
+                    .get(&axiom)
+                    .map(|chain| chain.iter().map(CorrectionStrategy::name).collect())
+                    .unwrap_or_default();
+                let handling = if !strategies.is_empty() {
+                    Handling::Chain
+                } else if self.record_only.contains(&axiom) {
+                    Handling::RecordOnly
+                } else {
+                    Handling::Unhandled
+                };
+                let weight = regularizer.weight(&axiom);
+
+                let mut suppressions = Vec::new();
+                if let Some(roles) = self.segment_roles.get(&axiom) {
+                    suppressions.push(format!("roles: {}", roles.join("|")));
+                }
+                if let Some(budget) = self.budgets.get(&axiom) {
+                    let status = budget.status(now);
+                    if budget.action == BudgetAction::ReturnError && status.exhausted() {
+                        suppressions.push("budget exhausted".to_string());
+                    }
+                }
+                if self.accepted_regressions.contains(&axiom) {
+                    suppressions.push("accepts regressions".to_string());
+                }
+
+                let mut flags = Vec::new();
+                let detected = !detectors.is_empty();
+                if detected && handling == Handling::Unhandled {
+                    flags.push(CoverageFlag::NoStrategies);
+                }
+                if !detected && !strategies.is_empty() {
+                    flags.push(CoverageFlag::NoDetectors);
+                }
+                match weight {
+                    None if detected => flags.push(CoverageFlag::MissingWeight),
+                    Some(w) if w <= 0.0 && (detected || !strategies.is_empty()) => {
+                        flags.push(CoverageFlag::NonPositiveWeight)
+                    }
+                    _ => {}
+                }
+
+                CoverageRow {
+                    detectors,
+                    severity_floor: rules.iter().flat_map(|rule| rule.severities()).min(),
+                    axiom,
+                    strategies,
+                    handling,
+                    weight,
+                    suppressions,
+                    flags,
+                }
+            })
+            .collect();
+        CoverageMatrix { rows, threshold: self.threshold_policy }
+    }
+
+    /// Latency histograms, violation counts and penalties of heal calls so far
+    pub fn metrics(&self) -> HealMetrics {
+        HealMetrics {
+            known_clean: self.known_clean_statistics().cloned(),
+            tiering: (self.regularizer.tiering_policy() != TieringPolicy::AlwaysBoth)
+                .then(|| self.regularizer.tiering_statistics()),
+            by_axiom: self
+                .latency
+                .by_axiom
+                .iter()
+                .map(|(axiom, histogram)| (axiom.clone(), histogram.snapshot()))
+                .collect(),
+            clean: self.latency.clean.snapshot(),
+            violations: self.latency.violations.clone().into_iter().collect(),
+            penalty: self.latency.penalty.clone(),
+        }
+    }
+
+    /// Attempt statistics for each strategy, keyed by strategy name
+    pub fn strategy_statistics(&self) -> &HashMap<String, StrategyStats> {
+        &self.strategy_stats
+    }
+
+    /// The policy currently used to decide whether a penalty warrants healing
+    pub fn threshold_policy(&self) -> ThresholdPolicy {
+        self.threshold_policy
+    }
+
+    /// Replace the threshold policy
+    pub fn set_threshold_policy(&mut self, policy: ThresholdPolicy) {
+        self.threshold_policy = policy;
+    }
+
+    /// Monitor and heal violations
+    pub fn monitor_and_heal(&mut self, context: &str) -> Result<String, String> {
+        self.monitor_and_heal_detailed(context)
+            .map(|report| report.context)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Monitor and heal violations, returning a report of what was decided
+    pub fn monitor_and_heal_detailed(&mut self, context: &str) -> Result<HealReport, HealError> {
+        self.monitor_and_heal_with(context, &HealOptions::default())
+    }
+
+    /// Monitor and heal violations under per-call options.
+    ///
+    /// The deadline bounds the whole call: once it passes, no further strategies
+    /// or verification run and `HealError::DeadlineExceeded` carries the partial report.
+    pub fn monitor_and_heal_with(
+        &mut self,
+        context: &str,
+        options: &HealOptions,
+    ) -> Result<HealReport, HealError> {
+        self.check_budgets()?;
+        let token = self.effective_token(options);
+
+        let covered = self.regularizer.covered_axioms();
+        let missing: Vec<Axiom> = options
+            .required_axioms
+            .iter()
+            .filter(|axiom| !covered.contains(axiom))
+            .cloned()
+            .collect();
+        if options.strict_coverage && !missing.is_empty() {
+            return Err(HealError::CoverageGap { missing });
+        }
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "heal",
+            context_len = context.len(),
+            violations = tracing::field::Empty,
+            penalty = tracing::field::Empty,
+            outcome = tracing::field::Empty,
+        )
+        .entered();
+        let weights = self.observed_weights();
+        let now = self.regularizer.current_timestamp();
+        let checkpoint = self.checkpoints.as_mut().map(|store| store.record(context, now));
+        let started = Instant::now();
+        self.heal_calls += 1;
+        let mut result = self.run_heal(context, &token, &missing);
+        if let Ok(report) = &mut result {
+            self.escalate(report);
+        }
+        if let Ok(report) = &result {
+            let exhausted = match self.retry_policy.on_exhausted {
+                ExhaustionAction::ReturnError => report
+                    .unhealed
+                    .iter()
+                    .any(|(_, reason)| *reason == UnhealedReason::StrategiesFailed),
+                _ => options.strict_healing && report.outcome == HealOutcome::Unhealed,
+            };
+            if exhausted {
+                result = Err(Self::exhausted_error(report.clone()));
+            }
+        }
+        let report = match &result {
+            Ok(report) => Some(report),
+            Err(err) => err.partial(),
+        };
+        if let Some(report) = report {
+            self.latency.record(report, started.elapsed());
+            if let (Some(store), Some(id)) = (self.checkpoints.as_mut(), checkpoint) {
+                store.validate(id, report.violations.is_empty());
+                store.prune(now);
+            }
+            self.feed_back_outcomes(report);
+            self.notify_observers(report, weights);
+            #[cfg(feature = "tracing")]
+            {
+                span.record("violations", report.violations.len());
+                span.record("penalty", report.penalty);
+                span.record("outcome", tracing::field::debug(&report.outcome));
+            }
+            self.record_trend(report);
+            self.account_unhealed(report);
+            let violations = report.violations.clone();
+            self.enqueue_for_sinks(&violations);
+        }
+        if let (Ok(report), Some(sampler)) = (&result, self.audit_sampler.as_mut()) {
+            if report.outcome == HealOutcome::Healed {
+                if let Some(rate) = sampler.sample(report) {
+                    self.audit_queue.push(report.clone(), rate);
+                }
+            }
+        }
+        self.maybe_persist();
+        self.maybe_publish_self_report();
+        result
+    }
+
+    /// Heal each text field of a structured value on its own and write the healed
+    /// text back into the fields that changed.
+    ///
+    /// If healing any field fails, no field is written, though the violations found
+    /// so far are still recorded. If the value refuses a field, the fields before it
+    /// stay written. Reports of fields the value marks `redacted` carry
+    /// `<redacted N chars>` in place of the field's text.
+    pub fn monitor_and_heal_fields<T: Healable + ?Sized>(
+        &mut self,
+        value: &mut T,
+    ) -> Result<FieldReports, FieldHealError> {
+        let mut fields = Vec::new();
+        for (path, text) in value.fields() {
+            let mut report = self
+                .monitor_and_heal_detailed(&text)
+                .map_err(|error| FieldHealError::Heal { path: path.clone(), error })?;
+            for violation in &mut report.violations {
+                violation.metadata.insert("field".to_string(), path.clone());
+            }
+            fields.push((path, report));
+        }
+        for (path, report) in &fields {
+            if report.outcome == HealOutcome::Healed {
+                value.set_field(path, report.context.clone()).map_err(|reason| {
+                    FieldHealError::SetField { path: path.clone(), reason }
+                })?;
+            }
+        }
+        for (path, report) in &mut fields {
+            if value.redacted(path) {
+                report.redact();
+            }
+        }
+        Ok(FieldReports { fields })
+    }
+
+    /// Heal a keyed context and advance its lifecycle in the context tracker.
+    ///
+    /// Each state change is also published as a `HealerEvent::ContextTransition`.
+    pub fn monitor_and_heal_ctx(&mut self, context: &Context<'_>) -> Result<HealReport, HealError> {
+        let result = self.monitor_and_heal_with(context.text, &HealOptions::default());
+        let report = match &result {
+            Ok(report) => Some(report),
+            Err(err) => err.partial(),
+        };
+        if let Some(report) = report {
+            let now = self.regularizer.current_timestamp();
+            for transition in self.contexts.observe(context.key, report, now) {
+                self.push_event(HealerEvent::ContextTransition(transition));
+            }
+        }
+        result
+    }
+
+    pub fn context_tracker(&self) -> &ContextTracker {
+        &self.contexts
+    }
+
+    pub fn context_tracker_mut(&mut self) -> &mut ContextTracker {
+        &mut self.contexts
+    }
+
+    /// Forget contexts not seen for `max_idle`, returning how many were evicted
+    pub fn evict_stale_contexts(&mut self, max_idle: Duration) -> usize {
+        let now = self.regularizer.current_timestamp();
+        let cutoff = now.saturating_sub(max_idle.as_millis() as u64);
+        self.contexts.evict_seen_before(cutoff)
+    }
+
+    /// Plan how `monitor_and_heal` would handle `context`, without healing it.
+    ///
+    /// Nothing is recorded, counted or published, and the known-clean filter is not
+    /// consulted. Custom strategies and correction handlers are called as usual, so
+    /// they should be free of side effects; `QueryUser` is never asked and counts as
+    /// failing. While an escalation fallback is active, a context with violations is
+    /// proposed as the fallback text, as `monitor_and_heal` would return it.
+    pub fn dry_run(&self, context: &str) -> HealingPlan {
+        let mut plan = self.plan_heal(context);
+        if let Err(error) = self.check_budgets() {
+            plan.would_heal = false;
+            plan.blocked_by = Some(error.to_string());
+        }
+        if let Some(fallback) = self.escalation_fallback() {
+            if !plan.violations.is_empty() {
+                let (remaining, _) = self.detect(fallback, None, false);
+                plan.predicted_penalty = self.regularizer.calculate_penalty(&remaining);
+                plan.proposed = fallback.to_string();
+            }
+        }
+        plan
+    }
+
+    fn plan_heal(&self, context: &str) -> HealingPlan {
+        let segments = self.segmenter.as_ref().map(|s| s.segment(context));
+        let (violations, _) = self.detect(context, segments.as_deref(), false);
+        let penalty = self.regularizer.calculate_penalty(&violations);
+        let decision = self.threshold_policy.decide(penalty, violations.len());
+        let mut plan = HealingPlan {
+            violations,
+            penalty,
+            decision,
+            would_heal: decision.heal && self.auto_heal,
+            blocked_by: None,
+            corrections: Vec::new(),
+            outcome: HealOutcome::Clean,
+            proposed: context.to_string(),
+            predicted_penalty: penalty,
+        };
+        if plan.violations.is_empty() {
+            return plan;
+        }
+
+        let token = CancellationToken::new();
+        let mut by_segment: BTreeMap<usize, Vec<&Violation>> = BTreeMap::new();
+        for violation in &plan.violations {
+            let index = violation.metadata.get("segment").and_then(|i| i.parse().ok());
+            by_segment.entry(index.unwrap_or(0)).or_default().push(violation);
+        }
+        let mut replacements = HashMap::new();
+        let mut applied = false;
+        let mut exhausted = false;
+        for (index, violations) in by_segment {
+            let span = segments.as_ref().map_or(0..context.len(), |s| s[index].span.clone());
+            let mut text = context[span].to_string();
+            for violation in violations {
+                let run = self.walk_chain(violation, &text, &token, true);
+                exhausted |= run.entry.chosen.is_none();
+                applied |= !run.applied.is_empty();
+                text = run.context;
+                plan.corrections.push(run.entry);
+            }
+            replacements.insert(index, text);
+        }
+        if exhausted && self.retry_policy.on_exhausted == ExhaustionAction::KeepOriginal {
+            applied = false;
+        }
+        if !applied {
+            plan.outcome = HealOutcome::Unhealed;
+            return plan;
+        }
+
+        let healed = match &segments {
+            Some(segments) => reassemble(context, segments, &replacements),
+            None => replacements.remove(&0).unwrap_or_default(),
+        };
+        let healed_segments = self
+            .segmenter
+            .as_ref()
+            .filter(|_| segments.is_some())
+            .map(|s| s.segment(&healed));
+        let (remaining, _) = self.detect(&healed, healed_segments.as_deref(), false);
+        let after = self.regularizer.calculate_penalty(&remaining);
+        let regressed = after > penalty && self.regresses(&plan.violations, &remaining);
+        if self.verify_after_heal && regressed {
+            plan.outcome = HealOutcome::HealRegressed { before: penalty, after };
+            return plan;
+        }
+        plan.outcome = HealOutcome::Healed;
+        plan.proposed = healed;
+        plan.predicted_penalty = after;
+        plan
+    }
+
+    /// `dry_run` each of `contexts` in turn, as a candidate configuration would
+    /// handle a log of past inputs
+    pub fn replay<I, S>(&self, contexts: I) -> ReplayReport
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        ReplayReport {
+            plans: contexts.into_iter().map(|c| self.dry_run(c.as_ref())).collect(),
+        }
+    }
+
+    /// `replay` the contexts that recorded `history` was detected in, such as the
+    /// results of a `history_query` or `export_history` lines read back with
+    /// `Violation::from_json`.
+    ///
+    /// Violations detected in one call share a context and timestamp, so each call is
+    /// replayed once. A segmented healer recorded segment text, which is what gets
+    /// replayed.
+    pub fn replay_history(&self, history: &[Violation]) -> ReplayReport {
+        let mut seen = HashSet::new();
+        let contexts = history
+            .iter()
+            .filter(|v| !v.metadata.contains_key("coverage_gap"))
+            .filter(|v| seen.insert((v.timestamp, v.context.as_str())))
+            .map(|v| v.context.as_str());
+        self.replay(contexts)
+    }
+
+    /// Classify violations in `after` against those in `before` as fixed, persisting
+    /// or introduced.
+    ///
+    /// Read-only: neither history nor detection tallies are touched. Use
+    /// `compare_and_record` to also record the `after` violations.
+    pub fn compare(&self, before: &str, after: &str) -> ComparisonReport {
+        let before = self.regularizer.scan(before);
+        let after = self.regularizer.scan(after);
+        self.comparison(&before, &after)
+    }
+
+    /// `compare`, then record the violations found in `after`
+    pub fn compare_and_record(&mut self, before: &str, after: &str) -> ComparisonReport {
+        let before = self.regularizer.scan(before);
+        let after = self.regularizer.detect_violations(after);
+        let report = self.comparison(&before, &after);
+        for violation in after {
+            self.regularizer.record_violation(violation);
+        }
+        report
+    }
+
+    fn comparison(&self, before: &[Violation], after: &[Violation]) -> ComparisonReport {
+        let mut report = ComparisonReport {
+            penalty_before: self.regularizer.calculate_penalty(before),
+            penalty_after: self.regularizer.calculate_penalty(after),
+            ..ComparisonReport::default()
+        };
+        for violation in before {
+            let penalty = self.regularizer.calculate_penalty(std::slice::from_ref(violation));
+            report.per_axiom.entry(violation.axiom.clone()).or_default().before += penalty;
+        }
+        for violation in after {
+            let penalty = self.regularizer.calculate_penalty(std::slice::from_ref(violation));
+            report.per_axiom.entry(violation.axiom.clone()).or_default().after += penalty;
+        }
+
+        // Pair by fingerprint; repeated detections pair off one-for-one
+        let mut unmatched: Vec<ComparedViolation> =
+            before.iter().map(ComparedViolation::new).collect();
+        for violation in after.iter().map(ComparedViolation::new) {
+            match unmatched.iter().position(|v| v.fingerprint == violation.fingerprint) {
+                Some(index) => {
+                    unmatched.remove(index);
+                    report.persisting.push(violation);
+                }
+                None => report.introduced.push(violation),
+            }
+        }
+        report.fixed = unmatched;
+        report
+    }
+
+    /// Skip detection for contexts already found clean; `None` turns the filter off.
+    ///
+    /// The filter is cleared whenever the regularizer's rules or the healer's
+    /// segmentation change, as "clean" only holds for the rules it was checked under.
+    pub fn set_known_clean_filter(&mut self, config: Option<KnownCleanConfig>) {
+        let generation = self.regularizer.rules_generation();
+        self.known_clean = config.map(|config| KnownCleanFilter::new(config, generation));
+    }
+
+    pub fn known_clean_statistics(&self) -> Option<&KnownCleanStats> {
+        self.known_clean.as_ref().map(|filter| &filter.stats)
+    }
+
+    fn invalidate_known_clean(&mut self) {
+        if let Some(filter) = self.known_clean.as_mut() {
+            filter.invalidate();
+        }
+    }
+
+    /// Queue a weighted sample of successful heals for review; `None` stops sampling
+    pub fn set_audit_sampler(&mut self, sampler: Option<AuditSampler>) {
+        self.audit_sampler = sampler;
+    }
+
+    /// Heals waiting for human review
+    pub fn audit_queue(&mut self) -> &mut AuditQueue {
+        &mut self.audit_queue
+    }
+
+    /// Weight feedback `ingest_feedback` gives good and bad heals
+    pub fn set_feedback_magnitudes(&mut self, magnitudes: FeedbackMagnitudes) {
+        self.feedback_magnitudes = magnitudes;
+    }
+
+    /// Apply reviewer verdicts on audited heals as weight feedback.
+    ///
+    /// Verdicts are joined by `heal_id` against heals drained from or spilled by the
+    /// audit queue and recorded as reviews there. A `corrected_text` is kept as a
+    /// labeled example. Unknown ids, repeated verdicts and unreadable rows are
+    /// reported in the summary.
+    pub fn ingest_feedback(
+        &mut self,
+        reader: impl BufRead,
+        format: FeedbackFormat,
+    ) -> std::io::Result<FeedbackSummary> {
+        let rows = match format {
+            FeedbackFormat::JsonLines => import::json_rows(reader)?,
+            FeedbackFormat::Csv => import::csv_rows(reader)?,
+        };
+        let mut summary = FeedbackSummary::default();
+        let mut updates = Vec::new();
+        for (line, row) in rows {
+            let parsed = row.and_then(|row| {
+                let id = row.get("heal_id").ok_or("missing heal_id")?;
+                let id: u64 = id.parse().map_err(|_| format!("invalid heal_id '{}'", id))?;
+                let verdict = match row.get("verdict").map(|v| v.to_lowercase()).as_deref() {
+                    Some("good" | "approved" | "accept") => AuditVerdict::Approved,
+                    Some("bad" | "rejected" | "reject") => AuditVerdict::Rejected {
+                        reason: row.get("reason").cloned().unwrap_or_default(),
+                    },
+                    Some(other) => return Err(format!("unknown verdict '{}'", other)),
+                    None => return Err("missing verdict".to_string()),
+                };
+                Ok((id, verdict, row.get("corrected_text").cloned()))
+            });
+            let (id, verdict, corrected) = match parsed {
+                Ok(parsed) => parsed,
+                Err(reason) => {
+                    summary.errors.push(import::RowError { line, reason });
+                    continue;
+                }
+            };
+            if self.audit_queue.reviewed.contains(&id) {
+                summary.duplicates.push((line, id));
+                continue;
+            }
+            let Some(record) = self.audit_queue.outstanding.get(&id).cloned() else {
+                summary.unknown_ids.push((line, id));
+                continue;
+            };
+
+            let magnitude = match verdict {
+                AuditVerdict::Approved => self.feedback_magnitudes.good,
+                AuditVerdict::Rejected { .. } => self.feedback_magnitudes.bad,
+            };
+            let axioms: BTreeSet<&Axiom> = record.axioms.iter().collect();
+            for axiom in axioms {
+                updates.push((axiom.clone(), magnitude));
+                *summary.feedback.entry(axiom.clone()).or_insert(0.0) += magnitude;
+            }
+            if let Some(corrected) = corrected {
+                self.labeled_examples.push(LabeledExample {
+                    heal_id: id,
+                    axioms: record.axioms.clone(),
+                    original: record.original.clone(),
+                    healed: record.healed.clone(),
+                    corrected,
+                });
+                summary.labeled += 1;
+            }
+            self.audit_queue.mark_reviewed(id, verdict);
+            summary.applied += 1;
+        }
+        let weights = self.observed_weights();
+        self.regularizer.update_weights_batch(&updates);
+        self.notify_weight_changes(weights);
+        Ok(summary)
+    }
+
+    /// Reviewer corrections collected by `ingest_feedback`
+    pub fn labeled_examples(&self) -> &[LabeledExample] {
+        &self.labeled_examples
+    }
+
+    /// The context with the violations detection finds in it marked in place.
+    ///
+    /// Like `compare`, this doesn't count as an observation of the context.
+    pub fn detect_annotated(&self, context: &str, style: AnnotationStyle) -> String {
+        annotate(context, &self.regularizer.scan(context), style)
+    }
+
+    /// The original context of `AnnotationStyle::Bracketed` output
+    pub fn strip_annotations(annotated: &str) -> String {
+        let mut out = String::with_capacity(annotated.len());
+        let mut chars = annotated.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '⟦' {
+                out.push(c);
+                continue;
+            }
+            if chars.next_if_eq(&'⟦').is_some() {
+                out.push('⟦');
+                continue;
+            }
+            let tag: String = chars.by_ref().take_while(|&c| c != '⟧').collect();
+            // Notes on unlocated violations each start on a line of their own
+            if tag.starts_with('^') && out.ends_with('\n') {
+                out.pop();
+            }
+        }
+        out
+    }
+
+    /// Sample messages for `audit`; `None` checks every message
+    pub fn set_detection_sampling(&mut self, sampling: Option<DetectionSampling>) {
+        self.sampler = sampling.map(DetectionSampler::new);
+    }
+
+    pub fn sampling_statistics(&self) -> Option<&SamplingStats> {
+        self.sampler.as_ref().map(|sampler| sampler.stats())
+    }
+
+    /// Detect and record violations without healing, subject to detection sampling.
+    ///
+    /// Returns `None` when the message was sampled out.
+    pub fn audit(&mut self, context: &str) -> Option<Vec<Violation>> {
+        let decision = match self.sampler.as_mut() {
+            Some(sampler) => sampler.decide(context),
+            None => SampleDecision::Check { rate: 1.0 },
+        };
+        if decision == SampleDecision::Skip {
+            return None;
+        }
+        let violations = self.regularizer.detect_violations(context);
+        if let Some(sampler) = self.sampler.as_mut() {
+            sampler.record(context, decision, !violations.is_empty());
+        }
+        for violation in &violations {
+            self.regularizer.record_violation(violation.clone());
+        }
+        self.enqueue_for_sinks(&violations);
+        Some(violations)
+    }
+
+    fn run_heal(
+        &mut self,
+        context: &str,
+        token: &CancellationToken,
+        coverage_gaps: &[Axiom],
+    ) -> Result<HealReport, HealError> {
+        let source_fingerprint = fingerprint(context);
+        let generation = self.regularizer.rules_generation();
+        let known = match self.known_clean.as_mut() {
+            Some(filter) if coverage_gaps.is_empty() => {
+                filter.sync(generation);
+                filter.check(source_fingerprint)
+            }
+            _ => KnownClean::Unknown,
+        };
+        if known == KnownClean::Skip {
+            return Ok(HealReport {
+                context: context.to_string(),
+                violations: Vec::new(),
+                penalty: 0.0,
+                decision: self.threshold_policy.decide(0.0, 0),
+                outcome: HealOutcome::Clean,
+                unhealed: Vec::new(),
+                attempts: Vec::new(),
+                skipped_known_clean: true,
+                tiers: TiersRun::default(),
+            });
+        }
+
+        let segments = self.segmenter.as_ref().map(|s| s.segment(context));
+        let (mut violations, tiers) = self.detect(context, segments.as_deref(), true);
+        let gaps: Vec<Violation> = coverage_gaps.iter().map(|a| self.coverage_gap(a)).collect();
+        violations.extend(gaps.iter().cloned());
+        let penalty = self.regularizer.calculate_penalty(&violations);
+        let decision = self.threshold_policy.decide(penalty, violations.len());
+
+        let mut report = HealReport {
+            context: context.to_string(),
+            violations,
+            penalty,
+            decision,
+            outcome: HealOutcome::Clean,
+            unhealed: Vec::new(),
+            attempts: Vec::new(),
+            skipped_known_clean: false,
+            tiers,
+        };
+
+        if let Some(filter) = self.known_clean.as_mut() {
+            if known == KnownClean::Unknown && report.violations.is_empty() {
+                filter.bloom.insert(source_fingerprint);
+                filter.stats.inserted += 1;
+            }
+            if known == KnownClean::Recheck && !report.violations.is_empty() {
+                filter.stats.false_positives += 1;
+                self.push_event(HealerEvent::KnownCleanFalsePositive {
+                    fingerprint: source_fingerprint,
+                });
+            }
+        }
+
+        if report.violations.is_empty() {
+            return Ok(report);
+        }
+
+        if !(decision.heal && self.auto_heal) {
+            // Record violations but don't heal
+            for v in &report.violations {
+                self.regularizer.record_violation(v.clone());
+            }
+            report.outcome = HealOutcome::Recorded;
+            report.unhealed = report
+                .violations
+                .iter()
+                .map(|v| (v.clone(), UnhealedReason::NotAttempted))
+                .collect();
+            return Ok(report);
+        }
+
+        // Coverage gaps describe the configuration, not the text, so they are
+        // recorded but never handed to strategies
+        let healable: Vec<Violation> = report
+            .violations
+            .iter()
+            .filter(|v| !v.metadata.contains_key("coverage_gap"))
+            .cloned()
+            .collect();
+        let mut pass = match &segments {
+            Some(segments) => self.heal_segments(&healable, context, segments, token),
+            None => self.heal_violations(&healable, context, token),
+        };
+        for gap in gaps {
+            self.regularizer.record_violation(gap.clone());
+            pass.unhealed.push((gap, UnhealedReason::NotAttempted));
+        }
+        report.unhealed = pass.unhealed;
+        report.attempts = pass.attempts;
+
+        let interrupted = pass.interrupted.or_else(|| token.interruption());
+        if let Some(reason) = interrupted {
+            report.outcome = if pass.applied.is_empty() {
+                HealOutcome::Recorded
+            } else {
+                HealOutcome::Healed
+            };
+            report.context = pass.context;
+            return Err(Self::interrupted_error(reason, report));
+        }
+        if healable.is_empty() {
+            report.outcome = HealOutcome::Recorded;
+            return Ok(report);
+        }
+        let exhausted = report
+            .unhealed
+            .iter()
+            .any(|(_, reason)| *reason == UnhealedReason::StrategiesFailed);
+        if exhausted && self.retry_policy.on_exhausted == ExhaustionAction::KeepOriginal {
+            let kept = report.attempts.iter().filter(|entry| {
+                entry.chosen.is_some() && entry.selection != SelectionReason::PartiallyHealed
+            });
+            for entry in kept {
+                report.unhealed.push((entry.violation.clone(), UnhealedReason::Reverted));
+            }
+            pass.applied.clear();
+        }
+        if pass.applied.is_empty() {
+            report.outcome = HealOutcome::Unhealed;
+            return Ok(report);
+        }
+
+        let segmented = segments.is_some();
+        let (healed_context, outcome) =
+            self.verify_heal(context, pass.context, &report.violations, &pass.applied, segmented);
+        if let HealOutcome::HealRegressed { .. } = outcome {
+            // The whole heal was discarded, so nothing in it counts as healed
+            report.unhealed = report
+                .violations
+                .iter()
+                .map(|v| (v.clone(), UnhealedReason::Regressed))
+                .collect();
+        }
+        report.context = healed_context;
+        report.outcome = outcome;
+        Ok(report)
+    }
+
+    /// Synthetic Completeness violation reporting that `axiom` was never evaluated
+    fn coverage_gap(&self, axiom: &Axiom) -> Violation {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("coverage_gap".to_string(), format!("{:?}", axiom));
+        metadata.insert("rule".to_string(), "the required-axiom coverage check".to_string());
+        Violation {
+            axiom: Axiom::Completeness,
+            severity: Severity::High,
+            context: format!("No registered detector covers required axiom {:?}", axiom),
+            timestamp: self.regularizer.current_timestamp(),
+            metadata,
+        }
+    }
+
+    /// Fold the per-call deadline (or the healer default) into a single token
+    fn effective_token(&self, options: &HealOptions) -> CancellationToken {
+        let token = options.cancel.clone().unwrap_or_default();
+        let deadline = options
+            .deadline
+            .or_else(|| self.default_deadline.map(|d| Instant::now() + d));
+        match deadline {
+            Some(deadline) => token.with_deadline(deadline),
+            None => token,
+        }
+    }
+
+    /// `UserInterventionRequired` when a failed chain ended by asking the user
+    fn exhausted_error(partial: HealReport) -> HealError {
+        let mut axioms: Vec<Axiom> = partial
+            .attempts
+            .iter()
+            .filter(|entry| {
+                let last = entry.attempts.last();
+                entry.selection == SelectionReason::Escalated
+                    || last.is_some_and(|a| a.strategy == "query_user" && a.result.is_err())
+            })
+            .map(|entry| entry.violation.axiom.clone())
+            .collect();
+        axioms.sort();
+        axioms.dedup();
+        let partial = Box::new(partial);
+        match axioms.is_empty() {
+            true => HealError::StrategiesExhausted { partial },
+            false => HealError::UserInterventionRequired { axioms, partial },
+        }
+    }
+
+    fn interrupted_error(reason: UnhealedReason, partial: HealReport) -> HealError {
+        let partial = Box::new(partial);
+        match reason {
+            UnhealedReason::Cancelled => HealError::Cancelled { partial },
+            _ => HealError::DeadlineExceeded { partial },
+        }
+    }
+
+    /// Guard against heals that leave the context scoring worse than before
+    fn verify_heal(
+        &mut self,
+        original: &str,
+        healed: String,
+        violations: &[Violation],
+        applied: &[&'static str],
+        segmented: bool,
+    ) -> (String, HealOutcome) {
+        if !self.verify_after_heal {
+            return (healed, HealOutcome::Healed);
+        }
+
+        let before = self.regularizer.calculate_penalty(violations);
+        let healed_segments = self
+            .segmenter
+            .as_ref()
+            .filter(|_| segmented)
+            .map(|s| s.segment(&healed));
+        let (remaining, _) = self.detect(&healed, healed_segments.as_deref(), false);
+        let after = self.regularizer.calculate_penalty(&remaining);
+        if after <= before || !self.regresses(violations, &remaining) {
+            return (healed, HealOutcome::Healed);
+        }
+
+        for name in applied {
+            self.strategy_stats.entry(name.to_string()).or_default().regressions += 1;
+        }
+        (original.to_string(), HealOutcome::HealRegressed { before, after })
+    }
+
+    /// Whether going from `violations` to `remaining` raised the penalty of an axiom
+    /// whose regressions are not accepted; only those axioms count against a heal
+    fn regresses(&self, violations: &[Violation], remaining: &[Violation]) -> bool {
+        let per_axiom = |vs: &[Violation]| {
+            let mut totals: HashMap<Axiom, f64> = HashMap::new();
+            for v in vs {
+                *totals.entry(v.axiom.clone()).or_insert(0.0) +=
+                    self.regularizer.calculate_penalty(std::slice::from_ref(v));
+            }
+            totals
+        };
+        let (before_axioms, after_axioms) = (per_axiom(violations), per_axiom(remaining));
+        after_axioms.iter().any(|(axiom, penalty)| {
+            *penalty > before_axioms.get(axiom).copied().unwrap_or(0.0)
+                && !self.accepted_regressions.contains(axiom)
+        })
+    }
+
+    /// Apply correction strategies to heal violations
+    ///
+    /// Stops attempting strategies as soon as the token is cancelled or expires; the
+    /// remaining violations are recorded as unhealed for that reason.
+    fn heal_violations(
+        &mut self,
+        violations: &[Violation],
+        context: &str,
+        token: &CancellationToken,
+    ) -> HealPass {
+        let mut pass = HealPass {
+            context: context.to_string(),
+            applied: Vec::new(),
+            unhealed: Vec::new(),
+            attempts: Vec::new(),
+            interrupted: None,
+        };
+
+        for violation in violations {
+            let run = self.walk_chain(violation, &pass.context, token, false);
+            for attempt in &run.entry.attempts {
+                let stats = self.strategy_stats.entry(attempt.strategy.to_string()).or_default();
+                stats.attempts += 1;
+                match attempt.result {
+                    Ok(()) => stats.successes += 1,
+                    Err(_) => stats.failures += 1,
+                }
+            }
+            for name in run.contracts_broken {
+                self.strategy_stats.entry(name.to_string()).or_default().contract_violations += 1;
+            }
+            pass.context = run.context;
+            pass.applied.extend(run.applied);
+            pass.interrupted = run.interrupted.or(pass.interrupted);
+            if run.entry.selection == SelectionReason::PartiallyHealed {
+                pass.unhealed.push((violation.clone(), UnhealedReason::PartiallyHealed));
+            }
+            if run.entry.chosen.is_none() {
+                let reason = token
+                    .interruption()
+                    .or(pass.interrupted)
+                    .unwrap_or(UnhealedReason::StrategiesFailed);
+                pass.unhealed.push((violation.clone(), reason));
+            }
+            pass.attempts.push(run.entry);
+            self.regularizer.record_violation(violation.clone());
+        }
+
+        pass
+    }
+
+    /// Try `violation`'s strategy chain on `context`, leaving statistics and history
+    /// alone. In a dry run `QueryUser` is never asked and counts as failing.
+    fn walk_chain(
+        &self,
+        violation: &Violation,
+        context: &str,
+        token: &CancellationToken,
+        dry_run: bool,
+    ) -> ChainRun {
+        let mut run = ChainRun {
+            entry: ViolationAttempts {
+                violation: violation.clone(),
+                attempts: Vec::new(),
+                chosen: None,
+                selection: SelectionReason::NoStrategies,
+                change: None,
+            },
+            context: context.to_string(),
+            applied: Vec::new(),
+            interrupted: None,
+            contracts_broken: Vec::new(),
+        };
+        let entry = &mut run.entry;
+        let retry = self.retry_policy;
+        let strategies = self
+            .correction_strategies
+            .get(&violation.axiom)
+            .filter(|chain| !chain.is_empty());
+        let Some(strategies) = strategies else {
+            if let Some(reason) = token.interruption() {
+                run.interrupted = Some(reason);
+                entry.selection = SelectionReason::Interrupted;
+            }
+            return run;
+        };
+        entry.selection = SelectionReason::AllFailed;
+        // Each strategy is retried in place before falling back to the next
+        let tried = if retry.escalate { strategies.len() } else { 1 };
+        let mut partial = None;
+        'chain: for strategy in strategies.iter().take(tried) {
+            let tries = strategy.max_attempts().unwrap_or(retry.max_attempts).max(1);
+            for _ in 0..tries {
+                if let Some(reason) = token.interruption() {
+                    run.interrupted = Some(reason);
+                    entry.selection = SelectionReason::Interrupted;
+                    break 'chain;
+                }
+                let result = match strategy {
+                    CorrectionStrategy::QueryUser if dry_run => {
+                        Ok(Correction::Failed("Not asked in a dry run".to_string()))
+                    }
+                    _ => self.apply_correction(strategy, &run.context, violation),
+                };
+                let failed = |entry: &mut ViolationAttempts, reason: String| {
+                    entry.attempts.push(StrategyAttempt {
+                        strategy: strategy.name(),
+                        result: Err(reason),
+                    });
+                };
+                let (corrected, healed) = match result {
+                    Ok(Correction::Healed(corrected)) => (corrected, true),
+                    Ok(Correction::Partial(corrected)) => (corrected, false),
+                    Ok(Correction::Escalate(reason)) => {
+                        failed(entry, reason);
+                        entry.selection = SelectionReason::Escalated;
+                        break 'chain;
+                    }
+                    Ok(Correction::Failed(reason)) => {
+                        failed(entry, reason);
+                        continue;
+                    }
+                    Err(failure) => {
+                        if let StrategyFailure::Contract(_) = &failure {
+                            run.contracts_broken.push(strategy.name());
+                        }
+                        failed(entry, failure.to_string());
+                        continue;
+                    }
+                };
+                entry.attempts.push(StrategyAttempt {
+                    strategy: strategy.name(),
+                    result: Ok(()),
+                });
+                entry.change = TextChange::between(&run.context, &corrected, &self.quote_policy);
+                run.context = corrected;
+                run.applied.push(strategy.name());
+                if healed {
+                    entry.chosen = Some(strategy.name());
+                    entry.selection = SelectionReason::FirstInChainOrder;
+                    break 'chain;
+                }
+                partial = Some(strategy.name());
+                continue 'chain;
+            }
+        }
+        if entry.chosen.is_none() && entry.selection == SelectionReason::AllFailed {
+            if let Some(name) = partial {
+                entry.chosen = Some(name);
+                entry.selection = SelectionReason::PartiallyHealed;
+            }
+        }
+        run
+    }
+
+    /// Heal each violating segment on its own text, then splice the results back
+    fn heal_segments(
+        &mut self,
+        violations: &[Violation],
+        context: &str,
+        segments: &[Segment],
+        token: &CancellationToken,
+    ) -> HealPass {
+        let mut by_segment: BTreeMap<usize, Vec<Violation>> = BTreeMap::new();
+        for violation in violations {
+            let index = violation.metadata.get("segment").and_then(|i| i.parse().ok());
+            if let Some(index) = index {
+                by_segment.entry(index).or_default().push(violation.clone());
+            }
+        }
+
+        let mut replacements = HashMap::new();
+        let mut pass = HealPass {
+            context: String::new(),
+            applied: Vec::new(),
+            unhealed: Vec::new(),
+            attempts: Vec::new(),
+            interrupted: None,
+        };
+        for (index, segment_violations) in by_segment {
+            let text = &context[segments[index].span.clone()];
+            let segment_pass = self.heal_violations(&segment_violations, text, token);
+            if segment_pass.context != text {
+                replacements.insert(index, segment_pass.context);
+            }
+            pass.applied.extend(segment_pass.applied);
+            pass.unhealed.extend(segment_pass.unhealed);
+            pass.attempts.extend(segment_pass.attempts);
+            pass.interrupted = pass.interrupted.or(segment_pass.interrupted);
+        }
+
+        pass.context = reassemble(context, segments, &replacements);
+        pass
+    }
+
+    /// Apply a specific correction strategy
+    fn apply_strategy(
+        &self,
+        strategy: &CorrectionStrategy,
+        context: &str,
+        violation: &Violation,
+    ) -> Result<String, String> {
+        match strategy {
+            CorrectionStrategy::Rollback => match &self.checkpoints {
+                Some(store) => store
+                    .latest_validated()
+                    .map(|checkpoint| checkpoint.context.clone())
+                    .ok_or_else(|| "No validated checkpoint to roll back to".to_string()),
+                None => Ok(format!("[ROLLED_BACK] {}", context)),
+            },
+            CorrectionStrategy::Recompute => {
+                Ok(context.replace("inconsistent", "consistent"))
+            }
+            CorrectionStrategy::Interpolate => {
+                Ok(format!("{} [INTERPOLATED]", context))
+            }
+            CorrectionStrategy::QueryUser => match &self.user_query {
+                Some(query) => query(context, violation),
+                None => Err("User intervention required".to_string()),
+            },
+            CorrectionStrategy::ApplyDefault => {
+                Ok(format!("{} [DEFAULT_APPLIED]", context))
+            }
+            CorrectionStrategy::ExciseSentence => rewrite_sentences(context, violation, None),
+            CorrectionStrategy::ReplaceSentence { template } => {
+                let axiom = format!("{:?}", violation.axiom).to_lowercase();
+                rewrite_sentences(context, violation, Some(&template.replace("{axiom}", &axiom)))
+            }
+            CorrectionStrategy::Custom(custom) => custom.0.apply(context, violation),
+        }
+    }
+
+    /// Apply a strategy and hold its output to the strategy's contract
+    fn apply_checked(
+        &self,
+        strategy: &CorrectionStrategy,
+        context: &str,
+        violation: &Violation,
+    ) -> Result<String, StrategyFailure> {
+        let output = self
+            .apply_strategy(strategy, context, violation)
+            .map_err(StrategyFailure::Failed)?;
+        self.check_contract(strategy, context, output, violation)
+    }
+
+    /// Like `apply_checked`, keeping the full outcome of application strategies
+    fn apply_correction(
+        &self,
+        strategy: &CorrectionStrategy,
+        context: &str,
+        violation: &Violation,
+    ) -> Result<Correction, StrategyFailure> {
+        let CorrectionStrategy::Custom(custom) = strategy else {
+            return self.apply_checked(strategy, context, violation).map(Correction::Healed);
+        };
+        match custom.0.correct(context, violation) {
+            Correction::Healed(output) => self
+                .check_contract(strategy, context, output, violation)
+                .map(Correction::Healed),
+            Correction::Partial(output) => self
+                .check_contract(strategy, context, output, violation)
+                .map(Correction::Partial),
+            other => Ok(other),
+        }
+    }
+
+    fn check_contract(
+        &self,
+        strategy: &CorrectionStrategy,
+        context: &str,
+        output: String,
+        violation: &Violation,
+    ) -> Result<String, StrategyFailure> {
+        match strategy.contract().check(context, &output, violation) {
+            Ok(()) => Ok(output),
+            Err(broken) if self.contract_enforcement == ContractEnforcement::Panic => {
+                panic!("strategy contract broken by {}: {}", strategy.name(), broken)
+            }
+            Err(broken) => Err(StrategyFailure::Contract(broken)),
+        }
+    }
+
+    /// How strategies are retried and escalated, and what happens when they run out
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Wire `CorrectionStrategy::QueryUser` to the application; without a handler it
+    /// always fails with "User intervention required"
+    pub fn set_user_query(&mut self, query: Option<Box<UserQuery>>) {
+        self.user_query = query;
+    }
+
+    /// What to do when a strategy breaks its `StrategyContract`
+    pub fn set_contract_enforcement(&mut self, enforcement: ContractEnforcement) {
+        self.contract_enforcement = enforcement;
+    }
+
+    /// Compute the outcome of every registered strategy for a violation without applying any
+    pub fn propose(&self, context: &str, violation: &Violation) -> Vec<Proposal> {
+        self.propose_with(context, violation, &PreviewOptions::default())
+    }
+
+    /// Like `propose`, skipping strategies excluded by the preview options
+    pub fn propose_with(
+        &self,
+        context: &str,
+        violation: &Violation,
+        options: &PreviewOptions,
+    ) -> Vec<Proposal> {
+        let strategies = match self.correction_strategies.get(&violation.axiom) {
+            Some(strategies) => strategies,
+            None => return Vec::new(),
+        };
+        let source_fingerprint = fingerprint(context);
+
+        strategies
+            .iter()
+            .filter(|strategy| options.max_cost.is_none_or(|cap| strategy.cost() <= cap))
+            .map(|strategy| {
+                let result = self
+                    .apply_checked(strategy, context, violation)
+                    .map_err(|failure| failure.to_string());
+                let verification = result.as_ref().ok().map(|candidate| {
+                    let remaining = self.regularizer.scan(candidate);
+                    Verification {
+                        penalty: self.regularizer.calculate_penalty(&remaining),
+                        resolved: !remaining.iter().any(|v| v.axiom == violation.axiom),
+                        remaining,
+                    }
+                });
+
+                Proposal {
+                    strategy: strategy.clone(),
+                    strategy_name: strategy.name(),
+                    violation: violation.clone(),
+                    result,
+                    verification,
+                    source_fingerprint,
+                }
+            })
+            .collect()
+    }
+
+    /// Apply a proposal previously computed by `propose` for the same context
+    pub fn apply_proposal(&self, context: &str, proposal: &Proposal) -> Result<String, String> {
+        if fingerprint(context) != proposal.source_fingerprint {
+            return Err("Proposal was computed for a different context".to_string());
+        }
+
+        let corrected = proposal.result.clone()?;
+        self.regularizer.record_violation(proposal.violation.clone());
+        Ok(corrected)
+    }
+
+    /// Get violation statistics
+    pub fn get_statistics(&self) -> ViolationStatistics {
+        self.regularizer.statistics()
+    }
+
+    /// Filter the regularizer's buffered history; see
+    /// `AdaptiveAxiomaticRegularizer::history_query`
+    pub fn history_query(&self) -> HistoryQuery<'_> {
+        self.regularizer.history_query()
+    }
+
+    /// The regularizer this healer detects and scores with
+    pub fn regularizer(&self) -> &AdaptiveAxiomaticRegularizer {
+        &self.regularizer
+    }
+}
+
+/// Tunable healer state: learned weights, thresholds and strategy chains.
+///
+/// Violation history and detection rules are not part of the snapshot; rules come
+/// from the regularizer the snapshot is restored onto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealerSnapshot {
+    pub weights: BTreeMap<Axiom, f64>,
+    pub learning_rate: f64,
+    pub threshold: f64,
+    pub threshold_policy: ThresholdPolicy,
+    pub auto_heal: bool,
+    pub verify_after_heal: bool,
+    /// Strategy chains without their custom strategies, which are code rather than state
+    pub strategies: BTreeMap<Axiom, Vec<CorrectionStrategy>>,
+    pub accepted_regressions: BTreeSet<Axiom>,
+    /// Lifecycle state of tracked contexts
+    pub contexts: BTreeMap<String, ContextRecord>,
+    pub disabled_axioms: BTreeSet<Axiom>,
+    /// Per-axiom overrides of `learning_rate`
+    pub learning_rates: BTreeMap<Axiom, f64>,
+    /// The feedback policy's decayed step scale per axiom
+    pub rate_scales: BTreeMap<Axiom, f64>,
+    /// Violations in the history per axiom when the snapshot was taken
+    pub violation_counts: BTreeMap<Axiom, usize>,
+    /// Healing attempts per strategy name
+    pub strategy_stats: BTreeMap<String, StrategyStats>,
+}
+
+/// Learned regularizer state: what `save_state` writes, as a value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegularizerState {
+    /// Version of the `schema::STATE` layout the value follows
+    pub version: u32,
+    pub learning_rate: f64,
+    pub threshold: f64,
+    /// Keyed by the axiom's `Debug` form with serde, as in the state file
+    #[cfg_attr(feature = "serde", serde(with = "axiom_keys"))]
+    pub weights: BTreeMap<Axiom, f64>,
+    /// Per-axiom overrides of `learning_rate`
+    #[cfg_attr(feature = "serde", serde(with = "axiom_keys"))]
+    pub learning_rates: BTreeMap<Axiom, f64>,
+    /// The feedback policy's decayed step scale per axiom
+    #[cfg_attr(feature = "serde", serde(with = "axiom_keys"))]
+    pub rate_scales: BTreeMap<Axiom, f64>,
+    pub history: Vec<Violation>,
+    /// Encoded `HealerSnapshot`, when taken from a healer
+    pub healer: Option<String>,
+}
+
+/// Serde for maps keyed by axiom. A custom axiom serializes as a newtype variant,
+/// which formats like JSON can't use as a map key, so keys go through the axiom's
+/// `Debug` form and `FromStr` instead.
+#[cfg(feature = "serde")]
+mod axiom_keys {
+    use super::Axiom;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<V: Serialize, S: Serializer>(
+        map: &BTreeMap<Axiom, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(axiom, value)| (format!("{:?}", axiom), value)))
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<Axiom, V>, D::Error> {
+        BTreeMap::<String, V>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, value)| Ok((name.parse().map_err(D::Error::custom)?, value)))
+            .collect()
+    }
+}
+
+impl RegularizerState {
+    fn into_parts(self) -> Result<(import::StateHeader, Vec<Violation>), StateError> {
+        if self.version != schema::STATE.version {
+            let found = format!("{}.v{}", schema::STATE.kind, self.version);
+            let error = schema::SchemaError { found, supported: schema::STATE };
+            return Err(StateError::Header(schema::ArtifactError::Schema(error)));
+        }
+        let header = import::StateHeader {
+            learning_rate: self.learning_rate,
+            threshold: self.threshold,
+            weights: self.weights,
+            learning_rates: self.learning_rates,
+            rate_scales: self.rate_scales,
+            history: self.history.len(),
+            healer: self.healer,
+        };
+        Ok((header, self.history))
+    }
+}
+
+/// Why `load_state` could not restore saved state
+#[derive(Debug)]
+pub enum StateError {
+    Io(std::io::Error),
+    /// The first line is missing or is not a state header this build reads
+    Header(schema::ArtifactError),
+    /// A history line could not be read (1-based line number in the file)
+    History { line: usize, error: schema::ArtifactError },
+    /// The file ends before the number of violations its header declares
+    Truncated { expected: usize, found: usize },
+    /// The embedded healer snapshot is invalid
+    Snapshot(SnapshotError),
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Io(e) => write!(f, "State I/O error: {}", e),
+            StateError::Header(e) => write!(f, "State header: {}", e),
+            StateError::History { line, error } => write!(f, "State line {}: {}", line, error),
+            StateError::Truncated { expected, found } => write!(
+                f,
+                "State has {} history lines, header declares {}",
+                found, expected
+            ),
+            StateError::Snapshot(e) => write!(f, "State snapshot: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl From<std::io::Error> for StateError {
+    fn from(e: std::io::Error) -> Self {
+        StateError::Io(e)
+    }
+}
+
+/// Reasons a snapshot file could not be read
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    /// The file does not start with a snapshot header
+    BadHeader,
+    /// The header names a snapshot version this build can't read
+    Schema(schema::SchemaError),
+    /// The body is shorter or longer than the header promised (e.g. a partial write)
+    LengthMismatch { expected: usize, found: usize },
+    ChecksumMismatch,
+    /// A body line could not be parsed (1-based line number within the body)
+    Parse { line: usize, reason: String },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "Snapshot I/O error: {}", e),
+            SnapshotError::BadHeader => write!(f, "Missing or malformed snapshot header"),
+            SnapshotError::Schema(e) => e.fmt(f),
+            SnapshotError::LengthMismatch { expected, found } => write!(
+                f,
+                "Snapshot body is {} bytes, header declares {}",
+                found, expected
+            ),
+            SnapshotError::ChecksumMismatch => write!(f, "Snapshot checksum mismatch"),
+            SnapshotError::Parse { line, reason } => {
+                write!(f, "Snapshot line {}: {}", line, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(e: std::io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+impl HealerSnapshot {
+    pub const FORMAT_VERSION: u32 = schema::SNAPSHOT.version;
+    const MAGIC: &'static str = "AARSNAP";
+
+    /// Encode as `AARSNAP <version> <body length> <checksum>` followed by the body,
+    /// so a partially written file can never decode as valid
+    pub fn encode(&self) -> String {
+        let mut body = String::new();
+        body.push_str(&format!("learning_rate {}\n", self.learning_rate));
+        body.push_str(&format!("threshold {}\n", self.threshold));
+        let policy = match self.threshold_policy {
+            ThresholdPolicy::HealAbove(v) => format!("heal_above {}", v),
+            ThresholdPolicy::HealAtOrAbove(v) => format!("heal_at_or_above {}", v),
+            ThresholdPolicy::HealOnAnyViolation => "heal_on_any_violation".to_string(),
+            ThresholdPolicy::Never => "never".to_string(),
+        };
+        body.push_str(&format!("policy {}\n", policy));
+        body.push_str(&format!("auto_heal {}\n", self.auto_heal));
+        body.push_str(&format!("verify_after_heal {}\n", self.verify_after_heal));
+        for (axiom, weight) in &self.weights {
+            body.push_str(&format!("weight {:?} {}\n", axiom, weight));
+        }
+        for (axiom, rate) in &self.learning_rates {
+            body.push_str(&format!("axiom_learning_rate {:?} {}\n", axiom, rate));
+        }
+        for (axiom, scale) in &self.rate_scales {
+            body.push_str(&format!("rate_scale {:?} {}\n", axiom, scale));
+        }
+        // Each strategies line is followed by the template of every replace_sentence
+        // in its chain, in order
+        for (axiom, chain) in &self.strategies {
+            let names: Vec<_> = chain.iter().map(|s| s.name()).collect();
+            body.push_str(&format!("strategies {:?} {}\n", axiom, names.join(",")));
+            for strategy in chain {
+                if let CorrectionStrategy::ReplaceSentence { template } = strategy {
+                    body.push_str(&format!("sentence_template {}\n", escape_line(template)));
+                }
+            }
+        }
+        for axiom in &self.accepted_regressions {
+            body.push_str(&format!("accept_regression {:?}\n", axiom));
+        }
+        // Each context line is followed by the transitions in its history
+        for (key, record) in &self.contexts {
+            body.push_str(&format!(
+                "context {} {} {}\n",
+                record.state.name(),
+                record.last_seen,
+                escape_line(key)
+            ));
+            for t in &record.history {
+                body.push_str(&format!(
+                    "transition {} {} {}\n",
+                    t.from.name(),
+                    t.to.name(),
+                    t.timestamp
+                ));
+            }
+        }
+        for axiom in &self.disabled_axioms {
+            body.push_str(&format!("disabled {:?}\n", axiom));
+        }
+        for (axiom, count) in &self.violation_counts {
+            body.push_str(&format!("violations {:?} {}\n", axiom, count));
+        }
+        // The name goes last since custom strategy names may contain spaces
+        for (name, stats) in &self.strategy_stats {
+            body.push_str(&format!(
+                "strategy_stats {} {} {} {} {} {}\n",
+                stats.attempts,
+                stats.successes,
+                stats.failures,
+                stats.regressions,
+                stats.contract_violations,
+                escape_line(name)
+            ));
+        }
+
+        format!(
+            "{} {} {} {:016x}\n{}",
+            Self::MAGIC,
+            Self::FORMAT_VERSION,
+            body.len(),
+            fingerprint(&body),
+            body
+        )
+    }
+
+    pub fn decode(data: &str) -> Result<Self, SnapshotError> {
+        let (header, body) = data.split_once('\n').ok_or(SnapshotError::BadHeader)?;
+        let fields: Vec<&str> = header.split(' ').collect();
+        if fields.len() != 4 || fields[0] != Self::MAGIC {
+            return Err(SnapshotError::BadHeader);
+        }
+        let version: u32 = fields[1].parse().map_err(|_| SnapshotError::BadHeader)?;
+        let found = format!("{}.v{}", schema::SNAPSHOT.kind, version);
+        if version > Self::FORMAT_VERSION {
+            return Err(SnapshotError::Schema(schema::SchemaError {
+                found,
+                supported: schema::SNAPSHOT,
+            }));
+        }
+        let expected: usize = fields[2].parse().map_err(|_| SnapshotError::BadHeader)?;
+        if body.len() != expected {
+            return Err(SnapshotError::LengthMismatch {
+                expected,
+                found: body.len(),
+            });
+        }
+        let checksum = u64::from_str_radix(fields[3], 16).map_err(|_| SnapshotError::BadHeader)?;
+        if fingerprint(body) != checksum {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+        let body = schema::Migrations::builtin()
+            .migrate(&found, schema::SNAPSHOT, body.to_string())
+            .map_err(SnapshotError::Schema)?;
+
+        let mut snapshot = HealerSnapshot {
+            weights: BTreeMap::new(),
+            learning_rate: 0.0,
+            threshold: 0.0,
+            threshold_policy: ThresholdPolicy::Never,
+            auto_heal: true,
+            verify_after_heal: false,
+            strategies: BTreeMap::new(),
+            accepted_regressions: BTreeSet::new(),
+            contexts: BTreeMap::new(),
+            disabled_axioms: BTreeSet::new(),
+            learning_rates: BTreeMap::new(),
+            rate_scales: BTreeMap::new(),
+            violation_counts: BTreeMap::new(),
+            strategy_stats: BTreeMap::new(),
+        };
+        let mut current_context: Option<String> = None;
+        // Chain positions still waiting for their sentence_template line, last first
+        let mut untemplated: Vec<(Axiom, usize)> = Vec::new();
+        let missing_template = "replace_sentence without a sentence_template line";
+        for (index, line) in body.lines().enumerate() {
+            let parse_err = |reason: String| SnapshotError::Parse {
+                line: index + 1,
+                reason,
+            };
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let number = |text: &str| {
+                text.parse::<f64>()
+                    .map_err(|_| parse_err(format!("invalid number '{}'", text)))
+            };
+            let flag = |text: &str| {
+                text.parse::<bool>()
+                    .map_err(|_| parse_err(format!("invalid flag '{}'", text)))
+            };
+            let axiom = |text: &str| text.parse::<Axiom>().map_err(parse_err);
+            let state = |text: &str| {
+                ContextState::from_name(text)
+                    .ok_or_else(|| parse_err(format!("unknown context state '{}'", text)))
+            };
+            let timestamp = |text: &str| {
+                text.parse::<u64>()
+                    .map_err(|_| parse_err(format!("invalid timestamp '{}'", text)))
+            };
+            let count = |text: &str| {
+                text.parse::<usize>().map_err(|_| parse_err(format!("invalid count '{}'", text)))
+            };
+
+            match key {
+                "learning_rate" => snapshot.learning_rate = number(rest)?,
+                "threshold" => snapshot.threshold = number(rest)?,
+                "policy" => {
+                    let (kind, value) = rest.split_once(' ').unwrap_or((rest, ""));
+                    snapshot.threshold_policy = match kind {
+                        "heal_above" => ThresholdPolicy::HealAbove(number(value)?),
+                        "heal_at_or_above" => ThresholdPolicy::HealAtOrAbove(number(value)?),
+                        "heal_on_any_violation" => ThresholdPolicy::HealOnAnyViolation,
+                        "never" => ThresholdPolicy::Never,
+                        other => return Err(parse_err(format!("unknown policy '{}'", other))),
+                    };
+                }
+                "auto_heal" => snapshot.auto_heal = flag(rest)?,
+                "verify_after_heal" => snapshot.verify_after_heal = flag(rest)?,
+                "weight" | "axiom_learning_rate" | "rate_scale" => {
+                    let (name, value) = rest
+                        .rsplit_once(' ')
+                        .ok_or_else(|| parse_err("expected '<axiom> <number>'".to_string()))?;
+                    let values = match key {
+                        "weight" => &mut snapshot.weights,
+                        "axiom_learning_rate" => &mut snapshot.learning_rates,
+                        _ => &mut snapshot.rate_scales,
+                    };
+                    values.insert(axiom(name)?, number(value)?);
+                }
+                "strategies" => {
+                    if !untemplated.is_empty() {
+                        return Err(parse_err(missing_template.to_string()));
+                    }
+                    let (name, list) = rest.rsplit_once(' ').unwrap_or((rest, ""));
+                    let chain = list
+                        .split(',')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| match s {
+                            "replace_sentence" => {
+                                Ok(CorrectionStrategy::ReplaceSentence { template: String::new() })
+                            }
+                            _ => CorrectionStrategy::from_name(s)
+                                .ok_or_else(|| parse_err(format!("unknown strategy '{}'", s))),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let axiom = axiom(name)?;
+                    let templated = chain.iter().enumerate().rev().filter(|(_, s)| {
+                        matches!(s, CorrectionStrategy::ReplaceSentence { .. })
+                    });
+                    untemplated.extend(templated.map(|(i, _)| (axiom.clone(), i)));
+                    snapshot.strategies.insert(axiom, chain);
+                }
+                "sentence_template" => {
+                    let (axiom, position) = untemplated.pop().ok_or_else(|| {
+                        parse_err("sentence_template without a replace_sentence".to_string())
+                    })?;
+                    let text = unescape_line(rest).map_err(parse_err)?;
+                    let chain = snapshot.strategies.get_mut(&axiom);
+                    if let Some(CorrectionStrategy::ReplaceSentence { template }) =
+                        chain.and_then(|chain| chain.get_mut(position))
+                    {
+                        *template = text;
+                    }
+                }
+                "accept_regression" => {
+                    snapshot.accepted_regressions.insert(axiom(rest)?);
+                }
+                "context" => {
+                    let mut parts = rest.splitn(3, ' ');
+                    let (Some(name), Some(seen), Some(key)) =
+                        (parts.next(), parts.next(), parts.next())
+                    else {
+                        return Err(parse_err("expected '<state> <last seen> <key>'".to_string()));
+                    };
+                    let key = unescape_line(key).map_err(parse_err)?;
+                    let record = ContextRecord {
+                        state: state(name)?,
+                        last_seen: timestamp(seen)?,
+                        history: VecDeque::new(),
+                    };
+                    snapshot.contexts.insert(key.clone(), record);
+                    current_context = Some(key);
+                }
+                "transition" => {
+                    let key = current_context
+                        .clone()
+                        .ok_or_else(|| parse_err("transition before any context".to_string()))?;
+                    let parts: Vec<&str> = rest.split(' ').collect();
+                    let [from, to, at] = parts[..] else {
+                        return Err(parse_err("expected '<from> <to> <timestamp>'".to_string()));
+                    };
+                    let transition = ContextTransition {
+                        key: key.clone(),
+                        from: state(from)?,
+                        to: state(to)?,
+                        timestamp: timestamp(at)?,
+                    };
+                    if let Some(record) = snapshot.contexts.get_mut(&key) {
+                        record.history.push_back(transition);
+                    }
+                }
+                "disabled" => {
+                    snapshot.disabled_axioms.insert(axiom(rest)?);
+                }
+                "violations" => {
+                    let (name, value) = rest
+                        .rsplit_once(' ')
+                        .ok_or_else(|| parse_err("expected '<axiom> <count>'".to_string()))?;
+                    snapshot.violation_counts.insert(axiom(name)?, count(value)?);
+                }
+                "strategy_stats" => {
+                    let parts: Vec<&str> = rest.splitn(6, ' ').collect();
+                    let [attempts, successes, failures, regressions, broken, name] = parts[..]
+                    else {
+                        return Err(parse_err(
+                            "expected '<attempts> <successes> <failures> <regressions> \
+                             <contract violations> <name>'"
+                                .to_string(),
+                        ));
+                    };
+                    let stats = StrategyStats {
+                        attempts: count(attempts)?,
+                        successes: count(successes)?,
+                        failures: count(failures)?,
+                        regressions: count(regressions)?,
+                        contract_violations: count(broken)?,
+                    };
+                    snapshot.strategy_stats.insert(unescape_line(name).map_err(parse_err)?, stats);
+                }
+                other => return Err(parse_err(format!("unknown key '{}'", other))),
+            }
+        }
+        if !untemplated.is_empty() {
+            let line = body.lines().count();
+            return Err(SnapshotError::Parse { line, reason: missing_template.to_string() });
+        }
+        Ok(snapshot)
+    }
+
+    /// What changed from `self` to `other`
+    pub fn diff(&self, other: &HealerSnapshot) -> SnapshotDiff {
+        let values = |before: &BTreeMap<Axiom, f64>, after: &BTreeMap<Axiom, f64>| {
+            let axioms: BTreeSet<&Axiom> = before.keys().chain(after.keys()).collect();
+            axioms
+                .into_iter()
+                .filter_map(|axiom| {
+                    let change =
+                        Change::between(before.get(axiom).copied(), after.get(axiom).copied())?;
+                    Some((axiom.clone(), change))
+                })
+                .collect()
+        };
+        let weights = values(&self.weights, &other.weights);
+
+        // Written the way policy files name them, so a changed template shows too
+        let names = |chain: Option<&Vec<CorrectionStrategy>>| -> Vec<String> {
+            let name = |s: &CorrectionStrategy| match s {
+                CorrectionStrategy::ReplaceSentence { template } => {
+                    format!("replace_sentence:{}", template)
+                }
+                s => s.name().to_string(),
+            };
+            chain.into_iter().flatten().map(name).collect()
+        };
+        let axioms: BTreeSet<&Axiom> =
+            self.strategies.keys().chain(other.strategies.keys()).collect();
+        let strategies = axioms
+            .into_iter()
+            .filter_map(|axiom| {
+                let before = names(self.strategies.get(axiom));
+                let after = names(other.strategies.get(axiom));
+                if before == after {
+                    return None;
+                }
+                let kept = |chain: &[String], other: &[String]| -> Vec<String> {
+                    chain.iter().filter(|s| other.contains(s)).cloned().collect()
+                };
+                let only = |chain: &[String], other: &[String]| -> Vec<String> {
+                    chain.iter().filter(|s| !other.contains(s)).cloned().collect()
+                };
+                let diff = ChainDiff {
+                    added: only(&after, &before),
+                    removed: only(&before, &after),
+                    reordered: kept(&before, &after) != kept(&after, &before),
+                    before,
+                    after,
+                };
+                Some((axiom.clone(), diff))
+            })
+            .collect();
+
+        let count = |snapshot: &HealerSnapshot| {
+            let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+            for record in snapshot.contexts.values() {
+                *counts.entry(record.state.name()).or_insert(0) += 1;
+            }
+            counts
+        };
+        let contexts = count_changes(&count(self), &count(other));
+        let violations = count_changes(&self.violation_counts, &other.violation_counts);
+        let names: BTreeSet<&String> =
+            self.strategy_stats.keys().chain(other.strategy_stats.keys()).collect();
+        let strategy_stats = names
+            .into_iter()
+            .filter_map(|name| {
+                let counters = |snapshot: &HealerSnapshot| {
+                    let stats = snapshot.strategy_stats.get(name).cloned().unwrap_or_default();
+                    BTreeMap::from([
+                        ("attempts", stats.attempts),
+                        ("successes", stats.successes),
+                        ("failures", stats.failures),
+                        ("regressions", stats.regressions),
+                        ("contract_violations", stats.contract_violations),
+                    ])
+                };
+                let changes = count_changes(&counters(self), &counters(other));
+                (!changes.is_empty()).then(|| (name.clone(), changes))
+            })
+            .collect();
+
+        SnapshotDiff {
+            weights,
+            learning_rates: values(&self.learning_rates, &other.learning_rates),
+            rate_scales: values(&self.rate_scales, &other.rate_scales),
+            learning_rate: Change::between(self.learning_rate, other.learning_rate),
+            threshold: Change::between(self.threshold, other.threshold),
+            threshold_policy: Change::between(self.threshold_policy, other.threshold_policy),
+            auto_heal: Change::between(self.auto_heal, other.auto_heal),
+            verify_after_heal: Change::between(self.verify_after_heal, other.verify_after_heal),
+            strategies,
+            regressions_accepted: other
+                .accepted_regressions
+                .difference(&self.accepted_regressions)
+                .cloned()
+                .collect(),
+            regressions_unaccepted: self
+                .accepted_regressions
+                .difference(&other.accepted_regressions)
+                .cloned()
+                .collect(),
+            contexts,
+            axioms_disabled: other
+                .disabled_axioms
+                .difference(&self.disabled_axioms)
+                .cloned()
+                .collect(),
+            axioms_enabled: self
+                .disabled_axioms
+                .difference(&other.disabled_axioms)
+                .cloned()
+                .collect(),
+            violations,
+            strategy_stats,
+        }
+    }
+
+    /// Decode two encoded snapshots and diff them; either failing its schema or
+    /// integrity checks is an error rather than a misleading diff
+    pub fn diff_encoded(before: &str, after: &str) -> Result<SnapshotDiff, SnapshotError> {
+        Ok(Self::decode(before)?.diff(&Self::decode(after)?))
+    }
+}
+
+/// Counts that differ between `before` and `after`, a missing key counting as zero
+fn count_changes<K: Ord + Clone>(
+    before: &BTreeMap<K, usize>,
+    after: &BTreeMap<K, usize>,
+) -> BTreeMap<K, Change<usize>> {
+    let keys: BTreeSet<&K> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let change = Change::between(
+                before.get(key).copied().unwrap_or(0),
+                after.get(key).copied().unwrap_or(0),
+            )?;
+            Some((key.clone(), change))
+        })
+        .collect()
+}
+
+/// A setting's value in the older and newer snapshot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn between(before: T, after: T) -> Option<Self> {
+        (before != after).then_some(Self { before, after })
+    }
+}
+
+impl Change<Option<f64>> {
+    /// `None` when the value is missing from either snapshot
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.after? - self.before?)
+    }
+}
+
+impl Change<usize> {
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// How one axiom's strategy chain changed, by strategy name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainDiff {
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Strategies in both chains run in a different order
+    pub reordered: bool,
+}
+
+/// Differences between two snapshots, from `HealerSnapshot::diff`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// Axioms whose weight changed; `None` where a snapshot has no weight
+    pub weights: BTreeMap<Axiom, Change<Option<f64>>>,
+    /// Per-axiom learning rates and feedback rate scales that changed, likewise
+    pub learning_rates: BTreeMap<Axiom, Change<Option<f64>>>,
+    pub rate_scales: BTreeMap<Axiom, Change<Option<f64>>>,
+    pub learning_rate: Option<Change<f64>>,
+    pub threshold: Option<Change<f64>>,
+    pub threshold_policy: Option<Change<ThresholdPolicy>>,
+    pub auto_heal: Option<Change<bool>>,
+    pub verify_after_heal: Option<Change<bool>>,
+    pub strategies: BTreeMap<Axiom, ChainDiff>,
+    /// Axioms that started or stopped accepting regressions
+    pub regressions_accepted: Vec<Axiom>,
+    pub regressions_unaccepted: Vec<Axiom>,
+    /// Tracked contexts per lifecycle state, where the count changed
+    pub contexts: BTreeMap<&'static str, Change<usize>>,
+    /// Axioms that were disabled or re-enabled
+    pub axioms_disabled: Vec<Axiom>,
+    pub axioms_enabled: Vec<Axiom>,
+    /// Violations in the history per axiom, where the count changed
+    pub violations: BTreeMap<Axiom, Change<usize>>,
+    /// Strategy counters (`attempts`, `successes`, …) that changed, per strategy
+    pub strategy_stats: BTreeMap<String, BTreeMap<&'static str, Change<usize>>>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Serialize as one line of `schema::SNAPSHOT_DIFF` JSON
+    pub fn to_json(&self) -> String {
+        use import::json_string;
+        fn change<T>(change: &Option<Change<T>>, value: impl Fn(&T) -> String) -> String {
+            change.as_ref().map_or("null".to_string(), |c| {
+                format!("{{\"before\":{},\"after\":{}}}", value(&c.before), value(&c.after))
+            })
+        }
+        let number = |n: &f64| n.to_string();
+        let optional = |n: &Option<f64>| n.map_or("null".to_string(), |n| n.to_string());
+        let strings = |items: &[String]| {
+            let items: Vec<String> = items.iter().map(|s| json_string(s)).collect();
+            format!("[{}]", items.join(","))
+        };
+        let axioms = |axioms: &[Axiom]| {
+            let names: Vec<String> = axioms.iter().map(|a| format!("{:?}", a)).collect();
+            strings(&names)
+        };
+        let object = |fields: Vec<String>| format!("{{{}}}", fields.join(","));
+
+        let by_axiom = |values: &BTreeMap<Axiom, Change<Option<f64>>>| {
+            let fields = values
+                .iter()
+                .map(|(axiom, c)| {
+                    let key = json_string(&format!("{:?}", axiom));
+                    format!("{}:{}", key, change(&Some(*c), optional))
+                })
+                .collect();
+            object(fields)
+        };
+        let strategies = self
+            .strategies
+            .iter()
+            .map(|(axiom, chain)| {
+                format!(
+                    "\"{:?}\":{{\"before\":{},\"after\":{},\"added\":{},\"removed\":{},\
+                     \"reordered\":{}}}",
+                    axiom,
+                    strings(&chain.before),
+                    strings(&chain.after),
+                    strings(&chain.added),
+                    strings(&chain.removed),
+                    chain.reordered
+                )
+            })
+            .collect();
+        let counts = |counts: Vec<(String, &Change<usize>)>| {
+            let fields = counts
+                .into_iter()
+                .map(|(key, c)| {
+                    format!("{}:{}", json_string(&key), change(&Some(*c), usize::to_string))
+                })
+                .collect();
+            object(fields)
+        };
+        let contexts = counts(self.contexts.iter().map(|(s, c)| (s.to_string(), c)).collect());
+        let violations =
+            counts(self.violations.iter().map(|(a, c)| (format!("{:?}", a), c)).collect());
+        let strategy_stats = self
+            .strategy_stats
+            .iter()
+            .map(|(name, counters)| {
+                let counters = counters.iter().map(|(n, c)| (n.to_string(), c)).collect();
+                format!("{}:{}", json_string(name), counts(counters))
+            })
+            .collect();
+        format!(
+            "{{\"schema\":{},\"weights\":{},\"learning_rates\":{},\"rate_scales\":{},\
+             \"learning_rate\":{},\"threshold\":{},\
+             \"threshold_policy\":{},\"auto_heal\":{},\"verify_after_heal\":{},\
+             \"strategies\":{},\"regressions_accepted\":{},\"regressions_unaccepted\":{},\
+             \"contexts\":{},\"axioms_disabled\":{},\"axioms_enabled\":{},\
+             \"violations\":{},\"strategy_stats\":{}}}",
+            json_string(&schema::SNAPSHOT_DIFF.to_string()),
+            by_axiom(&self.weights),
+            by_axiom(&self.learning_rates),
+            by_axiom(&self.rate_scales),
+            change(&self.learning_rate, number),
+            change(&self.threshold, number),
+            change(&self.threshold_policy, |p| json_string(&format!("{:?}", p))),
+            change(&self.auto_heal, bool::to_string),
+            change(&self.verify_after_heal, bool::to_string),
+            object(strategies),
+            axioms(&self.regressions_accepted),
+            axioms(&self.regressions_unaccepted),
+            contexts,
+            axioms(&self.axioms_disabled),
+            axioms(&self.axioms_enabled),
+            violations,
+            object(strategy_stats),
+        )
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        let weight = |w: Option<f64>| w.map_or("-".to_string(), |w| format!("{:.3}", w));
+        let by_axiom = [
+            ("weights", &self.weights),
+            ("learning rates", &self.learning_rates),
+            ("rate scales", &self.rate_scales),
+        ];
+        for (heading, values) in by_axiom {
+            if values.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}:", heading)?;
+            for (axiom, c) in values {
+                write!(f, "  {:?} {} → {}", axiom, weight(c.before), weight(c.after))?;
+                match c.delta() {
+                    Some(delta) => writeln!(f, " ({:+.3})", delta)?,
+                    None => writeln!(f)?,
+                }
+            }
+        }
+        fn pair<T: fmt::Debug>(change: Option<Change<T>>) -> Option<(String, String)> {
+            change.map(|c| (format!("{:?}", c.before), format!("{:?}", c.after)))
+        }
+        let settings = [
+            ("learning_rate", pair(self.learning_rate)),
+            ("threshold", pair(self.threshold)),
+            ("policy", pair(self.threshold_policy)),
+            ("auto_heal", pair(self.auto_heal)),
+            ("verify_after_heal", pair(self.verify_after_heal)),
+        ];
+        if settings.iter().any(|(_, c)| c.is_some()) {
+            writeln!(f, "settings:")?;
+            for (name, c) in &settings {
+                if let Some((before, after)) = c {
+                    writeln!(f, "  {} {} → {}", name, before, after)?;
+                }
+            }
+        }
+        if !self.strategies.is_empty() {
+            writeln!(f, "strategies:")?;
+            for (axiom, chain) in &self.strategies {
+                let mut parts: Vec<String> =
+                    chain.added.iter().map(|s| format!("+{}", s)).collect();
+                parts.extend(chain.removed.iter().map(|s| format!("-{}", s)));
+                if chain.reordered {
+                    parts.push("reordered".to_string());
+                }
+                writeln!(
+                    f,
+                    "  {:?} [{}] → [{}] ({})",
+                    axiom,
+                    chain.before.join(", "),
+                    chain.after.join(", "),
+                    parts.join(", ")
+                )?;
+            }
+        }
+        if !self.regressions_accepted.is_empty() || !self.regressions_unaccepted.is_empty() {
+            writeln!(f, "accepted regressions:")?;
+            for axiom in &self.regressions_accepted {
+                writeln!(f, "  +{:?}", axiom)?;
+            }
+            for axiom in &self.regressions_unaccepted {
+                writeln!(f, "  -{:?}", axiom)?;
+            }
+        }
+        if !self.contexts.is_empty() {
+            writeln!(f, "contexts:")?;
+            for (state, c) in &self.contexts {
+                writeln!(f, "  {} {} → {} ({:+})", state, c.before, c.after, c.delta())?;
+            }
+        }
+        if !self.axioms_disabled.is_empty() || !self.axioms_enabled.is_empty() {
+            writeln!(f, "disabled axioms:")?;
+            for axiom in &self.axioms_disabled {
+                writeln!(f, "  +{:?}", axiom)?;
+            }
+            for axiom in &self.axioms_enabled {
+                writeln!(f, "  -{:?}", axiom)?;
+            }
+        }
+        if !self.violations.is_empty() {
+            writeln!(f, "violations:")?;
+            for (axiom, c) in &self.violations {
+                writeln!(f, "  {:?} {} → {} ({:+})", axiom, c.before, c.after, c.delta())?;
+            }
+        }
+        if !self.strategy_stats.is_empty() {
+            writeln!(f, "strategy counters:")?;
+            for (name, counters) in &self.strategy_stats {
+                let counters: Vec<String> = counters
+                    .iter()
+                    .map(|(counter, c)| {
+                        format!("{} {} → {} ({:+})", counter, c.before, c.after, c.delta())
+                    })
+                    .collect();
+                writeln!(f, "  {}: {}", name, counters.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Callbacks for healing decisions as they are made; every method does nothing
+/// unless overridden.
+///
+/// Observers are called at the end of each heal call, in registration order, so
+/// they should return quickly; slow delivery belongs in a `ViolationSink`.
+pub trait HealObserver: Send {
+    /// A violation was detected in a context being healed
+    fn on_violation_detected(&mut self, _violation: &Violation) {}
+
+    /// Strategies were tried for a violation
+    fn on_heal_attempted(&mut self, _attempts: &ViolationAttempts) {}
+
+    /// A violation was left unhealed
+    fn on_heal_failed(&mut self, _violation: &Violation, _reason: UnhealedReason) {}
+
+    /// An axiom's weight changed, through healing outcomes or reviewer feedback
+    fn on_weights_updated(&mut self, _axiom: &Axiom, _old: f64, _new: f64) {}
+}
+
+/// Destination for detected violations, fed from a bounded per-sink queue.
+///
+/// Violations are queued as they are detected and delivered by
+/// `AxiomaticSelfHealer::flush_sinks`, so a slow sink never blocks healing.
+pub trait ViolationSink: Send {
+    fn name(&self) -> &str;
+    fn deliver(&mut self, violation: &Violation) -> std::io::Result<()>;
+}
+
+struct QueuedSink {
+    sink: Box<dyn ViolationSink>,
+    queue: VecDeque<Violation>,
+    capacity: usize,
+    /// Dropped because the queue was full
+    overflowed: usize,
+}
+
+/// What one sink did during a flush
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SinkFlush {
+    pub name: String,
+    pub delivered: usize,
+    pub failed: usize,
+    /// Still queued when the deadline passed
+    pub pending: usize,
+    /// Dropped on enqueue because the queue was full, since the last flush
+    pub overflowed: usize,
+}
+
+/// Result of `AxiomaticSelfHealer::shutdown`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    pub sinks: Vec<SinkFlush>,
+    /// Events and budget alerts nobody drained
+    pub undrained_events: usize,
+    pub undrained_alerts: usize,
+    /// `None` when persistence is not configured
+    pub snapshot: Option<Result<(), String>>,
+    pub timed_out: bool,
+}
+
+impl ShutdownReport {
+    pub fn delivered(&self) -> usize {
+        self.sinks.iter().map(|sink| sink.delivered).sum()
+    }
+
+    /// Violations that never reached their sink: queued at the deadline, failed or
+    /// overflowed
+    pub fn dropped(&self) -> usize {
+        self.sinks.iter().map(|sink| sink.pending + sink.failed + sink.overflowed).sum()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.dropped() == 0 && !matches!(self.snapshot, Some(Err(_)))
+    }
+}
+
+/// Notable things that happened inside the healer, retrievable via `drain_events`
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealerEvent {
+    /// A snapshot file could not be used during `recover`
+    SnapshotRejected { path: PathBuf, reason: String },
+    /// Recovery found no usable snapshot and fell back to defaults
+    RecoveredWithDefaults,
+    /// Writing a periodic snapshot failed
+    PersistFailed { reason: String },
+    /// `self_check` found a problem while building in `SelfCheckMode::Report`
+    ConfigWarning(ConfigWarning),
+    /// A tracked context changed lifecycle state
+    ContextTransition(ContextTransition),
+    /// Published by the `SelfReportSchedule`
+    SelfReport(Box<SelfReport>),
+    /// A context the known-clean filter matched turned out to have violations
+    KnownCleanFalsePositive { fingerprint: u64 },
+    /// An `AlertSink` failed to take an escalation alert
+    AlertFailed { sink: String, rule: String, reason: String },
+}
+
+/// Where and how often the healer writes its snapshot
+#[derive(Debug, Clone)]
+struct Persistence {
+    dir: PathBuf,
+    interval: Duration,
+    last_written: Option<u64>,
+}
+
+impl Persistence {
+    const FILE: &'static str = "healer.snapshot";
+    const PREVIOUS: &'static str = "healer.snapshot.prev";
+    const TEMP: &'static str = "healer.snapshot.tmp";
+
+    /// Write-temp-then-rename, keeping the previous snapshot as a fallback
+    fn write(&self, snapshot: &HealerSnapshot) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let temp = self.dir.join(Self::TEMP);
+        {
+            let mut file = std::fs::File::create(&temp)?;
+            file.write_all(snapshot.encode().as_bytes())?;
+            file.sync_all()?;
+        }
+        let current = self.dir.join(Self::FILE);
+        if current.exists() {
+            std::fs::rename(&current, self.dir.join(Self::PREVIOUS))?;
+        }
+        std::fs::rename(temp, current)
+    }
+}
+
+/// What the healer does with violations of an axiom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handling {
+    /// Its strategy chain runs
+    Chain,
+    /// Marked record-only with `mark_record_only`
+    RecordOnly,
+    /// No strategies: violations are recorded as failed heals
+    Unhandled,
+}
+
+/// A wiring problem visible in the coverage matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageFlag {
+    /// Detected, but nothing heals it and it is not record-only
+    NoStrategies,
+    /// Has strategies, but no rule can produce it
+    NoDetectors,
+    /// Detected without a weight, so it scores 1.0
+    MissingWeight,
+    /// Detected or healed with a weight that contributes nothing
+    NonPositiveWeight,
+}
+
+impl CoverageFlag {
+    fn label(&self) -> &'static str {
+        match self {
+            CoverageFlag::NoStrategies => "no-strategies",
+            CoverageFlag::NoDetectors => "no-detectors",
+            CoverageFlag::MissingWeight => "missing-weight",
+            CoverageFlag::NonPositiveWeight => "non-positive-weight",
+        }
+    }
+}
+
+/// How one enabled axiom is wired up
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageRow {
+    pub axiom: Axiom,
+    /// Rules that can produce the axiom
+    pub detectors: Vec<String>,
+    pub strategies: Vec<&'static str>,
+    pub handling: Handling,
+    pub weight: Option<f64>,
+    /// Lowest severity any of its rules reports
+    pub severity_floor: Option<Severity>,
+    /// What currently suppresses or limits its detections and heals
+    pub suppressions: Vec<String>,
+    pub flags: Vec<CoverageFlag>,
+}
+
+/// Cross-reference of detectors, strategies and weights for every enabled axiom;
+/// see `AxiomaticSelfHealer::coverage_matrix`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageMatrix {
+    pub rows: Vec<CoverageRow>,
+    /// Applies to every axiom alike
+    pub threshold: ThresholdPolicy,
+}
+
+impl CoverageMatrix {
+    pub fn row(&self, axiom: &Axiom) -> Option<&CoverageRow> {
+        self.rows.iter().find(|row| &row.axiom == axiom)
+    }
+
+    /// Rows with at least one flag
+    pub fn flagged(&self) -> impl Iterator<Item = &CoverageRow> {
+        self.rows.iter().filter(|row| !row.flags.is_empty())
+    }
+
+    /// Serialize as one line of `schema::COVERAGE` JSON
+    pub fn to_json(&self) -> String {
+        use import::json_string;
+        let strings = |items: &mut dyn Iterator<Item = &str>| {
+            format!("[{}]", items.map(json_string).collect::<Vec<_>>().join(","))
+        };
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "{{\"axiom\":{},\"detectors\":{},\"strategies\":{},\"handling\":{},\
+                     \"weight\":{},\"severity_floor\":{},\"suppressions\":{},\"flags\":{}}}",
+                    json_string(&format!("{:?}", row.axiom)),
+                    strings(&mut row.detectors.iter().map(String::as_str)),
+                    strings(&mut row.strategies.iter().copied()),
+                    json_string(&format!("{:?}", row.handling)),
+                    row.weight.map_or("null".to_string(), |w| w.to_string()),
+                    row.severity_floor
+                        .map_or("null".to_string(), |s| json_string(&format!("{:?}", s))),
+                    strings(&mut row.suppressions.iter().map(String::as_str)),
+                    strings(&mut row.flags.iter().map(CoverageFlag::label)),
+                )
+            })
+            .collect();
+        format!(
+            "{{\"schema\":{},\"threshold\":{},\"rows\":[{}]}}",
+            json_string(&schema::COVERAGE.to_string()),
+            json_string(&self.threshold.to_string()),
+            rows.join(",")
+        )
+    }
+}
+
+impl fmt::Display for CoverageMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_dash = |items: Vec<String>| {
+            if items.is_empty() {
+                "-".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let header = ["axiom", "detectors", "strategies", "weight", "floor", "suppressed", "flags"];
+        let mut table: Vec<[String; 7]> = vec![header.map(String::from)];
+        for row in &self.rows {
+            let strategies = match row.handling {
+                Handling::RecordOnly => "record-only".to_string(),
+                _ => or_dash(row.strategies.iter().map(|s| s.to_string()).collect()),
+            };
+            table.push([
+                format!("{:?}", row.axiom),
+                or_dash(row.detectors.clone()),
+                strategies,
+                row.weight.map_or("-".to_string(), |w| format!("{:.2}", w)),
+                row.severity_floor.map_or("-".to_string(), |s| format!("{:?}", s)),
+                or_dash(row.suppressions.clone()),
+                or_dash(row.flags.iter().map(|flag| flag.label().to_string()).collect()),
+            ]);
+        }
+        let mut widths = [0; 7];
+        for line in &table {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for line in &table {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())?;
+        }
+        write!(f, "threshold: {}", self.threshold)
+    }
+}
+
+/// A configuration problem found by `AxiomaticSelfHealer::self_check`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigWarning {
+    /// A detector can produce the axiom but it has no weight, so it scores 1.0
+    MissingWeight { axiom: Axiom },
+    /// The axiom is detected or healed but its weight contributes nothing to penalties
+    NonPositiveWeight { axiom: Axiom, weight: f64 },
+    /// A detected axiom has no strategies and was not marked record-only
+    NoStrategies { axiom: Axiom },
+    /// Even every rule matching at once cannot trigger healing
+    UnreachableThreshold { policy: ThresholdPolicy, max_penalty: f64 },
+    /// The rule matches every context
+    AlwaysMatchingRule { rule: String },
+    /// Two consecutive clock reads went backwards
+    ClockWentBackwards { first: u64, second: u64 },
+}
+
+impl ConfigWarning {
+    /// Stable identifier, suitable for allowlisting known warnings
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConfigWarning::MissingWeight { .. } => "C001",
+            ConfigWarning::NonPositiveWeight { .. } => "C002",
+            ConfigWarning::NoStrategies { .. } => "C003",
+            ConfigWarning::UnreachableThreshold { .. } => "C004",
+            ConfigWarning::AlwaysMatchingRule { .. } => "C005",
+            ConfigWarning::ClockWentBackwards { .. } => "C006",
+        }
+    }
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.code())?;
+        match self {
+            ConfigWarning::MissingWeight { axiom } => {
+                write!(f, "{:?} can be detected but has no weight", axiom)
+            }
+            ConfigWarning::NonPositiveWeight { axiom, weight } => {
+                write!(f, "{:?} is in use but has weight {}", axiom, weight)
+            }
+            ConfigWarning::NoStrategies { axiom } => write!(
+                f,
+                "{:?} can be detected but has no correction strategies and is not record-only",
+                axiom
+            ),
+            ConfigWarning::UnreachableThreshold { policy, max_penalty } => write!(
+                f,
+                "threshold policy '{}' can never heal: the largest possible penalty is {}",
+                policy, max_penalty
+            ),
+            ConfigWarning::AlwaysMatchingRule { rule } => {
+                write!(f, "{} matches every context", rule)
+            }
+            ConfigWarning::ClockWentBackwards { first, second } => {
+                write!(f, "clock went backwards from {} to {}", first, second)
+            }
+        }
+    }
+}
+
+/// Whether `HealerBuilder` runs `self_check`, and what it does with the warnings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfCheckMode {
+    #[default]
+    Off,
+    /// Queue each warning as a `HealerEvent::ConfigWarning`
+    Report,
+    /// Refuse to build while any warning outside the allowlist remains
+    Strict,
+}
+
+/// Builder for an `AxiomaticSelfHealer`
+pub struct HealerBuilder {
+    regularizer: AdaptiveAxiomaticRegularizer,
+    persistence: Option<(PathBuf, Duration)>,
+    self_check: SelfCheckMode,
+    allowed_warnings: HashSet<String>,
+    latency_buckets: Option<Vec<Duration>>,
+}
+
+impl HealerBuilder {
+    pub fn new(regularizer: AdaptiveAxiomaticRegularizer) -> Self {
+        Self {
+            regularizer,
+            persistence: None,
+            self_check: SelfCheckMode::Off,
+            allowed_warnings: HashSet::new(),
+            latency_buckets: None,
+        }
+    }
+
+    /// Run `self_check` when building
+    pub fn with_self_check(mut self, mode: SelfCheckMode) -> Self {
+        self.self_check = mode;
+        self
+    }
+
+    /// Bucket upper bounds for the heal latency histograms; defaults to
+    /// `DEFAULT_LATENCY_BUCKETS`
+    pub fn with_latency_buckets(mut self, bounds: Vec<Duration>) -> Self {
+        self.latency_buckets = Some(bounds);
+        self
+    }
+
+    /// Ignore warnings with this code (e.g. `"C003"`) during the build-time self-check
+    pub fn allow_warning(mut self, code: impl Into<String>) -> Self {
+        self.allowed_warnings.insert(code.into());
+        self
+    }
+
+    /// Persist the healer snapshot to `dir` at most once per `interval`, piggybacking
+    /// on heal calls
+    pub fn with_persistence(mut self, dir: impl Into<PathBuf>, interval: Duration) -> Self {
+        self.persistence = Some((dir.into(), interval));
+        self
+    }
+
+    /// Build the healer.
+    ///
+    /// # Panics
+    ///
+    /// In `SelfCheckMode::Strict`, if the self-check reports a warning that is not
+    /// allowlisted; use `try_build` to handle that case.
+    pub fn build(self) -> AxiomaticSelfHealer {
+        match self.try_build() {
+            Ok(healer) => healer,
+            Err(warnings) => {
+                let listed: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+                panic!("healer self-check failed: {}", listed.join("; "))
+            }
+        }
+    }
+
+    /// Build the healer, failing with the remaining warnings in strict mode
+    pub fn try_build(self) -> Result<AxiomaticSelfHealer, Vec<ConfigWarning>> {
+        let mut healer = AxiomaticSelfHealer::new(self.regularizer);
+        if let Some((dir, interval)) = self.persistence {
+            healer.set_persistence(dir, interval);
+        }
+        if let Some(bounds) = &self.latency_buckets {
+            healer.latency = LatencyMetrics::new(bounds);
+        }
+        if self.self_check == SelfCheckMode::Off {
+            return Ok(healer);
+        }
+
+        let warnings: Vec<ConfigWarning> = healer
+            .self_check()
+            .into_iter()
+            .filter(|w| !self.allowed_warnings.contains(w.code()))
+            .collect();
+        if self.self_check == SelfCheckMode::Strict && !warnings.is_empty() {
+            return Err(warnings);
+        }
+        for warning in warnings {
+            healer.push_event(HealerEvent::ConfigWarning(warning));
+        }
+        Ok(healer)
+    }
+}
+
+/// What a `HealingReader` emits for a line it could not heal
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailedLinePolicy {
+    /// The original line
+    PassThrough,
+    Drop,
+    /// This text instead, keeping the line's terminator
+    Notice(String),
+}
+
+/// Buffered reader that heals each complete line of `inner` before exposing it.
+///
+/// A line fails when `monitor_and_heal` errors or leaves a violation it attempted
+/// unhealed; `FailedLinePolicy` decides what is read in its place. Lines may be
+/// longer than `inner`'s buffer, and a last line without a newline is healed and
+/// returned without one. Invalid UTF-8 is replaced before healing.
+pub struct HealingReader<'h, R> {
+    inner: R,
+    healer: &'h mut AxiomaticSelfHealer,
+    policy: FailedLinePolicy,
+    line: Vec<u8>,
+    out: Vec<u8>,
+    pos: usize,
+    failed_lines: usize,
+}
+
+impl<'h, R: BufRead> HealingReader<'h, R> {
+    pub fn new(inner: R, healer: &'h mut AxiomaticSelfHealer, policy: FailedLinePolicy) -> Self {
+        Self {
+            inner,
+            healer,
+            policy,
+            line: Vec::new(),
+            out: Vec::new(),
+            pos: 0,
+            failed_lines: 0,
+        }
+    }
+
+    /// Lines handled by the failure policy so far
+    pub fn failed_lines(&self) -> usize {
+        self.failed_lines
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Read and heal lines until one produces output; false at end of input
+    fn next_line(&mut self) -> std::io::Result<bool> {
+        loop {
+            self.line.clear();
+            if self.inner.read_until(b'\n', &mut self.line)? == 0 {
+                return Ok(false);
+            }
+            let text = String::from_utf8_lossy(&self.line);
+            let body = text.trim_end_matches(['\n', '\r']);
+            let terminator = &text[body.len()..];
+
+            let healed = self.healer.monitor_and_heal_detailed(body).ok().filter(|report| {
+                report.unhealed.iter().all(|(_, reason)| *reason == UnhealedReason::NotAttempted)
+            });
+            let emitted = match (healed, &self.policy) {
+                (Some(report), _) => Some(report.context),
+                (None, policy) => {
+                    self.failed_lines += 1;
+                    match policy {
+                        FailedLinePolicy::PassThrough => Some(body.to_string()),
+                        FailedLinePolicy::Drop => None,
+                        FailedLinePolicy::Notice(notice) => Some(notice.clone()),
+                    }
+                }
+            };
+            if let Some(emitted) = emitted {
+                self.out.clear();
+                self.out.extend_from_slice(emitted.as_bytes());
+                self.out.extend_from_slice(terminator.as_bytes());
+                self.pos = 0;
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for HealingReader<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.pos >= self.out.len() && !self.next_line()? {
+            self.out.clear();
+            self.pos = 0;
+        }
+        Ok(&self.out[self.pos..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.out.len());
+    }
+}
+
+impl<R: BufRead> Read for HealingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+/// Cloneable, thread-safe handle to a shared healer
+#[derive(Clone)]
+pub struct HealerHandle {
+    inner: Arc<Mutex<AxiomaticSelfHealer>>,
+}
+
+impl HealerHandle {
+    pub fn new(healer: AxiomaticSelfHealer) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(healer)),
+        }
+    }
+
+    /// Run `f` with exclusive access to the healer
+    pub fn with<R>(&self, f: impl FnOnce(&mut AxiomaticSelfHealer) -> R) -> R {
+        // A panic in another caller must not take the healer down with it
+        let mut healer = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut healer)
+    }
+
+    pub fn monitor_and_heal(&self, context: &str) -> Result<HealReport, HealError> {
+        self.with(|healer| healer.monitor_and_heal_detailed(context))
+    }
+
+    pub fn detect(&self, context: &str) -> Vec<Violation> {
+        self.with(|healer| healer.regularizer.detect_violations(context))
+    }
+
+    pub fn get_statistics(&self) -> ViolationStatistics {
+        self.with(|healer| healer.get_statistics())
+    }
+}
+
+impl From<AxiomaticSelfHealer> for HealerHandle {
+    fn from(healer: AxiomaticSelfHealer) -> Self {
+        Self::new(healer)
+    }
+}
+
+/// Healing off the request path, on a tokio runtime.
+///
+/// `AxiomaticSelfHealer::spawn_monitor` moves a healer into a background task that
+/// reads contexts from a channel and heals up to `parallelism` of them at once,
+/// emitting `MonitorEvent`s on an output channel. Contexts are numbered in arrival
+/// order; events for different contexts may interleave and finish out of order.
+///
+/// Each context first goes through the `AsyncDetector`s in registration order. A
+/// violation they find is recorded and, if an `AsyncCorrectionHandler` is registered
+/// for its axiom, handed to it; a `Healed` or `Partial` result replaces the context.
+/// The result is then healed by the healer itself on the blocking pool, so its
+/// detectors and strategy chains never stall the runtime's workers.
+#[cfg(feature = "tokio")]
+pub mod monitor {
+    use super::{Arc, Axiom, AxiomaticSelfHealer, Correction, HashMap, HealError};
+    use super::{HealReport, HealerHandle, Violation};
+    use std::future::Future;
+    use std::pin::Pin;
+    use tokio::sync::{mpsc, Semaphore};
+    use tokio::task::{JoinHandle, JoinSet};
+
+    /// Contexts healed at once by default
+    pub const DEFAULT_PARALLELISM: usize = 4;
+
+    /// Events buffered on the output channel by default before healing waits
+    pub const DEFAULT_EVENT_BUFFER: usize = 256;
+
+    pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+    /// A detector that awaits, such as one calling out to a classifier service
+    pub trait AsyncDetector: Send + Sync {
+        fn name(&self) -> &str;
+
+        fn detect<'a>(&'a self, context: &'a str) -> BoxFuture<'a, Vec<Violation>>;
+    }
+
+    /// A `CorrectionHandler` that awaits
+    pub trait AsyncCorrectionHandler: Send + Sync {
+        fn name(&self) -> &'static str;
+
+        fn correct<'a>(
+            &'a self,
+            context: &'a str,
+            violation: &'a Violation,
+        ) -> BoxFuture<'a, Correction>;
+    }
+
+    /// What the monitor reports for a context, tagged with its arrival number
+    #[derive(Debug)]
+    pub enum MonitorEvent {
+        /// A violation found in the context, by an async detector or the healer
+        Violation { seq: u64, violation: Violation },
+        /// The context finished healing
+        Healed { seq: u64, report: Box<HealReport> },
+        /// The healer returned an error for the context
+        Failed { seq: u64, error: HealError },
+        /// Healing panicked; the healer itself survives and keeps serving
+        Aborted { seq: u64, reason: String },
+    }
+
+    impl MonitorEvent {
+        pub fn seq(&self) -> u64 {
+            match self {
+                MonitorEvent::Violation { seq, .. }
+                | MonitorEvent::Healed { seq, .. }
+                | MonitorEvent::Failed { seq, .. }
+                | MonitorEvent::Aborted { seq, .. } => *seq,
+            }
+        }
+    }
+
+    /// A background healing task under construction; `spawn` starts it
+    pub struct AsyncMonitor {
+        healer: HealerHandle,
+        detectors: Vec<Arc<dyn AsyncDetector>>,
+        handlers: HashMap<Axiom, Arc<dyn AsyncCorrectionHandler>>,
+        parallelism: usize,
+        event_buffer: usize,
+    }
+
+    impl AsyncMonitor {
+        pub fn new(healer: impl Into<HealerHandle>) -> Self {
+            Self {
+                healer: healer.into(),
+                detectors: Vec::new(),
+                handlers: HashMap::new(),
+                parallelism: DEFAULT_PARALLELISM,
+                event_buffer: DEFAULT_EVENT_BUFFER,
+            }
+        }
+
+        /// Contexts healed at once; at least one
+        pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+            self.parallelism = parallelism.max(1);
+            self
+        }
+
+        /// Capacity of the output channel; healing waits while it is full
+        pub fn with_event_buffer(mut self, capacity: usize) -> Self {
+            self.event_buffer = capacity.max(1);
+            self
+        }
+
+        pub fn with_detector(mut self, detector: impl AsyncDetector + 'static) -> Self {
+            self.detectors.push(Arc::new(detector));
+            self
+        }
+
+        /// Hand violations of `axiom` found by async detectors to `handler`,
+        /// replacing any handler registered for it before
+        pub fn with_handler(
+            mut self,
+            axiom: Axiom,
+            handler: impl AsyncCorrectionHandler + 'static,
+        ) -> Self {
+            self.handlers.insert(axiom, Arc::new(handler));
+            self
+        }
+
+        /// The healer the task heals with, for statistics and configuration while
+        /// it runs
+        pub fn healer(&self) -> &HealerHandle {
+            &self.healer
+        }
+
+        /// Start healing contexts from `contexts` on the current runtime.
+        ///
+        /// The task ends once `contexts` is closed and every context taken from it has
+        /// been reported, or once the event receiver is dropped.
+        pub fn spawn(
+            self,
+            mut contexts: mpsc::Receiver<String>,
+        ) -> (mpsc::Receiver<MonitorEvent>, JoinHandle<()>) {
+            let (events, output) = mpsc::channel(self.event_buffer);
+            let permits = Arc::new(Semaphore::new(self.parallelism));
+            let monitor = Arc::new(self);
+            let task = tokio::spawn(async move {
+                let mut running = JoinSet::new();
+                let mut seq = 0;
+                while let Some(context) = contexts.recv().await {
+                    if events.is_closed() {
+                        break;
+                    }
+                    let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+                        break;
+                    };
+                    let (monitor, events) = (Arc::clone(&monitor), events.clone());
+                    running.spawn(async move {
+                        monitor.process(seq, context, &events).await;
+                        drop(permit);
+                    });
+                    seq += 1;
+                    while running.try_join_next().is_some() {}
+                }
+                while running.join_next().await.is_some() {}
+            });
+            (output, task)
+        }
+
+        async fn process(
+            &self,
+            seq: u64,
+            mut context: String,
+            events: &mpsc::Sender<MonitorEvent>,
+        ) {
+            for detector in &self.detectors {
+                for violation in detector.detect(&context).await {
+                    self.healer.with(|h| h.regularizer.record_violation(violation.clone()));
+                    if let Some(handler) = self.handlers.get(&violation.axiom) {
+                        match handler.correct(&context, &violation).await {
+                            Correction::Healed(text) | Correction::Partial(text) => context = text,
+                            Correction::Escalate(_) | Correction::Failed(_) => {}
+                        }
+                    }
+                    if events.send(MonitorEvent::Violation { seq, violation }).await.is_err() {
+                        return;
+                    }
+                }
+            }
+
+            let healer = self.healer.clone();
+            let healed = tokio::task::spawn_blocking(move || healer.monitor_and_heal(&context));
+            let event = match healed.await {
+                Ok(Ok(report)) => {
+                    for violation in &report.violations {
+                        let violation = violation.clone();
+                        if events.send(MonitorEvent::Violation { seq, violation }).await.is_err() {
+                            return;
+                        }
+                    }
+                    MonitorEvent::Healed { seq, report: Box::new(report) }
+                }
+                Ok(Err(error)) => MonitorEvent::Failed { seq, error },
+                Err(join) => MonitorEvent::Aborted { seq, reason: join.to_string() },
+            };
+            let _ = events.send(event).await;
+        }
+    }
+
+    impl AxiomaticSelfHealer {
+        /// Move the healer into a background task healing contexts from `contexts`;
+        /// see `AsyncMonitor` for async detectors, handlers and parallelism
+        pub fn spawn_monitor(
+            self,
+            contexts: mpsc::Receiver<String>,
+        ) -> (mpsc::Receiver<MonitorEvent>, JoinHandle<()>) {
+            AsyncMonitor::new(self).spawn(contexts)
+        }
+    }
+}
+
+/// Severities that depend on where and how a rule matched.
+///
+/// An expression is a severity (`low`, `medium`, `high` or `critical`) or
+/// `if <condition> then <expression> else <expression>`. Conditions compare two
+/// operands with `<`, `<=`, `>`, `>=`, `==` or `!=` and combine with `and`, `or`,
+/// `not` and parentheses. Operands are numbers, double-quoted strings, the match
+/// variables `match_count`, `position_ratio`, `context_len` and `match_len`, and
+/// violation metadata as `meta.<key>`:
+///
+/// `if position_ratio > 0.8 and match_count >= 2 then critical else medium`
+///
+/// Expressions are parsed once, when the rule is built, and evaluation is total. A
+/// variable without a value takes the default set with `with_default`, or else 0 (the
+/// empty string when compared with a string). A string compared with a number is
+/// compared as a number if it parses as one, and is otherwise only `!=` to it.
+pub mod severity_expr {
+    use super::{fmt, BTreeMap, BTreeSet, Severity};
+
+    /// Deeper nesting is rejected, keeping parsing and evaluation off the stack limit
+    const MAX_DEPTH: usize = 64;
+
+    /// A variable's value during evaluation
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        Number(f64),
+        Text(String),
+    }
+
+    /// Why an expression failed to parse, at a 1-based character column
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ParseError {
+        pub column: usize,
+        pub message: String,
+    }
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "column {}: {}", self.column, self.message)
+        }
+    }
+
+    impl std::error::Error for ParseError {}
+
+    /// The match an expression is evaluated for
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct MatchVars {
+        /// Occurrences of the pattern in the context
+        pub match_count: usize,
+        /// Where the first occurrence starts, from 0.0 at the start of the context to
+        /// just under 1.0 at its end
+        pub position_ratio: f64,
+        pub context_len: usize,
+        pub match_len: usize,
+        pub metadata: BTreeMap<String, String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Variable {
+        MatchCount,
+        PositionRatio,
+        ContextLen,
+        MatchLen,
+        Meta(String),
+    }
+
+    impl Variable {
+        fn name(&self) -> String {
+            match self {
+                Variable::MatchCount => "match_count".to_string(),
+                Variable::PositionRatio => "position_ratio".to_string(),
+                Variable::ContextLen => "context_len".to_string(),
+                Variable::MatchLen => "match_len".to_string(),
+                Variable::Meta(key) => format!("meta.{}", key),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Operand {
+        Number(f64),
+        Text(String),
+        Variable(Variable),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum CompareOp {
+        Lt,
+        Le,
+        Gt,
+        Ge,
+        Eq,
+        Ne,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Condition {
+        Compare(Operand, CompareOp, Operand),
+        Not(Box<Condition>),
+        All(Vec<Condition>),
+        Any(Vec<Condition>),
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Expr {
+        Severity(Severity),
+        If(Condition, Box<Expr>, Box<Expr>),
+    }
+
+    /// A parsed severity expression
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SeverityExpr {
+        source: String,
+        expr: Expr,
+        defaults: BTreeMap<String, Value>,
+    }
+
+    impl SeverityExpr {
+        pub fn parse(source: &str) -> Result<Self, ParseError> {
+            let tokens = tokenize(source)?;
+            let mut parser = Parser { tokens, next: 0, end: source.chars().count() + 1 };
+            let expr = parser.expr(0)?;
+            if let Some(token) = parser.tokens.get(parser.next) {
+                return Err(ParseError {
+                    column: token.column,
+                    message: format!("unexpected {} after the expression", token.kind),
+                });
+            }
+            Ok(Self { source: source.to_string(), expr, defaults: BTreeMap::new() })
+        }
+
+        /// The value `variable` (e.g. `match_count` or `meta.section`) takes when the
+        /// match has none
+        pub fn with_default(mut self, variable: &str, value: Value) -> Self {
+            self.defaults.insert(variable.to_string(), value);
+            self
+        }
+
+        /// The expression as written
+        pub fn source(&self) -> &str {
+            &self.source
+        }
+
+        /// Every severity the expression can produce
+        pub fn outcomes(&self) -> BTreeSet<Severity> {
+            let mut outcomes = BTreeSet::new();
+            let mut pending = vec![&self.expr];
+            while let Some(expr) = pending.pop() {
+                match expr {
+                    Expr::Severity(severity) => {
+                        outcomes.insert(*severity);
+                    }
+                    Expr::If(_, then, otherwise) => pending.extend([&**then, &**otherwise]),
+                }
+            }
+            outcomes
+        }
+
+        pub fn evaluate(&self, vars: &MatchVars) -> Severity {
+            let mut expr = &self.expr;
+            loop {
+                match expr {
+                    Expr::Severity(severity) => return *severity,
+                    Expr::If(condition, then, otherwise) => {
+                        expr = if self.holds(condition, vars) { then } else { otherwise };
+                    }
+                }
+            }
+        }
+
+        fn holds(&self, condition: &Condition, vars: &MatchVars) -> bool {
+            match condition {
+                Condition::Compare(left, op, right) => {
+                    let left = self.resolve(left, vars);
+                    let right = self.resolve(right, vars);
+                    let text_side = matches!(left, Some(Value::Text(_)))
+                        || matches!(right, Some(Value::Text(_)));
+                    let missing = || match text_side {
+                        true => Value::Text(String::new()),
+                        false => Value::Number(0.0),
+                    };
+                    compare(&left.unwrap_or_else(missing), *op, &right.unwrap_or_else(missing))
+                }
+                Condition::Not(inner) => !self.holds(inner, vars),
+                Condition::All(all) => all.iter().all(|c| self.holds(c, vars)),
+                Condition::Any(any) => any.iter().any(|c| self.holds(c, vars)),
+            }
+        }
+
+        fn resolve(&self, operand: &Operand, vars: &MatchVars) -> Option<Value> {
+            let variable = match operand {
+                Operand::Number(n) => return Some(Value::Number(*n)),
+                Operand::Text(text) => return Some(Value::Text(text.clone())),
+                Operand::Variable(variable) => variable,
+            };
+            let value = match variable {
+                Variable::MatchCount => Some(Value::Number(vars.match_count as f64)),
+                Variable::PositionRatio => Some(Value::Number(vars.position_ratio)),
+                Variable::ContextLen => Some(Value::Number(vars.context_len as f64)),
+                Variable::MatchLen => Some(Value::Number(vars.match_len as f64)),
+                Variable::Meta(key) => vars.metadata.get(key).cloned().map(Value::Text),
+            };
+            value.or_else(|| self.defaults.get(&variable.name()).cloned())
+        }
+    }
+
+    impl std::str::FromStr for SeverityExpr {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::parse(s)
+        }
+    }
+
+    fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+        let ordering = match (left, right) {
+            (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+            (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+            (Value::Number(a), Value::Text(b)) => {
+                b.trim().parse::<f64>().ok().and_then(|b| a.partial_cmp(&b))
+            }
+            (Value::Text(a), Value::Number(b)) => {
+                a.trim().parse::<f64>().ok().and_then(|a| a.partial_cmp(b))
+            }
+        };
+        let Some(ordering) = ordering else {
+            return op == CompareOp::Ne;
+        };
+        match op {
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TokenKind {
+        Number(f64),
+        Text(String),
+        Word(String),
+        Compare(CompareOp),
+        Open,
+        Close,
+    }
+
+    impl fmt::Display for TokenKind {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TokenKind::Number(n) => write!(f, "number {}", n),
+                TokenKind::Text(text) => write!(f, "string \"{}\"", text),
+                TokenKind::Word(word) => write!(f, "'{}'", word),
+                TokenKind::Compare(_) => write!(f, "comparison"),
+                TokenKind::Open => write!(f, "'('"),
+                TokenKind::Close => write!(f, "')'"),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct Token {
+        kind: TokenKind,
+        column: usize,
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let column = i + 1;
+            let error = |message: String| ParseError { column, message };
+            if c.is_whitespace() {
+                i += 1;
+                continue;
+            }
+            let kind = if c == '(' || c == ')' {
+                i += 1;
+                if c == '(' { TokenKind::Open } else { TokenKind::Close }
+            } else if c == '"' {
+                let end = chars[i + 1..].iter().position(|&c| c == '"');
+                let end = end.ok_or_else(|| error("unterminated string".to_string()))?;
+                let text = chars[i + 1..i + 1 + end].iter().collect();
+                i += end + 2;
+                TokenKind::Text(text)
+            } else if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()))
+            {
+                let len = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit() || **c == '.')
+                    .count();
+                let text: String = chars[i..i + 1 + len].iter().collect();
+                i += 1 + len;
+                let number = text.parse().map_err(|_| error(format!("invalid number {}", text)))?;
+                TokenKind::Number(number)
+            } else if c.is_alphabetic() || c == '_' {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '.')
+                    .count();
+                let word = chars[i..i + len].iter().collect();
+                i += len;
+                TokenKind::Word(word)
+            } else {
+                let next = chars.get(i + 1) == Some(&'=');
+                let op = match (c, next) {
+                    ('<', true) => CompareOp::Le,
+                    ('<', false) => CompareOp::Lt,
+                    ('>', true) => CompareOp::Ge,
+                    ('>', false) => CompareOp::Gt,
+                    ('=', true) => CompareOp::Eq,
+                    ('!', true) => CompareOp::Ne,
+                    ('=' | '!', false) => {
+                        return Err(error(format!("expected '=' after '{}'", c)));
+                    }
+                    _ => return Err(error(format!("unexpected character '{}'", c))),
+                };
+                i += if next { 2 } else { 1 };
+                TokenKind::Compare(op)
+            };
+            tokens.push(Token { kind, column });
+        }
+        Ok(tokens)
+    }
+
+    struct Parser {
+        tokens: Vec<Token>,
+        next: usize,
+        /// Column reported for errors at the end of the input
+        end: usize,
+    }
+
+    impl Parser {
+        fn peek(&self) -> Option<&TokenKind> {
+            self.tokens.get(self.next).map(|token| &token.kind)
+        }
+
+        fn peek_word(&self, word: &str) -> bool {
+            matches!(self.peek(), Some(TokenKind::Word(w)) if w == word)
+        }
+
+        fn error(&self, expected: &str) -> ParseError {
+            match self.tokens.get(self.next) {
+                Some(token) => ParseError {
+                    column: token.column,
+                    message: format!("expected {}, found {}", expected, token.kind),
+                },
+                None => ParseError {
+                    column: self.end,
+                    message: format!("expected {}, found end of input", expected),
+                },
+            }
+        }
+
+        fn enter(&self, depth: usize) -> Result<usize, ParseError> {
+            if depth >= MAX_DEPTH {
+                let column = self.tokens.get(self.next).map_or(self.end, |token| token.column);
+                return Err(ParseError {
+                    column,
+                    message: format!("expression nested more than {} deep", MAX_DEPTH),
+                });
+            }
+            Ok(depth + 1)
+        }
+
+        fn expect_word(&mut self, word: &str) -> Result<(), ParseError> {
+            if !self.peek_word(word) {
+                return Err(self.error(&format!("'{}'", word)));
+            }
+            self.next += 1;
+            Ok(())
+        }
+
+        fn expr(&mut self, depth: usize) -> Result<Expr, ParseError> {
+            let depth = self.enter(depth)?;
+            if self.peek_word("if") {
+                self.next += 1;
+                let condition = self.or(depth)?;
+                self.expect_word("then")?;
+                let then = self.expr(depth)?;
+                self.expect_word("else")?;
+                let otherwise = self.expr(depth)?;
+                return Ok(Expr::If(condition, Box::new(then), Box::new(otherwise)));
+            }
+            let severity = match self.peek() {
+                Some(TokenKind::Word(word)) => match word.as_str() {
+                    "low" => Severity::Low,
+                    "medium" => Severity::Medium,
+                    "high" => Severity::High,
+                    "critical" => Severity::Critical,
+                    _ => return Err(self.error("a severity or 'if'")),
+                },
+                _ => return Err(self.error("a severity or 'if'")),
+            };
+            self.next += 1;
+            Ok(Expr::Severity(severity))
+        }
+
+        fn or(&mut self, depth: usize) -> Result<Condition, ParseError> {
+            let mut any = vec![self.and(depth)?];
+            while self.peek_word("or") {
+                self.next += 1;
+                any.push(self.and(depth)?);
+            }
+            Ok(if any.len() == 1 { any.remove(0) } else { Condition::Any(any) })
+        }
+
+        fn and(&mut self, depth: usize) -> Result<Condition, ParseError> {
+            let mut all = vec![self.unary(depth)?];
+            while self.peek_word("and") {
+                self.next += 1;
+                all.push(self.unary(depth)?);
+            }
+            Ok(if all.len() == 1 { all.remove(0) } else { Condition::All(all) })
+        }
+
+        fn unary(&mut self, depth: usize) -> Result<Condition, ParseError> {
+            let depth = self.enter(depth)?;
+            if self.peek_word("not") {
+                self.next += 1;
+                return Ok(Condition::Not(Box::new(self.unary(depth)?)));
+            }
+            if self.peek() == Some(&TokenKind::Open) {
+                self.next += 1;
+                let condition = self.or(depth)?;
+                if self.peek() != Some(&TokenKind::Close) {
+                    return Err(self.error("')'"));
+                }
+                self.next += 1;
+                return Ok(condition);
+            }
+            let left = self.operand()?;
+            let op = match self.peek() {
+                Some(TokenKind::Compare(op)) => *op,
+                _ => return Err(self.error("a comparison")),
+            };
+            self.next += 1;
+            Ok(Condition::Compare(left, op, self.operand()?))
+        }
+
+        fn operand(&mut self) -> Result<Operand, ParseError> {
+            let operand = match self.peek() {
+                Some(TokenKind::Number(n)) => Operand::Number(*n),
+                Some(TokenKind::Text(text)) => Operand::Text(text.clone()),
+                Some(TokenKind::Word(word)) => Operand::Variable(match word.as_str() {
+                    "match_count" => Variable::MatchCount,
+                    "position_ratio" => Variable::PositionRatio,
+                    "context_len" => Variable::ContextLen,
+                    "match_len" => Variable::MatchLen,
+                    word => match word.strip_prefix("meta.") {
+                        Some(key) if !key.is_empty() => Variable::Meta(key.to_string()),
+                        _ => {
+                            let column = self.tokens[self.next].column;
+                            let message = format!("unknown variable '{}'", word);
+                            return Err(ParseError { column, message });
+                        }
+                    },
+                }),
+                _ => return Err(self.error("a number, string or variable")),
+            };
+            self.next += 1;
+            Ok(operand)
+        }
+    }
+}
+
+/// Opt-in process-wide healer for small tools that don't want to pass a handle around
+pub mod global {
+    use super::{AxiomaticSelfHealer, HealError, HealReport, HealerHandle, Violation};
+    use std::fmt;
+    use std::sync::OnceLock;
+
+    static HEALER: OnceLock<HealerHandle> = OnceLock::new();
+
+    /// Returned by `init` when the global healer is already set
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AlreadyInitialized;
+
+    impl fmt::Display for AlreadyInitialized {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "The global healer is already initialized")
+        }
+    }
+
+    impl std::error::Error for AlreadyInitialized {}
+
+    /// Install the global healer; it can only be set once per process
+    pub fn init(healer: AxiomaticSelfHealer) -> Result<(), AlreadyInitialized> {
+        HEALER
+            .set(HealerHandle::new(healer))
+            .map_err(|_| AlreadyInitialized)
+    }
+
+    /// The global healer, or `None` before `init`
+    pub fn try_healer() -> Option<&'static HealerHandle> {
+        HEALER.get()
+    }
+
+    /// The global healer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `init` has not been called; use `try_healer` to degrade gracefully.
+    pub fn healer() -> &'static HealerHandle {
+        try_healer().expect("global::init must be called before global::healer")
+    }
+
+    /// Heal a context with the global healer
+    pub fn heal(context: &str) -> Result<HealReport, HealError> {
+        healer().monitor_and_heal(context)
+    }
+
+    /// Detect violations with the global healer
+    pub fn detect(context: &str) -> Vec<Violation> {
+        healer().detect(context)
+    }
+}
+
+/// Versioned identifiers for everything the crate serializes.
+///
+/// Every serialized artifact carries its schema id, e.g. `aar.violation.v1`, and every
+/// reader checks it, failing with a `SchemaError` rather than misreading a file.
+/// Older versions are upgraded through `Migrations` before they are parsed.
+pub mod schema {
+    use std::collections::HashMap;
+    use std::fmt;
+
+    /// An artifact kind and the version of its format
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct SchemaId {
+        pub kind: &'static str,
+        pub version: u32,
+    }
+
+    impl fmt::Display for SchemaId {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}.v{}", self.kind, self.version)
+        }
+    }
+
+    /// `HealerSnapshot` files; the version is the number in the `AARSNAP` header
+    pub const SNAPSHOT: SchemaId = SchemaId { kind: "aar.snapshot", version: 4 };
+    /// One JSON violation per line, as written by `export_history`
+    pub const VIOLATION: SchemaId = SchemaId { kind: "aar.violation", version: 1 };
+    /// A `ComparisonReport`, as written by its `to_json`
+    pub const COMPARISON: SchemaId = SchemaId { kind: "aar.comparison", version: 1 };
+    /// A `SelfReport`, as written by its `to_json`
+    pub const SELF_REPORT: SchemaId = SchemaId { kind: "aar.self_report", version: 1 };
+    /// Audit items spilled from a full `AuditQueue`
+    pub const AUDIT: SchemaId = SchemaId { kind: "aar.audit", version: 1 };
+    /// A `CoverageMatrix`, as written by its `to_json`
+    pub const COVERAGE: SchemaId = SchemaId { kind: "aar.coverage", version: 1 };
+    /// A `SnapshotDiff`, as written by its `to_json`
+    pub const SNAPSHOT_DIFF: SchemaId = SchemaId { kind: "aar.snapshot_diff", version: 3 };
+    /// Saved weights and history, as written by `save_state`
+    pub const STATE: SchemaId = SchemaId { kind: "aar.state", version: 2 };
+    /// An `EscalationAlert`, as written by its `to_json`
+    pub const ALERT: SchemaId = SchemaId { kind: "aar.alert", version: 1 };
+
+    /// Split an id like `aar.violation.v1` into its kind and version
+    pub fn parse(id: &str) -> Option<(&str, u32)> {
+        let (kind, version) = id.rsplit_once(".v")?;
+        Some((kind, version.parse().ok()?))
+    }
+
+    /// An artifact has a schema this build can't read
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SchemaError {
+        /// The schema id found in the artifact
+        pub found: String,
+        pub supported: SchemaId,
+    }
+
+    impl fmt::Display for SchemaError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Unsupported schema '{}' (supported: {})", self.found, self.supported)
+        }
+    }
+
+    impl std::error::Error for SchemaError {}
+
+    /// Why a serialized artifact could not be read
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ArtifactError {
+        Schema(SchemaError),
+        /// The schema is supported but the content doesn't follow it
+        Malformed(String),
+    }
+
+    impl fmt::Display for ArtifactError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ArtifactError::Schema(e) => e.fmt(f),
+                ArtifactError::Malformed(reason) => write!(f, "Malformed artifact: {}", reason),
+            }
+        }
+    }
+
+    impl std::error::Error for ArtifactError {}
+
+    impl From<SchemaError> for ArtifactError {
+        fn from(e: SchemaError) -> Self {
+            ArtifactError::Schema(e)
+        }
+    }
+
+    /// Upgrades the serialized text of one version to the next
+    pub type Migration = fn(String) -> String;
+
+    /// Registered migration steps, applied in sequence up to the supported version
+    #[derive(Debug, Clone, Default)]
+    pub struct Migrations {
+        steps: HashMap<(String, u32), Migration>,
+    }
+
+    impl Migrations {
+        /// The migrations shipped with the crate
+        pub fn builtin() -> Self {
+            let mut migrations = Self::default();
+            // Violation lines written before schema ids existed have the v1 fields
+            migrations.register(VIOLATION.kind, 0, |text| text);
+            // Snapshots from before disabled axioms and counters were captured just
+            // have none of those lines
+            migrations.register(SNAPSHOT.kind, 1, |text| text);
+            // Per-axiom learning rates and rate scales came next, in both snapshots and
+            // state headers; older ones have none
+            migrations.register(SNAPSHOT.kind, 2, |text| text);
+            migrations.register(STATE.kind, 1, |text| text);
+            // Snapshots without sentence templates never captured replace_sentence
+            migrations.register(SNAPSHOT.kind, 3, |text| text);
+            migrations
+        }
+
+        /// Upgrade `kind` artifacts from `from_version` to `from_version + 1`
+        pub fn register(&mut self, kind: &str, from_version: u32, step: Migration) {
+            self.steps.insert((kind.to_string(), from_version), step);
+        }
+
+        /// Bring `data`, found with schema id `found`, up to `target`
+        pub fn migrate(
+            &self,
+            found: &str,
+            target: SchemaId,
+            mut data: String,
+        ) -> Result<String, SchemaError> {
+            let error = || SchemaError { found: found.to_string(), supported: target };
+            let (kind, mut version) = parse(found).ok_or_else(error)?;
+            if kind != target.kind || version > target.version {
+                return Err(error());
+            }
+            while version < target.version {
+                let step = self.steps.get(&(kind.to_string(), version)).ok_or_else(error)?;
+                data = step(data);
+                version += 1;
+            }
+            Ok(data)
+        }
+    }
+}
+
+/// Backfill violation history from structured logs written before the healer existed.
+///
+/// Rows that can't be mapped are skipped and counted; the reasons for the first few are
+/// kept in the `ImportSummary`. Imported violations carry `source=import` metadata, and
+/// their contexts are passed through the mapping's `QuotePolicy`.
+pub mod import {
+    use super::schema::{self, ArtifactError, Migrations};
+    use super::{
+        Axiom, BTreeMap, ComparedViolation, ComparisonReport, HashMap, QuotePolicy, Severity,
+        Violation,
+    };
+    use std::io::BufRead;
+
+    /// How timestamps are written in the source
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TimestampFormat {
+        EpochMillis,
+        EpochSeconds,
+        /// A pattern of literal text and `%Y %m %d %H %M %S %f` fields, read as UTC;
+        /// `%f` is a fraction of a second of any length
+        Pattern(String),
+    }
+
+    /// Which source fields hold each violation attribute
+    #[derive(Debug, Clone)]
+    pub struct FieldMapping {
+        pub axiom_field: String,
+        /// Source values translated to axioms; other values must be axiom names
+        pub axiom_values: HashMap<String, Axiom>,
+        pub severity_field: String,
+        /// Source values translated to severities; other values must be severity names
+        pub severity_values: HashMap<String, Severity>,
+        pub timestamp_field: String,
+        pub timestamp_format: TimestampFormat,
+        pub context_field: String,
+        /// Applied to each imported context
+        pub quote: QuotePolicy,
+        /// Row errors kept in the summary; further errors are only counted
+        pub max_errors: usize,
+    }
+
+    impl FieldMapping {
+        /// Map the four fields by name, with epoch-millisecond timestamps
+        pub fn new(axiom: &str, severity: &str, timestamp: &str, context: &str) -> Self {
+            Self {
+                axiom_field: axiom.to_string(),
+                axiom_values: HashMap::new(),
+                severity_field: severity.to_string(),
+                severity_values: HashMap::new(),
+                timestamp_field: timestamp.to_string(),
+                timestamp_format: TimestampFormat::EpochMillis,
+                context_field: context.to_string(),
+                quote: QuotePolicy::default(),
+                max_errors: 100,
+            }
+        }
+
+        pub fn axiom_value(mut self, value: &str, axiom: Axiom) -> Self {
+            self.axiom_values.insert(value.to_string(), axiom);
+            self
+        }
+
+        pub fn severity_value(mut self, value: &str, severity: Severity) -> Self {
+            self.severity_values.insert(value.to_string(), severity);
+            self
+        }
+
+        pub fn timestamp_format(mut self, format: TimestampFormat) -> Self {
+            self.timestamp_format = format;
+            self
+        }
+
+        pub fn quote_policy(mut self, quote: QuotePolicy) -> Self {
+            self.quote = quote;
+            self
+        }
+
+        fn violation(&self, get: impl Fn(&str) -> Option<String>) -> Result<Violation, String> {
+            let field = |name: &str| get(name).ok_or_else(|| format!("missing field '{}'", name));
+
+            let raw = field(&self.axiom_field)?;
+            let axiom = match self.axiom_values.get(&raw) {
+                Some(axiom) => axiom.clone(),
+                None => raw.parse()?,
+            };
+            let raw = field(&self.severity_field)?;
+            let severity = match self.severity_values.get(&raw) {
+                Some(severity) => *severity,
+                None => raw.parse()?,
+            };
+            let raw = field(&self.timestamp_field)?;
+            let timestamp = parse_timestamp(&raw, &self.timestamp_format)
+                .ok_or_else(|| format!("bad timestamp '{}'", raw))?;
+            let context = field(&self.context_field)?;
+
+            Ok(Violation {
+                axiom,
+                severity,
+                context: self.quote.quote(&context),
+                timestamp,
+                metadata: BTreeMap::from([("source".to_string(), "import".to_string())]),
+            })
+        }
+    }
+
+    /// A source row that was skipped
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RowError {
+        /// 1-based line of the row in the source
+        pub line: usize,
+        pub reason: String,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ImportSummary {
+        pub imported: usize,
+        pub skipped: usize,
+        /// The first `FieldMapping::max_errors` skipped rows and why
+        pub errors: Vec<RowError>,
+    }
+
+    /// Violations read from a source, with an account of what was skipped
+    #[derive(Debug, Clone, Default)]
+    pub struct Imported {
+        pub violations: Vec<Violation>,
+        pub summary: ImportSummary,
+    }
+
+    impl Imported {
+        fn push(&mut self, line: usize, row: Result<Violation, String>, max_errors: usize) {
+            match row {
+                Ok(violation) => {
+                    self.summary.imported += 1;
+                    self.violations.push(violation);
+                }
+                Err(reason) => {
+                    self.summary.skipped += 1;
+                    if self.summary.errors.len() < max_errors {
+                        self.summary.errors.push(RowError { line, reason });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Import one JSON object per line; blank lines are ignored.
+    ///
+    /// Field names may be dotted paths into nested objects, e.g. `"labels.axiom"`.
+    pub fn from_json_lines(
+        reader: impl BufRead,
+        mapping: &FieldMapping,
+    ) -> std::io::Result<Imported> {
+        let mut imported = Imported::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = Json::parse(&line).and_then(|json| match json {
+                Json::Object(_) => mapping.violation(|name| json.path(name)),
+                _ => Err("row is not a JSON object".to_string()),
+            });
+            imported.push(index + 1, row, mapping.max_errors);
+        }
+        Ok(imported)
+    }
+
+    /// Read violations written by `AdaptiveAxiomaticRegularizer::export_history`,
+    /// upgrading older lines with `migrations`; blank lines are ignored
+    pub fn from_history_jsonl(
+        reader: impl BufRead,
+        migrations: &Migrations,
+    ) -> std::io::Result<Imported> {
+        const MAX_ERRORS: usize = 100;
+        let mut imported = Imported::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = violation_from_json(&line, migrations).map_err(|e| e.to_string());
+            imported.push(index + 1, row, MAX_ERRORS);
+        }
+        Ok(imported)
+    }
+
+    /// One line of `schema::VIOLATION` JSON
+    pub(super) fn violation_to_json(violation: &Violation) -> String {
+        let metadata: Vec<String> = violation
+            .metadata
+            .iter()
+            .map(|(k, v)| format!("{}:{}", json_string(k), json_string(v)))
+            .collect();
+        format!(
+            "{{\"schema\":{},\"axiom\":{},\"severity\":{},\"timestamp\":{},\"context\":{},\
+             \"metadata\":{{{}}}}}",
+            json_string(&schema::VIOLATION.to_string()),
+            json_string(&format!("{:?}", violation.axiom)),
+            json_string(&format!("{:?}", violation.severity)),
+            violation.timestamp,
+            json_string(&violation.context),
+            metadata.join(",")
+        )
+    }
+
+    /// First line of a saved state
+    pub(super) struct StateHeader {
+        pub(super) learning_rate: f64,
+        pub(super) threshold: f64,
+        pub(super) weights: BTreeMap<Axiom, f64>,
+        /// Per-axiom overrides of `learning_rate`
+        pub(super) learning_rates: BTreeMap<Axiom, f64>,
+        /// The feedback policy's decayed step scale per axiom
+        pub(super) rate_scales: BTreeMap<Axiom, f64>,
+        /// Number of violation lines that follow
+        pub(super) history: usize,
+        /// Encoded `HealerSnapshot`, when a healer saved the state
+        pub(super) healer: Option<String>,
+    }
+
+    pub(super) fn state_header_to_json(header: &StateHeader) -> String {
+        let by_axiom = |values: &BTreeMap<Axiom, f64>| {
+            let fields: Vec<String> = values
+                .iter()
+                .map(|(axiom, value)| format!("{}:{}", json_string(&format!("{:?}", axiom)), value))
+                .collect();
+            fields.join(",")
+        };
+        let healer = header
+            .healer
+            .as_deref()
+            .map(|snapshot| format!(",\"healer\":{}", json_string(snapshot)))
+            .unwrap_or_default();
+        format!(
+            "{{\"schema\":{},\"learning_rate\":{},\"threshold\":{},\"weights\":{{{}}},\
+             \"learning_rates\":{{{}}},\"rate_scales\":{{{}}},\"history\":{}{}}}",
+            json_string(&schema::STATE.to_string()),
+            header.learning_rate,
+            header.threshold,
+            by_axiom(&header.weights),
+            by_axiom(&header.learning_rates),
+            by_axiom(&header.rate_scales),
+            header.history,
+            healer
+        )
+    }
+
+    pub(super) fn state_header_from_json(
+        line: &str,
+        migrations: &Migrations,
+    ) -> Result<StateHeader, ArtifactError> {
+        let malformed = ArtifactError::Malformed;
+        let json = Json::parse(line).map_err(malformed)?;
+        let found = json.path("schema").unwrap_or_default();
+        let migrated = migrations.migrate(&found, schema::STATE, line.to_string())?;
+        let json = Json::parse(&migrated).map_err(malformed)?;
+
+        let field = |name: &str| {
+            json.path(name)
+                .ok_or_else(|| ArtifactError::Malformed(format!("missing field '{}'", name)))
+        };
+        let number = |name: &str| {
+            let text = field(name)?;
+            text.parse::<f64>()
+                .map_err(|_| ArtifactError::Malformed(format!("invalid {} '{}'", name, text)))
+        };
+        // Absent maps are empty: v1 headers have no learning rates or rate scales
+        let by_axiom = |key: &str, what: &str| -> Result<BTreeMap<Axiom, f64>, ArtifactError> {
+            let mut values = BTreeMap::new();
+            let Json::Object(fields) = &json else {
+                return Ok(values);
+            };
+            let Some((_, Json::Object(entries))) = fields.iter().find(|(k, _)| k == key) else {
+                return Ok(values);
+            };
+            for (name, value) in entries {
+                let axiom: Axiom = name.parse().map_err(malformed)?;
+                let number = match value {
+                    Json::Scalar(text) => text.parse::<f64>().ok(),
+                    _ => None,
+                };
+                let number = number.ok_or_else(|| {
+                    ArtifactError::Malformed(format!("invalid {} for {}", what, name))
+                })?;
+                values.insert(axiom, number);
+            }
+            Ok(values)
+        };
+        let history = field("history")?;
+        Ok(StateHeader {
+            learning_rate: number("learning_rate")?,
+            threshold: number("threshold")?,
+            weights: by_axiom("weights", "weight")?,
+            learning_rates: by_axiom("learning_rates", "learning rate")?,
+            rate_scales: by_axiom("rate_scales", "rate scale")?,
+            history: history
+                .parse()
+                .map_err(|_| ArtifactError::Malformed(format!("invalid history '{}'", history)))?,
+            healer: json.path("healer"),
+        })
+    }
+
+    pub(super) fn comparison_to_json(report: &ComparisonReport) -> String {
+        let list = |items: &[ComparedViolation]| {
+            let items: Vec<String> = items
+                .iter()
+                .map(|v| {
+                    format!(
+                        "{{\"axiom\":{},\"severity\":{},\"rule\":{},\"fingerprint\":{}}}",
+                        json_string(&format!("{:?}", v.axiom)),
+                        json_string(&format!("{:?}", v.severity)),
+                        json_string(&v.rule),
+                        v.fingerprint
+                    )
+                })
+                .collect();
+            format!("[{}]", items.join(","))
+        };
+        let per_axiom: Vec<String> = report
+            .per_axiom
+            .iter()
+            .map(|(axiom, delta)| {
+                format!(
+                    "{}:{{\"before\":{},\"after\":{}}}",
+                    json_string(&format!("{:?}", axiom)),
+                    delta.before,
+                    delta.after
+                )
+            })
+            .collect();
+        format!(
+            "{{\"schema\":{},\"fixed\":{},\"persisting\":{},\"introduced\":{},\
+             \"penalty_before\":{},\"penalty_after\":{},\"per_axiom\":{{{}}}}}",
+            json_string(&schema::COMPARISON.to_string()),
+            list(&report.fixed),
+            list(&report.persisting),
+            list(&report.introduced),
+            report.penalty_before,
+            report.penalty_after,
+            per_axiom.join(",")
+        )
+    }
+
+    pub(super) fn violation_from_json(
+        line: &str,
+        migrations: &Migrations,
+    ) -> Result<Violation, ArtifactError> {
+        let malformed = ArtifactError::Malformed;
+        let json = Json::parse(line).map_err(malformed)?;
+        // Lines from before schema ids were introduced are version 0
+        let found = json
+            .path("schema")
+            .unwrap_or_else(|| format!("{}.v0", schema::VIOLATION.kind));
+        let migrated = migrations.migrate(&found, schema::VIOLATION, line.to_string())?;
+        let json = Json::parse(&migrated).map_err(malformed)?;
+
+        let field = |name: &str| {
+            json.path(name)
+                .ok_or_else(|| ArtifactError::Malformed(format!("missing field '{}'", name)))
+        };
+        let mut metadata = BTreeMap::new();
+        if let Json::Object(fields) = &json {
+            if let Some((_, Json::Object(entries))) = fields.iter().find(|(k, _)| k == "metadata") {
+                for (key, value) in entries {
+                    if let Json::Scalar(value) = value {
+                        metadata.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        Ok(Violation {
+            axiom: field("axiom")?.parse().map_err(malformed)?,
+            severity: field("severity")?.parse().map_err(malformed)?,
+            context: field("context")?,
+            timestamp: field("timestamp")?
+                .parse()
+                .map_err(|_| ArtifactError::Malformed("invalid timestamp".to_string()))?,
+            metadata,
+        })
+    }
+
+    pub(super) fn json_string(text: &str) -> String {
+        let mut out = String::with_capacity(text.len() + 2);
+        out.push('"');
+        for c in text.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    /// Import CSV with a header row naming the fields; quoted fields may span lines
+    pub fn from_csv(reader: impl BufRead, mapping: &FieldMapping) -> std::io::Result<Imported> {
+        let mut imported = Imported::default();
+        let mut records = CsvRecords { lines: reader.lines(), line: 0 };
+        let header = match records.next_record()? {
+            Some((_, Ok(header))) => header,
+            Some((line, Err(reason))) => {
+                imported.push(line, Err(format!("bad header: {}", reason)), mapping.max_errors);
+                return Ok(imported);
+            }
+            None => return Ok(imported),
+        };
+
+        while let Some((line, record)) = records.next_record()? {
+            let row = record.and_then(|fields| {
+                if fields.len() != header.len() {
+                    return Err(format!("expected {} fields, found {}", header.len(), fields.len()));
+                }
+                mapping.violation(|name| {
+                    let at = header.iter().position(|h| h == name)?;
+                    Some(fields[at].clone()).filter(|value| !value.is_empty())
+                })
+            });
+            imported.push(line, row, mapping.max_errors);
+        }
+        Ok(imported)
+    }
+
+    /// A source row as field name to non-empty text value, or why it could not be read
+    pub(super) type Row = (usize, Result<HashMap<String, String>, String>);
+
+    /// Rows of a JSON-lines source, keyed by top-level field; blank lines are ignored
+    pub(super) fn json_rows(reader: impl BufRead) -> std::io::Result<Vec<Row>> {
+        let mut rows = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = Json::parse(&line).and_then(|json| match json {
+                Json::Object(fields) => Ok(fields
+                    .into_iter()
+                    .filter_map(|(key, value)| match value {
+                        Json::Scalar(text) if !text.is_empty() => Some((key, text)),
+                        _ => None,
+                    })
+                    .collect()),
+                _ => Err("row is not a JSON object".to_string()),
+            });
+            rows.push((index + 1, row));
+        }
+        Ok(rows)
+    }
+
+    /// Rows of a CSV source with a header row
+    pub(super) fn csv_rows(reader: impl BufRead) -> std::io::Result<Vec<Row>> {
+        let mut rows = Vec::new();
+        let mut records = CsvRecords { lines: reader.lines(), line: 0 };
+        let header = match records.next_record()? {
+            Some((_, Ok(header))) => header,
+            Some((line, Err(reason))) => {
+                return Ok(vec![(line, Err(format!("bad header: {}", reason)))]);
+            }
+            None => return Ok(rows),
+        };
+        while let Some((line, record)) = records.next_record()? {
+            let row = record.and_then(|fields| {
+                if fields.len() != header.len() {
+                    return Err(format!("expected {} fields, found {}", header.len(), fields.len()));
+                }
+                Ok(header
+                    .iter()
+                    .cloned()
+                    .zip(fields)
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect())
+            });
+            rows.push((line, row));
+        }
+        Ok(rows)
+    }
+
+    /// A record's fields, or why they couldn't be split, and the line it starts on
+    type CsvRecord = (usize, Result<Vec<String>, String>);
+
+    struct CsvRecords<L> {
+        lines: L,
+        line: usize,
+    }
+
+    impl<L: Iterator<Item = std::io::Result<String>>> CsvRecords<L> {
+        /// The next non-blank record and the line it starts on
+        fn next_record(&mut self) -> std::io::Result<Option<CsvRecord>> {
+            let mut text = String::new();
+            let mut start = None;
+            for line in self.lines.by_ref() {
+                let line = line?;
+                self.line += 1;
+                if start.is_none() && line.trim().is_empty() {
+                    continue;
+                }
+                start.get_or_insert(self.line);
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&line);
+                // An odd number of quotes means a quoted field continues on the next line
+                if text.matches('"').count().is_multiple_of(2) {
+                    break;
+                }
+            }
+            Ok(start.map(|line| (line, split_csv(&text))))
+        }
+    }
+
+    fn split_csv(record: &str) -> Result<Vec<String>, String> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut chars = record.chars().peekable();
+        let mut quoted = false;
+        let mut at_field_start = true;
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' if quoted => quoted = false,
+                '"' if at_field_start => quoted = true,
+                '"' => return Err("quote inside an unquoted field".to_string()),
+                ',' if !quoted => {
+                    fields.push(std::mem::take(&mut field));
+                    at_field_start = true;
+                    continue;
+                }
+                c => field.push(c),
+            }
+            at_field_start = false;
+        }
+        if quoted {
+            return Err("unterminated quoted field".to_string());
+        }
+        fields.push(field);
+        Ok(fields)
+    }
+
+    fn parse_timestamp(raw: &str, format: &TimestampFormat) -> Option<u64> {
+        match format {
+            TimestampFormat::EpochMillis => raw.trim().parse().ok(),
+            TimestampFormat::EpochSeconds => {
+                let seconds: f64 = raw.trim().parse().ok()?;
+                (seconds >= 0.0 && seconds.is_finite()).then(|| (seconds * 1000.0).round() as u64)
+            }
+            TimestampFormat::Pattern(pattern) => parse_pattern(raw, pattern),
+        }
+    }
+
+    fn parse_pattern(raw: &str, pattern: &str) -> Option<u64> {
+        let (mut year, mut month, mut day) = (1970i64, 1u32, 1u32);
+        let (mut hour, mut minute, mut second, mut millis) = (0u32, 0u32, 0u32, 0u32);
+        let mut input = raw;
+        let mut spec = pattern.chars();
+
+        fn digits<'a>(input: &mut &'a str, max: usize) -> Option<&'a str> {
+            let len = input.bytes().take(max).take_while(u8::is_ascii_digit).count();
+            let (taken, rest) = (len > 0).then(|| input.split_at(len))?;
+            *input = rest;
+            Some(taken)
+        }
+
+        while let Some(c) = spec.next() {
+            if c != '%' {
+                input = input.strip_prefix(c)?;
+                continue;
+            }
+            match spec.next()? {
+                'Y' => year = digits(&mut input, 4)?.parse().ok()?,
+                'm' => month = digits(&mut input, 2)?.parse().ok()?,
+                'd' => day = digits(&mut input, 2)?.parse().ok()?,
+                'H' => hour = digits(&mut input, 2)?.parse().ok()?,
+                'M' => minute = digits(&mut input, 2)?.parse().ok()?,
+                'S' => second = digits(&mut input, 2)?.parse().ok()?,
+                'f' => {
+                    let fraction = digits(&mut input, usize::MAX)?;
+                    let padded = format!("{:0<3}", &fraction[..fraction.len().min(3)]);
+                    millis = padded.parse().ok()?;
+                }
+                '%' => input = input.strip_prefix('%')?,
+                _ => return None,
+            }
+        }
+        let valid = input.is_empty()
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+        if !valid {
+            return None;
+        }
+
+        let days = days_from_civil(year, month, day);
+        let seconds = days * 86_400 + i64::from(hour * 3600 + minute * 60 + second);
+        u64::try_from(seconds * 1000 + i64::from(millis)).ok()
+    }
+
+    fn days_in_month(year: i64, month: u32) -> u32 {
+        match month {
+            2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Days since 1970-01-01 of a proleptic Gregorian date
+    fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The four hex digits of a `\u` escape
+    fn hex4(chars: &mut std::str::CharIndices<'_>) -> Option<u32> {
+        let digits: String = chars.take(4).map(|(_, c)| c).collect();
+        u32::from_str_radix(&digits, 16).ok().filter(|_| digits.len() == 4)
+    }
+
+    /// Just enough JSON to read log rows
+    enum Json {
+        Null,
+        Scalar(String),
+        Array,
+        Object(Vec<(String, Json)>),
+    }
+
+    impl Json {
+        fn parse(text: &str) -> Result<Json, String> {
+            let mut parser = JsonParser { text, pos: 0 };
+            let value = parser.value()?;
+            parser.skip_whitespace();
+            if parser.pos != text.len() {
+                return Err(format!("trailing characters at column {}", parser.pos + 1));
+            }
+            Ok(value)
+        }
+
+        /// The scalar at a dotted path, as text
+        fn path(&self, path: &str) -> Option<String> {
+            let mut value = self;
+            for key in path.split('.') {
+                value = match value {
+                    Json::Object(fields) => &fields.iter().rev().find(|(k, _)| k == key)?.1,
+                    _ => return None,
+                };
+            }
+            match value {
+                Json::Scalar(text) => Some(text.clone()),
+                Json::Null | Json::Array | Json::Object(_) => None,
+            }
+        }
+    }
+
+    struct JsonParser<'a> {
+        text: &'a str,
+        pos: usize,
+    }
+
+    impl JsonParser<'_> {
+        fn error(&self, what: &str) -> String {
+            format!("invalid JSON: {} at column {}", what, self.pos + 1)
+        }
+
+        fn skip_whitespace(&mut self) {
+            let rest = &self.text[self.pos..];
+            self.pos += rest.len() - rest.trim_start().len();
+        }
+
+        fn eat(&mut self, c: u8) -> bool {
+            let found = self.text.as_bytes().get(self.pos) == Some(&c);
+            self.pos += usize::from(found);
+            found
+        }
+
+        fn value(&mut self) -> Result<Json, String> {
+            self.skip_whitespace();
+            let rest = &self.text[self.pos..];
+            match rest.bytes().next() {
+                Some(b'{') => self.object(),
+                Some(b'[') => self.array(),
+                Some(b'"') => self.string().map(Json::Scalar),
+                Some(b'n') if rest.starts_with("null") => {
+                    self.pos += 4;
+                    Ok(Json::Null)
+                }
+                Some(b't') if rest.starts_with("true") => {
+                    self.pos += 4;
+                    Ok(Json::Scalar("true".to_string()))
+                }
+                Some(b'f') if rest.starts_with("false") => {
+                    self.pos += 5;
+                    Ok(Json::Scalar("false".to_string()))
+                }
+                Some(b'-' | b'0'..=b'9') => {
+                    let len = rest
+                        .bytes()
+                        .take_while(|b| matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+                        .count();
+                    let number = &rest[..len];
+                    number.parse::<f64>().map_err(|_| self.error("bad number"))?;
+                    self.pos += len;
+                    Ok(Json::Scalar(number.to_string()))
+                }
+                _ => Err(self.error("expected a value")),
+            }
+        }
+
+        fn object(&mut self) -> Result<Json, String> {
+            self.pos += 1;
+            let mut fields = Vec::new();
+            self.skip_whitespace();
+            if self.eat(b'}') {
+                return Ok(Json::Object(fields));
+            }
+            loop {
+                self.skip_whitespace();
+                let key = self.string()?;
+                self.skip_whitespace();
+                if !self.eat(b':') {
+                    return Err(self.error("expected ':'"));
+                }
+                fields.push((key, self.value()?));
+                self.skip_whitespace();
+                if self.eat(b'}') {
+                    return Ok(Json::Object(fields));
+                }
+                if !self.eat(b',') {
+                    return Err(self.error("expected ',' or '}'"));
+                }
+            }
+        }
+
+        fn array(&mut self) -> Result<Json, String> {
+            self.pos += 1;
+            // Arrays are validated but their items are never mapped, so they aren't kept
+            self.skip_whitespace();
+            if self.eat(b']') {
+                return Ok(Json::Array);
+            }
+            loop {
+                self.value()?;
+                self.skip_whitespace();
+                if self.eat(b']') {
+                    return Ok(Json::Array);
+                }
+                if !self.eat(b',') {
+                    return Err(self.error("expected ',' or ']'"));
+                }
+            }
+        }
+
+        fn string(&mut self) -> Result<String, String> {
+            if !self.eat(b'"') {
+                return Err(self.error("expected a string"));
+            }
+            let mut out = String::new();
+            let mut chars = self.text[self.pos..].char_indices();
+            while let Some((offset, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        self.pos += offset + 1;
+                        return Ok(out);
+                    }
+                    '\\' => {
+                        let escaped = match chars.next().map(|(_, c)| c) {
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            Some('/') => '/',
+                            Some('b') => '\u{8}',
+                            Some('f') => '\u{c}',
+                            Some('n') => '\n',
+                            Some('r') => '\r',
+                            Some('t') => '\t',
+                            Some('u') => {
+                                let unit = hex4(&mut chars)
+                                    .ok_or_else(|| self.error("bad \\u escape"))?;
+                                let code = if (0xD800..0xDC00).contains(&unit) {
+                                    let low = match (chars.next(), chars.next()) {
+                                        (Some((_, '\\')), Some((_, 'u'))) => hex4(&mut chars),
+                                        _ => None,
+                                    };
+                                    let low = low.filter(|low| (0xDC00..0xE000).contains(low));
+                                    let low = low.ok_or_else(|| self.error("unpaired surrogate"))?;
+                                    0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                                } else {
+                                    unit
+                                };
+                                char::from_u32(code).ok_or_else(|| self.error("bad \\u escape"))?
+                            }
+                            _ => return Err(self.error("bad escape")),
+                        };
+                        out.push(escaped);
+                    }
+                    c if (c as u32) < 0x20 => return Err(self.error("control character in string")),
+                    c => out.push(c),
+                }
+            }
+            Err(self.error("unterminated string"))
+        }
+    }
+}
+
+/// Healer configuration read from a TOML policy file.
+///
+/// At the top level a policy may set `learning_rate`, `threshold`, and `heal` (one of
+/// `"above"`, `"at_or_above"`, `"any"` or `"never"`, with `threshold` as the value to
+/// compare against), plus a `[severity_multipliers]` table, an `[axioms.<name>]` table
+/// for each axiom it configures, and any number of `[[rules]]`. An axiom table names a
+/// built-in axiom unless it sets `custom = true`, so a misspelt built-in is an error
+/// rather than a new axiom:
+///
+/// ```toml
+/// threshold = 2.0
+/// heal = "above"
+///
+/// [axioms.Safety]
+/// weight = 2.5
+/// learning_rate = 0.05
+/// strategies = ["excise_sentence", "rollback"]
+///
+/// [axioms.Privacy]
+/// custom = true
+/// weight = 1.5
+/// strategies = ["replace_sentence:[{axiom} removed]"]
+///
+/// [axioms.Privacy.severity_multipliers]
+/// critical = 12.0
+///
+/// [[rules]]
+/// pattern = "ssn"
+/// axiom = "Privacy"
+/// severity = "critical"
+/// ```
+///
+/// Only as much TOML as policies need is read: tables, arrays of tables, comments,
+/// and keys set to strings, numbers, booleans or single-line arrays of them. Every
+/// error names the key at fault, as `axioms.Safety.weight` or `rules[0].severity`,
+/// with its line.
+pub mod policy {
+    use super::{fmt, AdaptiveAxiomaticRegularizer, Axiom, AxiomaticSelfHealer};
+    use super::{CorrectionStrategy, DetectionRule, DetectionTier, Severity};
+    use super::{SeverityMultipliers, ThresholdPolicy};
+    use std::path::Path;
+
+    /// A policy key that is malformed, unknown or out of range
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PolicyError {
+        /// Dotted path of the key, e.g. `axioms.Safety.weight`; empty for a line that
+        /// doesn't parse at all
+        pub key: String,
+        /// 1-based line in the policy source
+        pub line: usize,
+        pub message: String,
+    }
+
+    impl fmt::Display for PolicyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.key.is_empty() {
+                true => write!(f, "line {}: {}", self.line, self.message),
+                false => write!(f, "line {}: {}: {}", self.line, self.key, self.message),
+            }
+        }
+    }
+
+    impl std::error::Error for PolicyError {}
+
+    /// Why `AxiomaticSelfHealer::from_policy_file` failed
+    #[derive(Debug)]
+    pub enum PolicyFileError {
+        Io(std::io::Error),
+        Invalid(PolicyError),
+    }
+
+    impl fmt::Display for PolicyFileError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                PolicyFileError::Io(e) => write!(f, "Policy I/O error: {}", e),
+                PolicyFileError::Invalid(e) => write!(f, "Invalid policy: {}", e),
+            }
+        }
+    }
+
+    impl std::error::Error for PolicyFileError {}
+
+    impl From<std::io::Error> for PolicyFileError {
+        fn from(e: std::io::Error) -> Self {
+            PolicyFileError::Io(e)
+        }
+    }
+
+    impl From<PolicyError> for PolicyFileError {
+        fn from(e: PolicyError) -> Self {
+            PolicyFileError::Invalid(e)
+        }
+    }
+
+    /// What a policy sets for one axiom; anything left `None` keeps its current value
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct AxiomPolicy {
+        pub axiom: Axiom,
+        pub weight: Option<f64>,
+        pub learning_rate: Option<f64>,
+        pub severity_multipliers: Option<SeverityMultipliers>,
+        pub strategies: Option<Vec<CorrectionStrategy>>,
+    }
+
+    /// A validated policy, applied with `AxiomaticSelfHealer::apply_policy`
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct Policy {
+        pub learning_rate: Option<f64>,
+        pub threshold: Option<f64>,
+        pub threshold_policy: Option<ThresholdPolicy>,
+        pub severity_multipliers: Option<SeverityMultipliers>,
+        /// In file order
+        pub axioms: Vec<AxiomPolicy>,
+        pub rules: Vec<DetectionRule>,
+    }
+
+    impl Policy {
+        pub fn parse(source: &str) -> Result<Self, PolicyError> {
+            let root = parse_document(source)?;
+            let mut policy = Policy::default();
+            let mut heal = None;
+            for (key, entry) in &root.entries {
+                let at = Key::root(key, entry.line);
+                match key.as_str() {
+                    "learning_rate" => {
+                        let rate = at.number(&entry.value)?;
+                        if !(rate > 0.0 && rate <= 1.0) {
+                            return Err(at.error("must be in (0, 1]"));
+                        }
+                        policy.learning_rate = Some(rate);
+                    }
+                    "threshold" => policy.threshold = Some(at.non_negative(&entry.value)?),
+                    "heal" => heal = Some((at.text(&entry.value)?, at)),
+                    "severity_multipliers" => {
+                        let base = SeverityMultipliers::default();
+                        let table = at.table(&entry.value)?;
+                        policy.severity_multipliers = Some(multipliers(&at, table, base)?);
+                    }
+                    "axioms" => {
+                        for (name, entry) in &at.table(&entry.value)?.entries {
+                            let at = at.child(name, entry.line);
+                            let base = policy.severity_multipliers.unwrap_or_default();
+                            policy.axioms.push(axiom_policy(&at, name, &entry.value, base)?);
+                        }
+                    }
+                    "rules" => {
+                        let Value::Array(rules) = &entry.value else {
+                            return Err(at.error("expected [[rules]] tables"));
+                        };
+                        for (index, rule) in rules.iter().enumerate() {
+                            let at = at.index(index, line_of(rule, entry.line));
+                            policy.rules.push(detection_rule(&at, rule, &policy.axioms)?);
+                        }
+                    }
+                    _ => return Err(at.error("unknown key")),
+                }
+            }
+            if let Some((mode, at)) = heal {
+                let value = policy.threshold.unwrap_or(DEFAULT_THRESHOLD);
+                policy.threshold_policy = Some(match mode {
+                    "above" => ThresholdPolicy::HealAbove(value),
+                    "at_or_above" => ThresholdPolicy::HealAtOrAbove(value),
+                    "any" => ThresholdPolicy::HealOnAnyViolation,
+                    "never" => ThresholdPolicy::Never,
+                    _ => return Err(at.error("expected above, at_or_above, any or never")),
+                });
+            }
+            Ok(policy)
+        }
+    }
+
+    /// What `heal = "above"` compares against when the policy sets no threshold
+    const DEFAULT_THRESHOLD: f64 = 0.5;
+
+    fn axiom_policy(
+        at: &Key,
+        name: &str,
+        value: &Value,
+        base: SeverityMultipliers,
+    ) -> Result<AxiomPolicy, PolicyError> {
+        let table = at.table(value)?;
+        let custom = table.get("custom");
+        let custom = custom.map(|entry| (at.child("custom", entry.line), &entry.value));
+        let is_custom = match &custom {
+            Some((key, value)) => key.flag(value)?,
+            None => false,
+        };
+        let axiom = match Axiom::ALL.iter().find(|axiom| axiom.name() == name) {
+            Some(_) if is_custom => {
+                let key = custom.as_ref().map_or(at, |(key, _)| key);
+                return Err(key.error("a built-in axiom can't be custom"));
+            }
+            Some(axiom) => axiom.clone(),
+            None if is_custom => Axiom::custom(name),
+            None => {
+                return Err(at.error(&format!(
+                    "unknown axiom{}; set `custom = true` to define a custom one",
+                    suggestion(name)
+                )))
+            }
+        };
+        let mut policy = AxiomPolicy {
+            axiom,
+            weight: None,
+            learning_rate: None,
+            severity_multipliers: None,
+            strategies: None,
+        };
+        for (key, entry) in &table.entries {
+            let at = at.child(key, entry.line);
+            match key.as_str() {
+                "custom" => {}
+                "weight" => {
+                    let weight = at.number(&entry.value)?;
+                    if !(0.1..=10.0).contains(&weight) {
+                        return Err(at.error("must be between 0.1 and 10"));
+                    }
+                    policy.weight = Some(weight);
+                }
+                "learning_rate" => {
+                    let rate = at.number(&entry.value)?;
+                    if !(rate > 0.0 && rate <= 1.0) {
+                        return Err(at.error("must be in (0, 1]"));
+                    }
+                    policy.learning_rate = Some(rate);
+                }
+                "severity_multipliers" => {
+                    let table = at.table(&entry.value)?;
+                    policy.severity_multipliers = Some(multipliers(&at, table, base)?);
+                }
+                "strategies" => {
+                    let Value::Array(names) = &entry.value else {
+                        return Err(at.error("expected an array of strategy names"));
+                    };
+                    let chain = names
+                        .iter()
+                        .map(|name| strategy(&at, at.text(name)?))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    policy.strategies = Some(chain);
+                }
+                _ => return Err(at.error("unknown key")),
+            }
+        }
+        Ok(policy)
+    }
+
+    fn strategy(at: &Key, name: &str) -> Result<CorrectionStrategy, PolicyError> {
+        if let Some(template) = name.strip_prefix("replace_sentence:") {
+            return Ok(CorrectionStrategy::ReplaceSentence { template: template.to_string() });
+        }
+        CorrectionStrategy::from_name(name)
+            .ok_or_else(|| at.error(&format!("unknown strategy '{}'", name)))
+    }
+
+    fn multipliers(
+        at: &Key,
+        table: &Table,
+        base: SeverityMultipliers,
+    ) -> Result<SeverityMultipliers, PolicyError> {
+        let mut multipliers = base;
+        for (key, entry) in &table.entries {
+            let at = at.child(key, entry.line);
+            let value = at.non_negative(&entry.value)?;
+            match severity(key) {
+                Some(Severity::Low) => multipliers.low = value,
+                Some(Severity::Medium) => multipliers.medium = value,
+                Some(Severity::High) => multipliers.high = value,
+                Some(Severity::Critical) => multipliers.critical = value,
+                None => return Err(at.error("expected low, medium, high or critical")),
+            }
+        }
+        Ok(multipliers)
+    }
+
+    fn detection_rule(
+        at: &Key,
+        value: &Value,
+        axioms: &[AxiomPolicy],
+    ) -> Result<DetectionRule, PolicyError> {
+        let table = at.table(value)?;
+        let field = |name: &str| {
+            let entry = table.get(name).ok_or_else(|| at.child(name, at.line).error("missing"))?;
+            Ok::<_, PolicyError>((at.child(name, entry.line), &entry.value))
+        };
+        let (key, pattern) = field("pattern")?;
+        let pattern = key.text(pattern)?;
+        if pattern.is_empty() {
+            return Err(key.error("must not be empty"));
+        }
+        let (key, axiom) = field("axiom")?;
+        let name = key.text(axiom)?;
+        let axiom = Axiom::ALL
+            .iter()
+            .chain(axioms.iter().map(|policy| &policy.axiom))
+            .find(|axiom| axiom.name() == name)
+            .cloned()
+            .ok_or_else(|| {
+                let hint = suggestion(name);
+                key.error(&format!("axiom '{}' is not built in or declared{}", name, hint))
+            })?;
+        let (key, level) = field("severity")?;
+        let level = key.text(level)?;
+        let severity = severity(level).ok_or_else(|| key.error("unknown severity"))?;
+
+        let mut rule = DetectionRule::new(pattern, axiom, severity);
+        for (name, entry) in &table.entries {
+            let key = at.child(name, entry.line);
+            match name.as_str() {
+                "pattern" | "axiom" | "severity" => {}
+                "tier" => {
+                    rule = match key.text(&entry.value)? {
+                        "fast" => rule.with_tier(DetectionTier::Fast),
+                        "thorough" => rule.with_tier(DetectionTier::Thorough),
+                        _ => return Err(key.error("expected fast or thorough")),
+                    }
+                }
+                "severity_expr" => {
+                    rule = rule
+                        .with_severity_expr(key.text(&entry.value)?)
+                        .map_err(|e| key.error(&e.to_string()))?;
+                }
+                _ => return Err(key.error("unknown key")),
+            }
+        }
+        Ok(rule)
+    }
+
+    /// ` (did you mean '<name>'?)` for the built-in axiom `name` is probably a typo of
+    fn suggestion(name: &str) -> String {
+        let name = name.to_lowercase();
+        Axiom::ALL
+            .iter()
+            .map(|axiom| (edit_distance(&name, &axiom.name().to_lowercase()), axiom.name()))
+            .filter(|(distance, _)| *distance <= 2)
+            .min()
+            .map(|(_, closest)| format!(" (did you mean '{}'?)", closest))
+            .unwrap_or_default()
+    }
+
+    /// Levenshtein distance, counting a swap of adjacent characters as one edit
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+        let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+        for i in 1..=a.len() {
+            let mut row = vec![i; b.len() + 1];
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                row[j] = (rows[i - 1][j] + 1).min(row[j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+                }
+            }
+            rows.push(row);
+        }
+        rows[a.len()][b.len()]
+    }
+
+    fn severity(name: &str) -> Option<Severity> {
+        match name {
+            "low" | "Low" => Some(Severity::Low),
+            "medium" | "Medium" => Some(Severity::Medium),
+            "high" | "High" => Some(Severity::High),
+            "critical" | "Critical" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    /// Where a value sits in the document, for errors
+    struct Key {
+        path: String,
+        line: usize,
+    }
+
+    impl Key {
+        fn root(name: &str, line: usize) -> Self {
+            Self { path: name.to_string(), line }
+        }
+
+        fn child(&self, name: &str, line: usize) -> Self {
+            Self { path: format!("{}.{}", self.path, name), line }
+        }
+
+        fn index(&self, index: usize, line: usize) -> Self {
+            Self { path: format!("{}[{}]", self.path, index), line }
+        }
+
+        fn error(&self, message: &str) -> PolicyError {
+            PolicyError { key: self.path.clone(), line: self.line, message: message.to_string() }
+        }
+
+        fn number(&self, value: &Value) -> Result<f64, PolicyError> {
+            match value {
+                Value::Number(n) if n.is_finite() => Ok(*n),
+                _ => Err(self.error("expected a number")),
+            }
+        }
+
+        fn non_negative(&self, value: &Value) -> Result<f64, PolicyError> {
+            let n = self.number(value)?;
+            if n < 0.0 {
+                return Err(self.error("must not be negative"));
+            }
+            Ok(n)
+        }
+
+        fn flag(&self, value: &Value) -> Result<bool, PolicyError> {
+            match value {
+                Value::Bool(flag) => Ok(*flag),
+                _ => Err(self.error("expected true or false")),
+            }
+        }
+
+        fn text<'v>(&self, value: &'v Value) -> Result<&'v str, PolicyError> {
+            match value {
+                Value::Text(text) => Ok(text),
+                _ => Err(self.error("expected a string")),
+            }
+        }
+
+        fn table<'v>(&self, value: &'v Value) -> Result<&'v Table, PolicyError> {
+            match value {
+                Value::Table(table) => Ok(table),
+                _ => Err(self.error("expected a table")),
+            }
+        }
+    }
+
+    fn line_of(value: &Value, fallback: usize) -> usize {
+        match value {
+            Value::Table(table) => table.line,
+            _ => fallback,
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Value {
+        Text(String),
+        Number(f64),
+        Bool(bool),
+        Array(Vec<Value>),
+        Table(Table),
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Table {
+        /// In file order
+        entries: Vec<(String, Entry)>,
+        /// Line of the header that opened the table
+        line: usize,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Entry {
+        value: Value,
+        line: usize,
+    }
+
+    impl Table {
+        fn get(&self, key: &str) -> Option<&Entry> {
+            self.entries.iter().find(|(k, _)| k == key).map(|(_, entry)| entry)
+        }
+
+        fn get_mut(&mut self, key: &str) -> Option<&mut Entry> {
+            self.entries.iter_mut().find(|(k, _)| k == key).map(|(_, entry)| entry)
+        }
+
+        fn insert(&mut self, key: String, value: Value, line: usize) -> Result<(), String> {
+            if self.get(&key).is_some() {
+                return Err(format!("'{}' is set twice", key));
+            }
+            self.entries.push((key, Entry { value, line }));
+            Ok(())
+        }
+
+        /// The table at `path`, creating missing ones; an array of tables resolves to
+        /// its last element
+        fn descend(&mut self, path: &[String], line: usize) -> Result<&mut Table, String> {
+            let Some((first, rest)) = path.split_first() else {
+                return Ok(self);
+            };
+            if self.get(first).is_none() {
+                let table = Table { entries: Vec::new(), line };
+                self.entries.push((first.clone(), Entry { value: Value::Table(table), line }));
+            }
+            let entry = self.get_mut(first).expect("inserted above");
+            match &mut entry.value {
+                Value::Table(table) => table.descend(rest, line),
+                Value::Array(items) => match items.last_mut() {
+                    Some(Value::Table(table)) => table.descend(rest, line),
+                    _ => Err(format!("'{}' is not a table", first)),
+                },
+                _ => Err(format!("'{}' is not a table", first)),
+            }
+        }
+    }
+
+    fn parse_document(source: &str) -> Result<Table, PolicyError> {
+        let mut root = Table::default();
+        let mut current: Vec<String> = Vec::new();
+        let mut opened: Vec<Vec<String>> = Vec::new();
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            let syntax = |message: String| PolicyError { key: String::new(), line, message };
+            let mut cursor = Cursor { text: raw, pos: 0 };
+            cursor.skip_space();
+            if cursor.at_end() {
+                continue;
+            }
+            if cursor.eat("[[") {
+                let path = cursor.key_path().map_err(syntax)?;
+                cursor.expect("]]").and_then(|_| cursor.finish()).map_err(syntax)?;
+                let (name, parent) = path.split_last().expect("key_path is never empty");
+                let table = root.descend(parent, line).map_err(syntax)?;
+                let fresh = Value::Table(Table { entries: Vec::new(), line });
+                match table.get_mut(name) {
+                    None => table.insert(name.clone(), Value::Array(vec![fresh]), line),
+                    Some(Entry { value: Value::Array(items), .. }) => {
+                        items.push(fresh);
+                        Ok(())
+                    }
+                    Some(_) => Err(format!("'{}' is not an array of tables", name)),
+                }
+                .map_err(syntax)?;
+                current = path;
+            } else if cursor.eat("[") {
+                let path = cursor.key_path().map_err(syntax)?;
+                cursor.expect("]").and_then(|_| cursor.finish()).map_err(syntax)?;
+                if opened.contains(&path) {
+                    return Err(syntax(format!("table [{}] is defined twice", path.join("."))));
+                }
+                root.descend(&path, line).map_err(syntax)?;
+                opened.push(path.clone());
+                current = path;
+            } else {
+                let key = cursor.key().map_err(syntax)?;
+                cursor.skip_space();
+                cursor.expect("=").map_err(syntax)?;
+                let value = cursor.value().map_err(syntax)?;
+                cursor.finish().map_err(syntax)?;
+                let table = root.descend(&current, line).map_err(syntax)?;
+                table.insert(key, value, line).map_err(syntax)?;
+            }
+        }
+        Ok(root)
+    }
+
+    struct Cursor<'a> {
+        text: &'a str,
+        pos: usize,
+    }
+
+    impl<'a> Cursor<'a> {
+        fn rest(&self) -> &'a str {
+            &self.text[self.pos..]
+        }
+
+        fn at_end(&self) -> bool {
+            self.rest().is_empty() || self.rest().starts_with('#')
+        }
+
+        fn skip_space(&mut self) {
+            let rest = self.rest();
+            self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        }
+
+        fn eat(&mut self, token: &str) -> bool {
+            let found = self.rest().starts_with(token);
+            if found {
+                self.pos += token.len();
+            }
+            found
+        }
+
+        fn expect(&mut self, token: &str) -> Result<(), String> {
+            self.skip_space();
+            match self.eat(token) {
+                true => Ok(()),
+                false => Err(format!("expected '{}' at column {}", token, self.pos + 1)),
+            }
+        }
+
+        /// Only whitespace or a comment may follow
+        fn finish(&mut self) -> Result<(), String> {
+            self.skip_space();
+            match self.at_end() {
+                true => Ok(()),
+                false => Err(format!("unexpected text at column {}", self.pos + 1)),
+            }
+        }
+
+        fn key(&mut self) -> Result<String, String> {
+            self.skip_space();
+            if self.rest().starts_with('"') {
+                return self.string();
+            }
+            let rest = self.rest();
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(format!("expected a key at column {}", self.pos + 1));
+            }
+            self.pos += len;
+            Ok(rest[..len].to_string())
+        }
+
+        fn key_path(&mut self) -> Result<Vec<String>, String> {
+            let mut path = vec![self.key()?];
+            loop {
+                self.skip_space();
+                if !self.eat(".") {
+                    return Ok(path);
+                }
+                path.push(self.key()?);
+            }
+        }
+
+        fn value(&mut self) -> Result<Value, String> {
+            self.skip_space();
+            let column = self.pos + 1;
+            let rest = self.rest();
+            if rest.starts_with('"') {
+                return self.string().map(Value::Text);
+            }
+            if self.eat("[") {
+                let mut items = Vec::new();
+                loop {
+                    self.skip_space();
+                    if self.eat("]") {
+                        return Ok(Value::Array(items));
+                    }
+                    items.push(self.value()?);
+                    self.skip_space();
+                    if !self.eat(",") {
+                        self.expect("]")?;
+                        return Ok(Value::Array(items));
+                    }
+                }
+            }
+            let len = rest.find([',', ']', ' ', '\t', '#']).unwrap_or(rest.len());
+            let word = &rest[..len];
+            let value = match word {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => match word.replace('_', "").parse::<f64>() {
+                    Ok(n) if !word.is_empty() => Value::Number(n),
+                    _ => return Err(format!("expected a value at column {}", column)),
+                },
+            };
+            self.pos += len;
+            Ok(value)
+        }
+
+        /// A basic `"..."` string with TOML's escapes
+        fn string(&mut self) -> Result<String, String> {
+            let start = self.pos + 1;
+            let mut chars = self.rest().char_indices().skip(1);
+            let mut out = String::new();
+            while let Some((offset, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        self.pos += offset + 1;
+                        return Ok(out);
+                    }
+                    '\\' => match chars.next().map(|(_, c)| c) {
+                        Some('"') => out.push('"'),
+                        Some('\\') => out.push('\\'),
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        Some('u') => {
+                            let digits: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                            let c = u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32);
+                            out.push(c.ok_or("bad \\u escape")?);
+                        }
+                        _ => return Err(format!("bad escape in string at column {}", start)),
+                    },
+                    c => out.push(c),
+                }
+            }
+            Err(format!("unterminated string at column {}", start))
+        }
+    }
+
+    impl AdaptiveAxiomaticRegularizer {
+        /// Apply the regularizer's part of `policy`: learning rate, threshold,
+        /// multipliers, weights and rules
+        pub fn apply_policy(&mut self, policy: &Policy) {
+            if let Some(rate) = policy.learning_rate {
+                self.learning_rate = rate;
+            }
+            if let Some(threshold) = policy.threshold {
+                self.threshold = threshold;
+            }
+            if let Some(multipliers) = policy.severity_multipliers {
+                self.set_severity_multipliers(multipliers);
+            }
+            for axiom in &policy.axioms {
+                if let Axiom::Custom(name) = &axiom.axiom {
+                    let weight = axiom.weight.unwrap_or(Self::UNREGISTERED_AXIOM_WEIGHT);
+                    self.register_axiom(name.clone(), weight);
+                } else if let Some(weight) = axiom.weight {
+                    self.axiom_weights.insert(axiom.axiom.clone(), weight);
+                    self.pinned_since.remove(&axiom.axiom);
+                }
+                if axiom.learning_rate.is_some() {
+                    self.set_axiom_learning_rate(axiom.axiom.clone(), axiom.learning_rate);
+                }
+                if axiom.severity_multipliers.is_some() {
+                    let multipliers = axiom.severity_multipliers;
+                    self.set_axiom_severity_multipliers(axiom.axiom.clone(), multipliers);
+                }
+            }
+            for rule in &policy.rules {
+                self.add_rule(rule.clone());
+            }
+        }
+    }
+
+    impl AxiomaticSelfHealer {
+        /// A healer on the default regularizer with `policy` applied
+        pub fn from_policy(policy: &Policy) -> Self {
+            let mut healer = Self::new(AdaptiveAxiomaticRegularizer::new());
+            healer.apply_policy(policy);
+            healer
+        }
+
+        /// Read, validate and apply a TOML policy file; see the `policy` module
+        pub fn from_policy_file(path: impl AsRef<Path>) -> Result<Self, PolicyFileError> {
+            let source = std::fs::read_to_string(path)?;
+            Ok(Self::from_policy(&Policy::parse(&source)?))
+        }
+
+        /// Apply `policy` over the current configuration. A threshold without `heal`
+        /// moves the value of a `HealAbove` or `HealAtOrAbove` policy.
+        pub fn apply_policy(&mut self, policy: &Policy) {
+            self.regularizer.apply_policy(policy);
+            for axiom in &policy.axioms {
+                if let Some(chain) = &axiom.strategies {
+                    self.set_strategies(axiom.axiom.clone(), chain.clone());
+                }
+            }
+            self.threshold_policy = match (policy.threshold_policy, policy.threshold) {
+                (Some(threshold_policy), _) => threshold_policy,
+                (None, Some(value)) => match self.threshold_policy {
+                    ThresholdPolicy::HealAbove(_) => ThresholdPolicy::HealAbove(value),
+                    ThresholdPolicy::HealAtOrAbove(_) => ThresholdPolicy::HealAtOrAbove(value),
+                    other => other,
+                },
+                (None, None) => self.threshold_policy,
+            };
+        }
+    }
+}
+
+/// Escalation of critical or repeated violations.
+///
+/// An `EscalationRule` fires once `count` matching violations have been detected
+/// within its `window`, then stays quiet for its `cooldown`. Each firing builds an
+/// `EscalationAlert`, applies the rule's actions and hands the alert to every
+/// `AlertSink`. The actions can switch the healer into a fallback mode, where
+/// `auto_heal` is off or contexts with violations are answered with fixed text, until
+/// `AxiomaticSelfHealer::clear_escalation` is called.
+///
+/// A sink that fails is reported as `HealerEvent::AlertFailed`; the alert is not
+/// retried.
+pub mod escalation {
+    use super::import::{json_string, violation_to_json};
+    use super::{schema, Axiom, AxiomaticSelfHealer, HealReport, HealerEvent};
+    use super::{Severity, Violation};
+    use std::collections::VecDeque;
+    use std::io::{self, Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::sync::mpsc::Sender;
+    use std::time::Duration;
+
+    /// What the healer does when a rule fires
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum EscalationAction {
+        /// Report the alert at this severity rather than the highest one that matched
+        RaiseSeverity(Severity),
+        /// Turn `auto_heal` off until `clear_escalation`
+        DisableAutoHeal,
+        /// Answer every context with violations with this text until
+        /// `clear_escalation`
+        Fallback(String),
+    }
+
+    impl EscalationAction {
+        fn label(&self) -> String {
+            match self {
+                Self::RaiseSeverity(severity) => format!("raise_severity:{:?}", severity),
+                Self::DisableAutoHeal => "disable_auto_heal".to_string(),
+                Self::Fallback(_) => "fallback".to_string(),
+            }
+        }
+    }
+
+    /// Fire once `count` matching violations are detected within `window`
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct EscalationRule {
+        /// Identifies the rule in alerts; unique per healer
+        pub name: String,
+        /// `None` matches every axiom
+        pub axiom: Option<Axiom>,
+        pub min_severity: Severity,
+        pub count: usize,
+        pub window: Duration,
+        /// How long the rule stays quiet after firing
+        pub cooldown: Duration,
+        pub actions: Vec<EscalationAction>,
+    }
+
+    impl EscalationRule {
+        /// A rule matching violations of any axiom and severity, with no cooldown and
+        /// no actions beyond alerting
+        pub fn new(name: impl Into<String>, count: usize, window: Duration) -> Self {
+            Self {
+                name: name.into(),
+                axiom: None,
+                min_severity: Severity::Low,
+                count: count.max(1),
+                window,
+                cooldown: Duration::ZERO,
+                actions: Vec::new(),
+            }
+        }
+
+        pub fn with_axiom(mut self, axiom: Axiom) -> Self {
+            self.axiom = Some(axiom);
+            self
+        }
+
+        pub fn with_min_severity(mut self, severity: Severity) -> Self {
+            self.min_severity = severity;
+            self
+        }
+
+        pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+            self.cooldown = cooldown;
+            self
+        }
+
+        pub fn with_action(mut self, action: EscalationAction) -> Self {
+            self.actions.push(action);
+            self
+        }
+
+        fn matches(&self, violation: &Violation) -> bool {
+            violation.severity >= self.min_severity
+                && self.axiom.as_ref().is_none_or(|axiom| *axiom == violation.axiom)
+        }
+    }
+
+    /// Raised each time an `EscalationRule` fires
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct EscalationAlert {
+        pub rule: String,
+        /// The rule's axiom, or that of the violation that made it fire
+        pub axiom: Axiom,
+        /// Raised by `EscalationAction::RaiseSeverity`, otherwise the highest matched
+        pub severity: Severity,
+        /// The violations that made up the count, oldest first
+        pub violations: Vec<Violation>,
+        pub actions: Vec<EscalationAction>,
+        pub timestamp: u64,
+    }
+
+    impl EscalationAlert {
+        /// Serialize as one line of `schema::ALERT` JSON. Fallback text is left out.
+        pub fn to_json(&self) -> String {
+            let actions: Vec<String> =
+                self.actions.iter().map(|action| json_string(&action.label())).collect();
+            let violations: Vec<String> = self.violations.iter().map(violation_to_json).collect();
+            format!(
+                "{{\"schema\":{},\"rule\":{},\"axiom\":{},\"severity\":{},\"timestamp\":{},\
+                 \"count\":{},\"actions\":[{}],\"violations\":[{}]}}",
+                json_string(&schema::ALERT.to_string()),
+                json_string(&self.rule),
+                json_string(&format!("{:?}", self.axiom)),
+                json_string(&format!("{:?}", self.severity)),
+                self.timestamp,
+                self.violations.len(),
+                actions.join(","),
+                violations.join(",")
+            )
+        }
+    }
+
+    /// Destination for escalation alerts, called as soon as a rule fires
+    pub trait AlertSink: Send {
+        fn name(&self) -> &str;
+        fn alert(&mut self, alert: &EscalationAlert) -> io::Result<()>;
+    }
+
+    /// Sends alerts down a channel; fails once the receiver is gone
+    pub struct ChannelAlertSink {
+        name: String,
+        sender: Sender<EscalationAlert>,
+    }
+
+    impl ChannelAlertSink {
+        pub fn new(name: impl Into<String>, sender: Sender<EscalationAlert>) -> Self {
+            Self { name: name.into(), sender }
+        }
+    }
+
+    impl AlertSink for ChannelAlertSink {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn alert(&mut self, alert: &EscalationAlert) -> io::Result<()> {
+            self.sender
+                .send(alert.clone())
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "receiver dropped"))
+        }
+    }
+
+    /// Calls a closure with each alert
+    pub struct CallbackAlertSink<F> {
+        name: String,
+        callback: F,
+    }
+
+    impl<F: FnMut(&EscalationAlert) + Send> CallbackAlertSink<F> {
+        pub fn new(name: impl Into<String>, callback: F) -> Self {
+            Self { name: name.into(), callback }
+        }
+    }
+
+    impl<F: FnMut(&EscalationAlert) + Send> AlertSink for CallbackAlertSink<F> {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn alert(&mut self, alert: &EscalationAlert) -> io::Result<()> {
+            (self.callback)(alert);
+            Ok(())
+        }
+    }
+
+    /// POSTs each alert's JSON to a plain `http://` endpoint.
+    ///
+    /// Each alert opens its own connection. Anything but a 2xx status is an error.
+    /// TLS is not supported; put a local relay in front of an `https://` endpoint.
+    pub struct WebhookAlertSink {
+        name: String,
+        host: String,
+        port: u16,
+        path: String,
+        timeout: Duration,
+    }
+
+    impl WebhookAlertSink {
+        pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+        /// Fails with `InvalidInput` unless `url` is `http://host[:port][/path]`
+        pub fn new(name: impl Into<String>, url: &str) -> io::Result<Self> {
+            let invalid = |reason: &str| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {}", reason, url))
+            };
+            let rest = url.strip_prefix("http://").ok_or_else(|| invalid("not an http URL"))?;
+            let (authority, path) = match rest.find('/') {
+                Some(at) => (&rest[..at], &rest[at..]),
+                None => (rest, "/"),
+            };
+            let (host, port) = match authority.rsplit_once(':') {
+                Some((host, port)) => (host, port.parse().map_err(|_| invalid("bad port"))?),
+                None => (authority, 80),
+            };
+            if host.is_empty() {
+                return Err(invalid("missing host"));
+            }
+            Ok(Self {
+                name: name.into(),
+                host: host.to_string(),
+                port,
+                path: path.to_string(),
+                timeout: Self::DEFAULT_TIMEOUT,
+            })
+        }
+
+        /// Bound on connecting, and on each read and write
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+
+        fn connect(&self) -> io::Result<TcpStream> {
+            let mut last = None;
+            for addr in (self.host.as_str(), self.port).to_socket_addrs()? {
+                match TcpStream::connect_timeout(&addr, self.timeout) {
+                    Ok(stream) => return Ok(stream),
+                    Err(err) => last = Some(err),
+                }
+            }
+            Err(last.unwrap_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("{} did not resolve", self.host))
+            }))
+        }
+    }
+
+    impl AlertSink for WebhookAlertSink {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn alert(&mut self, alert: &EscalationAlert) -> io::Result<()> {
+            let body = alert.to_json();
+            let mut stream = self.connect()?;
+            stream.set_read_timeout(Some(self.timeout))?;
+            stream.set_write_timeout(Some(self.timeout))?;
+            write!(
+                stream,
+                "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                self.path,
+                self.host,
+                self.port,
+                body.len(),
+                body
+            )?;
+            stream.flush()?;
+            let mut head = [0u8; 64];
+            let mut read = 0;
+            while read < head.len() && !head[..read].contains(&b'\n') {
+                match stream.read(&mut head[read..])? {
+                    0 => break,
+                    n => read += n,
+                }
+            }
+            let status_line = String::from_utf8_lossy(&head[..read]);
+            let status = status_line.split_whitespace().nth(1).unwrap_or("");
+            if status.len() == 3 && status.starts_with('2') {
+                Ok(())
+            } else {
+                let line = status_line.lines().next().unwrap_or("").trim();
+                Err(io::Error::other(format!("webhook answered {:?}", line)))
+            }
+        }
+    }
+
+    struct RuleState {
+        rule: EscalationRule,
+        /// Matching violations within the window, oldest first
+        recent: VecDeque<Violation>,
+        last_fired: Option<u64>,
+    }
+
+    /// Rules, sinks and fallback mode for one healer
+    #[derive(Default)]
+    pub(super) struct Escalation {
+        rules: Vec<RuleState>,
+        sinks: Vec<Box<dyn AlertSink>>,
+        fallback: Option<String>,
+        /// `auto_heal` as it was before a rule turned it off
+        suspended_auto_heal: Option<bool>,
+    }
+
+    impl AxiomaticSelfHealer {
+        /// Add `rule`, replacing any rule with the same name along with its window and
+        /// cooldown
+        pub fn add_escalation_rule(&mut self, rule: EscalationRule) {
+            self.escalation.rules.retain(|state| state.rule.name != rule.name);
+            self.escalation.rules.push(RuleState {
+                rule,
+                recent: VecDeque::new(),
+                last_fired: None,
+            });
+        }
+
+        /// Whether a rule named `name` was removed
+        pub fn remove_escalation_rule(&mut self, name: &str) -> bool {
+            let before = self.escalation.rules.len();
+            self.escalation.rules.retain(|state| state.rule.name != name);
+            self.escalation.rules.len() != before
+        }
+
+        pub fn escalation_rules(&self) -> impl Iterator<Item = &EscalationRule> {
+            self.escalation.rules.iter().map(|state| &state.rule)
+        }
+
+        pub fn add_alert_sink(&mut self, sink: impl AlertSink + 'static) {
+            self.escalation.sinks.push(Box::new(sink));
+        }
+
+        /// The text contexts with violations are answered with, while a rule's
+        /// `EscalationAction::Fallback` is in effect
+        pub fn escalation_fallback(&self) -> Option<&str> {
+            self.escalation.fallback.as_deref()
+        }
+
+        /// Leave fallback mode: drop the fallback text and turn `auto_heal` back on if
+        /// a rule turned it off. Rule windows and cooldowns are kept.
+        pub fn clear_escalation(&mut self) {
+            self.escalation.fallback = None;
+            if let Some(auto_heal) = self.escalation.suspended_auto_heal.take() {
+                self.auto_heal = auto_heal;
+            }
+        }
+
+        /// Count `report`'s violations against every rule, fire the rules that are due,
+        /// and apply fallback text to `report`
+        pub(super) fn escalate(&mut self, report: &mut HealReport) {
+            let now = self.regularizer.current_timestamp();
+            let mut alerts = Vec::new();
+            for state in &mut self.escalation.rules {
+                let rule = &state.rule;
+                let matching = report.violations.iter().filter(|v| rule.matches(v));
+                state.recent.extend(matching.cloned());
+                let since = now.saturating_sub(rule.window.as_millis() as u64);
+                while state.recent.front().is_some_and(|v| v.timestamp < since) {
+                    state.recent.pop_front();
+                }
+                let cooled = state.last_fired.is_none_or(|fired| {
+                    now.saturating_sub(fired) >= rule.cooldown.as_millis() as u64
+                });
+                if state.recent.len() < rule.count.max(1) || !cooled {
+                    continue;
+                }
+                state.last_fired = Some(now);
+                let violations: Vec<Violation> = state.recent.drain(..).collect();
+                let highest = violations.iter().map(|v| v.severity).max();
+                let mut severity = highest.unwrap_or(rule.min_severity);
+                for action in &rule.actions {
+                    if let EscalationAction::RaiseSeverity(raised) = action {
+                        severity = severity.max(*raised);
+                    }
+                }
+                alerts.push(EscalationAlert {
+                    rule: rule.name.clone(),
+                    axiom: match &rule.axiom {
+                        Some(axiom) => axiom.clone(),
+                        None => violations[violations.len() - 1].axiom.clone(),
+                    },
+                    severity,
+                    violations,
+                    actions: rule.actions.clone(),
+                    timestamp: now,
+                });
+            }
+
+            for alert in &alerts {
+                for action in &alert.actions {
+                    match action {
+                        EscalationAction::RaiseSeverity(_) => {}
+                        EscalationAction::DisableAutoHeal => {
+                            if self.escalation.suspended_auto_heal.is_none() {
+                                self.escalation.suspended_auto_heal = Some(self.auto_heal);
+                            }
+                            self.auto_heal = false;
+                        }
+                        EscalationAction::Fallback(text) => {
+                            self.escalation.fallback = Some(text.clone());
+                        }
+                    }
+                }
+                let mut failures = Vec::new();
+                for sink in &mut self.escalation.sinks {
+                    if let Err(err) = sink.alert(alert) {
+                        failures.push(HealerEvent::AlertFailed {
+                            sink: sink.name().to_string(),
+                            rule: alert.rule.clone(),
+                            reason: err.to_string(),
+                        });
+                    }
+                }
+                for failure in failures {
+                    self.push_event(failure);
+                }
+            }
+
+            if let Some(fallback) = &self.escalation.fallback {
+                if !report.violations.is_empty() {
+                    report.context = fallback.clone();
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Each thread's healers, keyed by the id of the pool that owns them
+    static POOL_LOCALS: RefCell<HashMap<u64, Arc<Mutex<AxiomaticSelfHealer>>>> =
+        RefCell::new(HashMap::new());
+}
+
+static NEXT_POOL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Coordinator-side state of a `ThreadLocalHealerPool`
+struct PoolAggregate {
+    weights: AdaptiveAxiomaticRegularizer,
+    statistics: ViolationStatistics,
+    strategy_stats: HashMap<String, StrategyStats>,
+}
+
+/// Per-thread healers for latency-critical paths, merged periodically by a coordinator.
+///
+/// Each thread lazily builds its own healer from the factory, so heal calls only lock a
+/// mutex no other thread uses except during `merge_now`. Axiom weights are owned by the
+/// coordinator: `update_weights` changes them on the pool and they are pushed to every
+/// local healer at the next merge, overwriting any local adjustments.
+pub struct ThreadLocalHealerPool {
+    id: u64,
+    factory: Box<dyn Fn() -> AxiomaticSelfHealer + Send + Sync>,
+    locals: Mutex<Vec<Arc<Mutex<AxiomaticSelfHealer>>>>,
+    aggregate: Mutex<PoolAggregate>,
+}
+
+impl ThreadLocalHealerPool {
+    /// `factory` builds each thread's healer; a first instance seeds the shared weights.
+    ///
+    /// The pool takes a factory rather than a healer to clone because a healer can't be
+    /// cloned: registered detectors, observers and the user query are boxed trait
+    /// objects, so each thread's copy has to be configured the way the first one was.
+    /// Whatever history and strategy statistics a built healer comes with are dropped,
+    /// so every thread starts empty.
+    pub fn new(factory: impl Fn() -> AxiomaticSelfHealer + Send + Sync + 'static) -> Self {
+        let mut template = factory();
+        Self {
+            id: NEXT_POOL_ID.fetch_add(1, Ordering::Relaxed),
+            factory: Box::new(factory),
+            locals: Mutex::new(Vec::new()),
+            aggregate: Mutex::new(PoolAggregate {
+                weights: std::mem::take(&mut template.regularizer),
+                statistics: ViolationStatistics::default(),
+                strategy_stats: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Run `f` with this thread's healer, creating it on first use
+    pub fn with_local<R>(&self, f: impl FnOnce(&mut AxiomaticSelfHealer) -> R) -> R {
+        let local = POOL_LOCALS.with(|locals| {
+            locals
+                .borrow_mut()
+                .entry(self.id)
+                .or_insert_with(|| self.register_local())
+                .clone()
+        });
+        let mut healer = local.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut healer)
+    }
+
+    fn register_local(&self) -> Arc<Mutex<AxiomaticSelfHealer>> {
+        let mut healer = (self.factory)();
+        healer.regularizer.drain_history();
+        healer.strategy_stats.clear();
+        if let Ok(aggregate) = self.aggregate.lock() {
+            healer.regularizer.axiom_weights = aggregate.weights.axiom_weights.clone();
+        }
+        let local = Arc::new(Mutex::new(healer));
+        if let Ok(mut locals) = self.locals.lock() {
+            locals.push(Arc::clone(&local));
+        }
+        local
+    }
+
+    /// Change a shared axiom weight; locals pick it up at the next merge
+    pub fn update_weights(&self, axiom: Axiom, feedback: f64) {
+        if let Ok(mut aggregate) = self.aggregate.lock() {
+            aggregate.weights.update_weights(axiom, feedback);
+        }
+    }
+
+    /// The coordinator's current weight for an axiom
+    pub fn weight(&self, axiom: &Axiom) -> Option<f64> {
+        self.aggregate.lock().ok().and_then(|a| a.weights.weight(axiom))
+    }
+
+    /// Drain every local history and strategy statistics into the aggregate, push the
+    /// coordinator's weights to all locals, and return the aggregate statistics
+    pub fn merge_now(&self) -> ViolationStatistics {
+        let mut aggregate = self.aggregate.lock().unwrap_or_else(|e| e.into_inner());
+        let mut locals = self.locals.lock().unwrap_or_else(|e| e.into_inner());
+
+        for local in locals.iter() {
+            let mut healer = local.lock().unwrap_or_else(|e| e.into_inner());
+            for violation in healer.regularizer.drain_history() {
+                aggregate.statistics.record(&violation);
+            }
+            for (name, stats) in std::mem::take(&mut healer.strategy_stats) {
+                aggregate.strategy_stats.entry(name).or_default().merge(&stats);
+            }
+            healer.regularizer.axiom_weights = aggregate.weights.axiom_weights.clone();
+        }
+        // Locals whose threads have exited are only referenced from here
+        locals.retain(|local| Arc::strong_count(local) > 1);
+
+        aggregate.statistics.clone()
+    }
+
+    /// Strategy statistics merged so far
+    pub fn strategy_statistics(&self) -> HashMap<String, StrategyStats> {
+        self.aggregate
+            .lock()
+            .map(|a| a.strategy_stats.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// How one shadowed call differed between the control and candidate healers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ABComparison {
+    /// Both healers made the same threshold decision
+    pub same_decision: bool,
+    /// Candidate's residual penalty minus the control's, scored by the control's regularizer
+    pub penalty_delta: f64,
+    /// The healed contexts differ
+    pub output_differs: bool,
+    /// Per axiom, violations the candidate healed minus those the control healed
+    pub per_axiom_deltas: BTreeMap<Axiom, i64>,
+}
+
+/// Comparison totals for one axiom across all compared calls
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AxiomComparison {
+    /// Sum of the per-call heal deltas
+    pub healed_delta: i64,
+    /// Compared calls touching this axiom whose outputs differed
+    pub differing_calls: usize,
+}
+
+/// Aggregate statistics over every call an `ABHealer` has served
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ABStatistics {
+    pub calls: usize,
+    /// Calls where both healers succeeded and were compared
+    pub compared: usize,
+    /// Calls where the candidate ran past the latency budget and was discarded
+    pub skipped_over_budget: usize,
+    /// Calls where the candidate returned an error
+    pub candidate_failures: usize,
+    pub same_decision: usize,
+    pub output_differs: usize,
+    /// Sum of `ABComparison::penalty_delta` over compared calls
+    pub total_penalty_delta: f64,
+    pub per_axiom: BTreeMap<Axiom, AxiomComparison>,
+}
+
+/// How the candidate behaves on one axiom relative to the control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ABVerdict {
+    HealsMore,
+    HealsLess,
+    /// Heals as many violations, but produces different output
+    HealsDifferently,
+    Same,
+}
+
+impl fmt::Display for ABVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ABVerdict::HealsMore => "heals more",
+            ABVerdict::HealsLess => "heals less",
+            ABVerdict::HealsDifferently => "heals differently",
+            ABVerdict::Same => "same",
+        })
+    }
+}
+
+/// Summary of an A/B run, produced by `ABHealer::report`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ABReport {
+    pub statistics: ABStatistics,
+    pub verdicts: BTreeMap<Axiom, ABVerdict>,
+}
+
+impl fmt::Display for ABReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = &self.statistics;
+        write!(
+            f,
+            "{} calls, {} compared, {} skipped over budget, {} candidate failures; \
+             same decision {}/{}, output differs {}/{}",
+            s.calls,
+            s.compared,
+            s.skipped_over_budget,
+            s.candidate_failures,
+            s.same_decision,
+            s.compared,
+            s.output_differs,
+            s.compared
+        )?;
+        for (axiom, verdict) in &self.verdicts {
+            write!(f, "\n  {:?}: candidate {}", axiom, verdict)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a candidate healer in shadow of a control healer on the same inputs.
+///
+/// Callers always get the control's result. The candidate runs afterwards under the
+/// latency budget as its deadline; if it overruns, its result is discarded and counted
+/// as a skip. Candidate errors are counted and otherwise ignored. When the control
+/// fails the candidate is not run.
+pub struct ABHealer {
+    control: AxiomaticSelfHealer,
+    candidate: AxiomaticSelfHealer,
+    latency_budget: Option<Duration>,
+    statistics: ABStatistics,
+    comparisons: VecDeque<ABComparison>,
+}
+
+impl ABHealer {
+    /// Comparisons retained for `drain_comparisons`; aggregate statistics are unbounded
+    const MAX_BUFFERED_COMPARISONS: usize = 1024;
+
+    pub fn new(control: AxiomaticSelfHealer, candidate: AxiomaticSelfHealer) -> Self {
+        Self {
+            control,
+            candidate,
+            latency_budget: None,
+            statistics: ABStatistics::default(),
+            comparisons: VecDeque::new(),
+        }
+    }
+
+    /// Bound the extra time the candidate may add to a call; `None` disables the bound
+    pub fn set_latency_budget(&mut self, budget: Option<Duration>) {
+        self.latency_budget = budget;
+    }
+
+    pub fn control(&self) -> &AxiomaticSelfHealer {
+        &self.control
+    }
+
+    pub fn candidate(&self) -> &AxiomaticSelfHealer {
+        &self.candidate
+    }
+
+    /// Heal with the control, shadowing the candidate, and return the control's result
+    pub fn monitor_and_heal(&mut self, context: &str) -> Result<HealReport, HealError> {
+        self.statistics.calls += 1;
+        let control = self.control.monitor_and_heal_detailed(context)?;
+
+        let started = Instant::now();
+        let options = HealOptions {
+            deadline: self.latency_budget.map(|budget| started + budget),
+            ..HealOptions::default()
+        };
+        let candidate = self.candidate.monitor_and_heal_with(context, &options);
+        let over_budget = self.latency_budget.is_some_and(|b| started.elapsed() > b);
+
+        match candidate {
+            Err(HealError::DeadlineExceeded { .. }) => self.statistics.skipped_over_budget += 1,
+            _ if over_budget => self.statistics.skipped_over_budget += 1,
+            Err(_) => self.statistics.candidate_failures += 1,
+            Ok(candidate) => {
+                let comparison = self.compare(&control, &candidate);
+                self.record(comparison);
+            }
+        }
+
+        Ok(control)
+    }
+
+    fn compare(&self, control: &HealReport, candidate: &HealReport) -> ABComparison {
+        let residual = |report: &HealReport| {
+            let remaining = self.control.regularizer.scan(&report.context);
+            self.control.regularizer.calculate_penalty(&remaining)
+        };
+
+        let mut per_axiom_deltas: BTreeMap<Axiom, i64> = BTreeMap::new();
+        for (axiom, healed) in healed_by_axiom(control) {
+            *per_axiom_deltas.entry(axiom).or_insert(0) -= healed;
+        }
+        for (axiom, healed) in healed_by_axiom(candidate) {
+            *per_axiom_deltas.entry(axiom).or_insert(0) += healed;
+        }
+        for violation in control.violations.iter().chain(&candidate.violations) {
+            per_axiom_deltas.entry(violation.axiom.clone()).or_insert(0);
+        }
+
+        ABComparison {
+            same_decision: control.decision.heal == candidate.decision.heal,
+            penalty_delta: residual(candidate) - residual(control),
+            output_differs: control.context != candidate.context,
+            per_axiom_deltas,
+        }
+    }
+
+    fn record(&mut self, comparison: ABComparison) {
+        let stats = &mut self.statistics;
+        stats.compared += 1;
+        stats.same_decision += usize::from(comparison.same_decision);
+        stats.output_differs += usize::from(comparison.output_differs);
+        stats.total_penalty_delta += comparison.penalty_delta;
+        for (axiom, delta) in &comparison.per_axiom_deltas {
+            let entry = stats.per_axiom.entry(axiom.clone()).or_default();
+            entry.healed_delta += delta;
+            entry.differing_calls += usize::from(comparison.output_differs);
+        }
+
+        if self.comparisons.len() >= Self::MAX_BUFFERED_COMPARISONS {
+            self.comparisons.pop_front();
+        }
+        self.comparisons.push_back(comparison);
+    }
+
+    pub fn statistics(&self) -> &ABStatistics {
+        &self.statistics
+    }
+
+    /// Take the per-call comparisons recorded since the last drain
+    pub fn drain_comparisons(&mut self) -> Vec<ABComparison> {
+        self.comparisons.drain(..).collect()
+    }
+
+    /// Summarize, per axiom, whether the candidate heals more, less or differently
+    pub fn report(&self) -> ABReport {
+        let verdicts = self
+            .statistics
+            .per_axiom
+            .iter()
+            .map(|(axiom, totals)| {
+                let verdict = match totals.healed_delta {
+                    d if d > 0 => ABVerdict::HealsMore,
+                    d if d < 0 => ABVerdict::HealsLess,
+                    _ if totals.differing_calls > 0 => ABVerdict::HealsDifferently,
+                    _ => ABVerdict::Same,
+                };
+                (axiom.clone(), verdict)
+            })
+            .collect();
+        ABReport {
+            statistics: self.statistics.clone(),
+            verdicts,
+        }
+    }
+}
+
+/// Violations a report counts as healed, per axiom
+fn healed_by_axiom(report: &HealReport) -> BTreeMap<Axiom, i64> {
+    let mut healed = BTreeMap::new();
+    if report.outcome != HealOutcome::Healed {
+        return healed;
+    }
+    for violation in &report.violations {
+        *healed.entry(violation.axiom.clone()).or_insert(0) += 1;
+    }
+    for (violation, _) in &report.unhealed {
+        *healed.entry(violation.axiom.clone()).or_insert(0) -= 1;
+    }
+    healed
+}
+
+impl Drop for AxiomaticSelfHealer {
+    /// Deliver what can go out within a short budget; use `shutdown` to control it
+    fn drop(&mut self) {
+        const DROP_FLUSH_BUDGET: Duration = Duration::from_millis(10);
+        if self.sinks.iter().all(|queued| queued.queue.is_empty()) {
+            return;
+        }
+        let flushes = self.flush_sinks(Some(Instant::now() + DROP_FLUSH_BUDGET));
+        for flush in flushes {
+            let lost = flush.pending + flush.failed + flush.overflowed;
+            if lost > 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(sink = %flush.name, lost, "healer dropped undelivered violations");
+                #[cfg(not(feature = "tracing"))]
+                let _ = lost;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViolationStatistics {
+    pub total: usize,
+    pub by_axiom: HashMap<Axiom, usize>,
+    pub by_severity: HashMap<Severity, usize>,
+    /// Rates over the recent window, when the statistics come from a regularizer's
+    /// `statistics`; not carried by `merge`
+    pub window: Option<WindowedStatistics>,
+}
+
+/// Violation rates over the most recent stretch of history
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowedStatistics {
+    pub window: Duration,
+    /// Violations of enabled axioms recorded within the window
+    pub violations: usize,
+    pub per_minute: f64,
+    pub per_minute_by_axiom: BTreeMap<Axiom, f64>,
+    /// Mean penalty of a violation in the window at the current weights; zero when
+    /// the window is empty
+    pub average_penalty: f64,
+}
+
+impl ViolationStatistics {
+    /// Count one more violation
+    pub fn record(&mut self, violation: &Violation) {
+        self.total += 1;
+        *self.by_axiom.entry(violation.axiom.clone()).or_insert(0) += 1;
+        *self.by_severity.entry(violation.severity).or_insert(0) += 1;
+    }
+
+    /// Violations counted for the axiom called `name`, built-in or custom
+    pub fn count_named(&self, name: &str) -> usize {
+        self.by_axiom
+            .iter()
+            .filter(|(axiom, _)| axiom.name() == name)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Add another set of statistics into this one
+    pub fn merge(&mut self, other: &ViolationStatistics) {
+        self.total += other.total;
+        for (axiom, count) in &other.by_axiom {
+            *self.by_axiom.entry(axiom.clone()).or_insert(0) += count;
+        }
+        for (severity, count) in &other.by_severity {
+            *self.by_severity.entry(*severity).or_insert(0) += count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation_detection() {
+        let aar = AdaptiveAxiomaticRegularizer::new();
+        let violations = aar.detect_violations("This is an inconsistent result");
+        assert!(!violations.is_empty());
+    }
+
+    #[test]
+    fn test_self_healing() {
+        let aar = AdaptiveAxiomaticRegularizer::new();
+        let mut healer = AxiomaticSelfHealer::new(aar);
+        
+        let result = healer.monitor_and_heal("This is inconsistent");
+        assert!(result.is_ok());
+    }
+
+    #[test]
     fn test_penalty_calculation() {
         let aar = AdaptiveAxiomaticRegularizer::new();
         let violations = vec![
             Violation {
-                axiom: Axiom::Safety,
-                severity: Severity::Critical,
-                context: "test".to_string(),
+                axiom: Axiom::Safety,
+                severity: Severity::Critical,
+                context: "test".to_string(),
+                timestamp: 0,
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let penalty = aar.calculate_penalty(&violations);
+        assert!(penalty > 0.0);
+    }
+
+    #[test]
+    fn test_default_threshold_policy_matches_regularizer() {
+        let aar = AdaptiveAxiomaticRegularizer::new();
+        let threshold = aar.threshold();
+        let healer = AxiomaticSelfHealer::new(aar);
+        assert_eq!(healer.threshold_policy(), ThresholdPolicy::HealAbove(threshold));
+    }
+
+    #[test]
+    fn test_threshold_policy_boundary_at_equal_penalty() {
+        // A single High consistency violation at weight 1.0 scores exactly 4.0
+        let context = "This is inconsistent";
+
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_threshold_policy(ThresholdPolicy::HealAbove(4.0));
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.penalty, 4.0);
+        assert!(!report.decision.heal);
+        assert_eq!(report.decision.policy, ThresholdPolicy::HealAbove(4.0));
+        assert_eq!(report.context, context);
+
+        healer.set_threshold_policy(ThresholdPolicy::HealAtOrAbove(4.0));
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert!(report.decision.heal);
+        assert_eq!(report.decision.policy.value(), Some(4.0));
+        assert_ne!(report.context, context);
+    }
+
+    #[test]
+    fn test_threshold_policy_any_violation_and_never() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        assert!(healer.monitor_and_heal_detailed("inconsistent").unwrap().decision.heal);
+        assert!(!healer.monitor_and_heal_detailed("all good").unwrap().decision.heal);
+
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+        let report = healer.monitor_and_heal_detailed("unsafe").unwrap();
+        assert!(!report.decision.heal);
+        assert_eq!(report.context, "unsafe");
+        assert_eq!(healer.get_statistics().total, 2);
+    }
+
+    #[test]
+    fn test_propose_evaluates_every_strategy() {
+        let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let context = "This is inconsistent";
+        let violation = healer.regularizer.detect_violations(context).remove(0);
+
+        let proposals = healer.propose(context, &violation);
+        let names: Vec<_> = proposals.iter().map(|p| p.strategy_name).collect();
+        assert_eq!(names, vec!["rollback", "recompute"]);
+
+        let rollback = proposals[0].verification.as_ref().unwrap();
+        assert!(!rollback.resolved);
+        let recompute = proposals[1].verification.as_ref().unwrap();
+        assert!(recompute.resolved);
+        assert_eq!(recompute.penalty, 0.0);
+
+        // Nothing is recorded until a proposal is applied
+        assert_eq!(healer.get_statistics().total, 0);
+        let applied = healer.apply_proposal(context, &proposals[1]).unwrap();
+        assert_eq!(applied, "This is consistent");
+        assert_eq!(healer.get_statistics().total, 1);
+    }
+
+    #[test]
+    fn test_propose_cost_cap_and_failed_proposals() {
+        let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let context = "an unsafe operation";
+        let violation = healer.regularizer.detect_violations(context).remove(0);
+
+        let options = PreviewOptions { max_cost: Some(1) };
+        let proposals = healer.propose_with(context, &violation, &options);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].strategy_name, "rollback");
+
+        let proposals = healer.propose(context, &violation);
+        let query = &proposals[1];
+        assert!(query.result.is_err());
+        assert!(query.verification.is_none());
+        assert!(healer.apply_proposal(context, query).is_err());
+        assert!(healer.apply_proposal("another context", &proposals[0]).is_err());
+    }
+
+    #[test]
+    fn test_penalty_for_batch() {
+        let aar = AdaptiveAxiomaticRegularizer::new();
+        let contexts = ["fine", "inconsistent", "unsafe and inconsistent"];
+
+        let mut streamed = Vec::new();
+        let batch = aar.penalty_for_batch_with(&contexts, |i, p| streamed.push((i, p)));
+        assert_eq!(batch.per_context, vec![0.0, 4.0, 16.0]);
+        assert_eq!(batch.total, 20.0);
+        assert_eq!(streamed, vec![(0, 0.0), (1, 4.0), (2, 16.0)]);
+
+        let consistency = &batch.per_axiom[&Axiom::Consistency];
+        assert_eq!(consistency.violations, 2);
+        assert_eq!(consistency.gradient, 8.0);
+        assert_eq!(batch.per_axiom[&Axiom::Safety].penalty, 12.0);
+
+        let parallel = aar.penalty_for_batch_parallel(&contexts, 2);
+        assert_eq!(parallel, batch);
+    }
+
+    #[test]
+    fn test_suggest_weight_feedback_closes_loop() {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        let targets = HashMap::from([(Axiom::Consistency, 0.1), (Axiom::Safety, 0.5)]);
+        assert!(aar.suggest_weight_feedback(&targets).is_empty());
+
+        aar.penalty_for_batch(&["inconsistent", "inconsistent", "fine", "fine"]);
+        let feedback = aar.suggest_weight_feedback(&targets);
+        assert_eq!(feedback, vec![(Axiom::Consistency, 1.0), (Axiom::Safety, -1.0)]);
+
+        let consistency_before = aar.weight(&Axiom::Consistency).unwrap();
+        let safety_before = aar.weight(&Axiom::Safety).unwrap();
+        aar.update_weights_batch(&feedback);
+        assert!(aar.weight(&Axiom::Consistency).unwrap() > consistency_before);
+        assert!(aar.weight(&Axiom::Safety).unwrap() < safety_before);
+    }
+
+    /// A regularizer where interpolated output is itself a Transparency violation,
+    /// so the built-in Interpolate strategy makes Completeness heals score worse
+    fn regressing_regularizer() -> AdaptiveAxiomaticRegularizer {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("TODO", Axiom::Completeness, Severity::High));
+        aar.add_rule(DetectionRule::new("INTERPOLATED", Axiom::Transparency, Severity::High));
+        aar
+    }
+
+    #[test]
+    fn test_outcomes_distinguish_clean_recorded_healed() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        assert_eq!(healer.monitor_and_heal_detailed("fine").unwrap().outcome, HealOutcome::Clean);
+        assert_eq!(
+            healer.monitor_and_heal_detailed("inconsistent").unwrap().outcome,
+            HealOutcome::Healed
+        );
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+        assert_eq!(
+            healer.monitor_and_heal_detailed("inconsistent").unwrap().outcome,
+            HealOutcome::Recorded
+        );
+    }
+
+    #[test]
+    fn test_verify_after_heal_rejects_regression() {
+        let context = "Summary: TODO";
+
+        // Without verification the worse output is returned as healed
+        let mut healer = AxiomaticSelfHealer::new(regressing_regularizer());
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+        assert_eq!(report.context, "Summary: TODO [INTERPOLATED]");
+
+        healer.set_verify_after_heal(true);
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.outcome, HealOutcome::HealRegressed { before: 4.0, after: 8.0 });
+        assert_eq!(report.context, context);
+
+        let stats = &healer.strategy_statistics()["interpolate"];
+        assert_eq!(stats.successes, 2);
+        assert_eq!(stats.regressions, 1);
+        assert_eq!(healer.get_statistics().total, 2);
+    }
+
+    #[test]
+    fn test_expired_deadline_records_unhealed_due_to_deadline() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let options = HealOptions {
+            deadline: Some(Instant::now()),
+            ..HealOptions::default()
+        };
+
+        let err = healer.monitor_and_heal_with("unsafe and inconsistent", &options).unwrap_err();
+        assert!(matches!(err, HealError::DeadlineExceeded { .. }));
+        let partial = err.partial().unwrap();
+        assert_eq!(partial.context, "unsafe and inconsistent");
+        assert_eq!(partial.unhealed.len(), 2);
+        assert!(partial.unhealed.iter().all(|(_, r)| *r == UnhealedReason::DeadlineExceeded));
+
+        assert_eq!(healer.unhealed_counts()[&UnhealedReason::DeadlineExceeded], 2);
+        assert!(!healer.unhealed_counts().contains_key(&UnhealedReason::StrategiesFailed));
+        assert_eq!(healer.get_statistics().total, 2);
+    }
+
+    #[test]
+    fn test_default_deadline_and_cancellation_share_token() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_default_deadline(Some(Duration::from_secs(60)));
+        assert!(healer.monitor_and_heal_detailed("inconsistent").is_ok());
+
+        let token = CancellationToken::new();
+        let bounded = token.with_deadline(Instant::now() + Duration::from_secs(60));
+        assert!(!bounded.is_cancelled());
+        token.cancel();
+        assert!(bounded.is_cancelled());
+
+        let options = HealOptions {
+            cancel: Some(token),
+            ..HealOptions::default()
+        };
+        let err = healer.monitor_and_heal_with("inconsistent", &options).unwrap_err();
+        assert!(matches!(err, HealError::Cancelled { .. }));
+        assert_eq!(healer.unhealed_counts()[&UnhealedReason::Cancelled], 1);
+    }
+
+    #[test]
+    fn test_failed_strategies_are_unhealed_distinctly() {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("biased", Axiom::Fairness, Severity::High));
+        let mut healer = AxiomaticSelfHealer::new(aar);
+
+        // Fairness has no strategies registered
+        let report = healer.monitor_and_heal_detailed("a biased answer").unwrap();
+        assert_eq!(report.unhealed.len(), 1);
+        assert_eq!(report.unhealed[0].1, UnhealedReason::StrategiesFailed);
+        assert_eq!(report.outcome, HealOutcome::Unhealed);
+        assert_eq!(report.context, "a biased answer");
+    }
+
+    #[test]
+    fn test_strict_healing_turns_unhealed_reports_into_errors() {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("biased", Axiom::Fairness, Severity::High));
+        let mut healer = AxiomaticSelfHealer::new(aar);
+        healer.correction_strategies.insert(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        let strict = HealOptions { strict_healing: true, ..HealOptions::default() };
+
+        let error = healer.monitor_and_heal_with("a biased answer", &strict).unwrap_err();
+        let HealError::StrategiesExhausted { partial } = &error else {
+            panic!("expected StrategiesExhausted, got {:?}", error);
+        };
+        assert_eq!(partial.attempts[0].selection, SelectionReason::NoStrategies);
+        let error = healer.monitor_and_heal_with("unsafe", &strict).unwrap_err();
+        let HealError::UserInterventionRequired { axioms, partial } = &error else {
+            panic!("expected UserInterventionRequired, got {:?}", error);
+        };
+        assert_eq!(axioms, &vec![Axiom::Safety]);
+        let attempt = &partial.attempts[0].attempts[0];
+        assert_eq!(attempt.result, Err("User intervention required".to_string()));
+        assert_eq!(error.to_string(), "User intervention required for [Safety]");
+
+        // A partial heal is still a heal
+        let report = healer.monitor_and_heal_with("unsafe and inconsistent", &strict).unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+        assert_eq!(report.unhealed.len(), 1);
+        assert_eq!(healer.monitor_and_heal("unsafe").unwrap(), "unsafe");
+    }
+
+    fn healer_with_manual_clock() -> (AxiomaticSelfHealer, ManualClock) {
+        let clock = ManualClock::new(1_000_000);
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.set_clock(clock.clone());
+        let mut healer = AxiomaticSelfHealer::new(aar);
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+        (healer, clock)
+    }
+
+    #[test]
+    fn test_trend_buckets_window() {
+        let mut trend = TrendBuckets::new(Duration::from_secs(10), 4);
+        trend.record(0, 1);
+        trend.record(9_999, 2);
+        trend.record(25_000, 1);
+        assert_eq!(trend.buckets().collect::<Vec<_>>(), vec![(0, 3), (20_000, 1)]);
+        assert_eq!(trend.count_within(25_000, Duration::from_secs(10)), 1);
+        assert_eq!(trend.count_within(25_000, Duration::from_secs(30)), 4);
+
+        for ts in [30_000, 40_000, 50_000] {
+            trend.record(ts, 1);
+        }
+        assert_eq!(trend.buckets().count(), 4);
+        assert_eq!(trend.buckets().next(), Some((20_000, 1)));
+    }
+
+    #[test]
+    fn test_budget_return_error_and_window_expiry() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        let hour = Duration::from_secs(3600);
+        healer.set_violation_budget(Axiom::Safety, 2, hour, BudgetAction::ReturnError);
+
+        assert!(healer.monitor_and_heal_detailed("unsafe").is_ok());
+        clock.advance(Duration::from_secs(600));
+        assert!(healer.monitor_and_heal_detailed("unsafe").is_ok());
+        let status = healer.budget_consumption(&Axiom::Safety).unwrap();
+        assert_eq!(status.used, 2);
+        assert!(status.exhausted());
+
+        let err = healer.monitor_and_heal_detailed("fine").unwrap_err();
+        assert!(matches!(
+            err,
+            HealError::BudgetExhausted { axiom: Axiom::Safety, window } if window == hour
+        ));
+
+        // The first violation leaves the window, restoring one unit of budget
+        clock.advance(Duration::from_secs(3000 + 60));
+        assert_eq!(healer.budget_consumption(&Axiom::Safety).unwrap().used, 1);
+        assert!(healer.monitor_and_heal_detailed("fine").is_ok());
+
+        clock.advance(hour);
+        assert_eq!(healer.budget_consumption(&Axiom::Safety).unwrap().used, 0);
+    }
+
+    #[test]
+    fn test_budget_alert_and_healed_violations_do_not_count() {
+        let (mut healer, _clock) = healer_with_manual_clock();
+        let window = Duration::from_secs(60);
+        healer.set_violation_budget(Axiom::Consistency, 1, window, BudgetAction::RaiseAlert);
+        healer.set_violation_budget(Axiom::Safety, 1, window, BudgetAction::RecordOnly);
+
+        healer.monitor_and_heal_detailed("unsafe").unwrap();
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        let alerts = healer.take_budget_alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].axiom, Axiom::Consistency);
+        assert_eq!(alerts[0].status.used, 1);
+        assert_eq!(healer.budget_consumption(&Axiom::Safety).unwrap().used, 1);
+
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_violation_budget(Axiom::Consistency, 1, window, BudgetAction::ReturnError);
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        assert_eq!(healer.budget_consumption(&Axiom::Consistency).unwrap().used, 0);
+    }
+
+    #[test]
+    fn test_role_prefix_segmenter_spans() {
+        let context = "preamble\nuser: hi there\nmore from user\nassistant: unsafe reply";
+        let segments = RolePrefixSegmenter::default().segment(context);
+        let texts: Vec<_> = segments
+            .iter()
+            .map(|s| (s.role.as_deref(), &context[s.span.clone()]))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (None, "preamble"),
+                (Some("user"), "hi there\nmore from user"),
+                (Some("assistant"), "unsafe reply"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segmented_healing_scopes_to_violating_turn() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_segmenter(Some(Box::new(RolePrefixSegmenter::default())));
+        healer.restrict_axiom_to_roles(Axiom::Safety, ["assistant"]);
+
+        let context =
+            "user: is this unsafe?\nassistant: it is inconsistent\nassistant: unsafe op\n";
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+
+        // The user's "unsafe" is not a Safety violation under the role rule
+        let found: Vec<_> = report
+            .violations
+            .iter()
+            .map(|v| (v.axiom.clone(), v.metadata["segment"].as_str(), v.metadata["role"].as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(Axiom::Consistency, "1", "assistant"), (Axiom::Safety, "2", "assistant")]
+        );
+        assert_eq!(report.violations[0].context, "it is inconsistent");
+        assert_eq!(
+            report.context,
+            "user: is this unsafe?\nassistant: [ROLLED_BACK] it is inconsistent\n\
+             assistant: [ROLLED_BACK] unsafe op\n"
+        );
+    }
+
+    #[test]
+    fn test_segment_reassembly_is_lossless() {
+        // Deterministic pseudo-random transcripts built from awkward pieces
+        let pieces = [
+            "user:", "user: ", "assistant: ", "system:x", "\n", "\r\n", "\n\n", "héllo ",
+            "ok", "user", ": ", "assistant:\n", " ", "日本語", "inconsistent",
+        ];
+        let segmenter = RolePrefixSegmenter::default();
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_segmenter(Some(Box::new(RolePrefixSegmenter::default())));
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..500 {
+            let mut context = String::new();
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            for _ in 0..(state >> 59) {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                context.push_str(pieces[(state >> 33) as usize % pieces.len()]);
+            }
+
+            let segments = segmenter.segment(&context);
+            assert_eq!(reassemble(&context, &segments, &HashMap::new()), context);
+            assert_eq!(healer.monitor_and_heal(&context).unwrap(), context);
+        }
+    }
+
+    #[test]
+    fn test_detector_coverage() {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("biased", Axiom::Fairness, Severity::Medium));
+        let coverage = aar.detector_coverage();
+        assert_eq!(coverage[0], ("rule:inconsistent".to_string(), vec![Axiom::Consistency]));
+        assert_eq!(coverage[2], ("rule:biased".to_string(), vec![Axiom::Fairness]));
+        assert_eq!(
+            aar.covered_axioms().into_iter().collect::<Vec<_>>(),
+            vec![Axiom::Consistency, Axiom::Safety, Axiom::Fairness]
+        );
+    }
+
+    #[test]
+    fn test_required_axiom_gap_reported_as_violation() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let options = HealOptions {
+            required_axioms: vec![Axiom::Safety, Axiom::Fairness],
+            ..HealOptions::default()
+        };
+
+        let report = healer.monitor_and_heal_with("all good", &options).unwrap();
+        assert_eq!(report.violations.len(), 1);
+        let gap = &report.violations[0];
+        assert_eq!(gap.axiom, Axiom::Completeness);
+        assert_eq!(gap.metadata["coverage_gap"], "Fairness");
+        // The gap is recorded, but the context is left alone
+        assert_eq!(report.context, "all good");
+        assert_eq!(report.unhealed.len(), 1);
+        assert_eq!(healer.get_statistics().by_axiom[&Axiom::Completeness], 1);
+    }
+
+    #[test]
+    fn test_required_axiom_gap_strict_mode() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let options = HealOptions {
+            required_axioms: vec![Axiom::Fairness],
+            strict_coverage: true,
+            ..HealOptions::default()
+        };
+        let err = healer.monitor_and_heal_with("inconsistent", &options).unwrap_err();
+        assert!(matches!(
+            err,
+            HealError::CoverageGap { ref missing } if missing == &[Axiom::Fairness]
+        ));
+
+        let options = HealOptions {
+            required_axioms: vec![Axiom::Safety],
+            strict_coverage: true,
+            ..HealOptions::default()
+        };
+        assert!(healer.monitor_and_heal_with("inconsistent", &options).is_ok());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aar-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_snapshot_encode_roundtrip_and_integrity() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.regularizer.update_weights(Axiom::Safety, 7.3);
+        healer.set_threshold_policy(ThresholdPolicy::HealAtOrAbove(2.5));
+        healer.accept_regressions_for(Axiom::Transparency);
+
+        let snapshot = healer.snapshot();
+        let encoded = snapshot.encode();
+        assert_eq!(HealerSnapshot::decode(&encoded).unwrap(), snapshot);
+
+        // A write cut short can never decode as valid
+        let truncated = &encoded[..encoded.len() - 3];
+        assert!(matches!(
+            HealerSnapshot::decode(truncated),
+            Err(SnapshotError::LengthMismatch { .. })
+        ));
+        let tampered = encoded.replace("2.5", "9.5");
+        assert!(matches!(
+            HealerSnapshot::decode(&tampered),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+        let future = encoded.replacen("AARSNAP 4", "AARSNAP 5", 1);
+        assert!(matches!(
+            HealerSnapshot::decode(&future),
+            Err(SnapshotError::Schema(schema::SchemaError { ref found, supported }))
+                if found == "aar.snapshot.v5" && supported == schema::SNAPSHOT
+        ));
+
+        // Disabled axioms and counters survive, and restore brings back all but the
+        // violation counts, which follow the history
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.monitor_and_heal("unsafe").unwrap();
+        healer.strategy_stats.insert("odd name".to_string(), StrategyStats {
+            attempts: 3,
+            ..StrategyStats::default()
+        });
+        healer.regularizer.disable_axiom(Axiom::Fairness);
+        let snapshot = healer.snapshot();
+        assert_eq!(snapshot.violation_counts, BTreeMap::from([(Axiom::Safety, 1)]));
+        assert!(snapshot.strategy_stats.values().any(|stats| stats.successes == 1));
+        let decoded = HealerSnapshot::decode(&snapshot.encode()).unwrap();
+        assert_eq!(decoded, snapshot);
+        let mut restored = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        restored.restore(&decoded);
+        assert!(!restored.regularizer.is_enabled(&Axiom::Fairness));
+        assert_eq!(restored.strategy_stats["odd name"].attempts, 3);
+        assert!(restored.snapshot().violation_counts.is_empty());
+
+        // Sentence templates are captured with their chain, whatever they contain
+        let replace = |template: &str| CorrectionStrategy::ReplaceSentence {
+            template: template.to_string(),
+        };
+        healer.set_strategies(Axiom::Safety, vec![
+            replace("[{axiom}, removed]"),
+            CorrectionStrategy::ExciseSentence,
+            replace("two\nlines \\ here"),
+        ]);
+        let snapshot = healer.snapshot();
+        let decoded = HealerSnapshot::decode(&snapshot.encode()).unwrap();
+        let chain = &healer.correction_strategies[&Axiom::Safety];
+        assert_eq!(&decoded.strategies[&Axiom::Safety], chain);
+        healer.set_strategies(Axiom::Safety, vec![replace("[redacted]")]);
+        let diff = decoded.diff(&healer.snapshot());
+        assert_eq!(diff.strategies[&Axiom::Safety].added, ["replace_sentence:[redacted]"]);
+
+        // A version 1 body has none of those lines and migrates as is
+        let v1 = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new()).snapshot();
+        let encoded = v1.encode().replacen("AARSNAP 4 ", "AARSNAP 1 ", 1);
+        assert_eq!(HealerSnapshot::decode(&encoded).unwrap(), v1);
+    }
+
+    #[test]
+    fn test_periodic_persistence_and_recovery() {
+        let dir = temp_dir("persist");
+        let clock = ManualClock::new(0);
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.set_clock(clock.clone());
+        let mut healer = AxiomaticSelfHealer::builder(aar)
+            .with_persistence(&dir, Duration::from_secs(60))
+            .build();
+
+        healer.monitor_and_heal("fine").unwrap();
+        let first = std::fs::read_to_string(dir.join("healer.snapshot")).unwrap();
+
+        // Within the interval nothing is rewritten
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        clock.advance(Duration::from_secs(30));
+        healer.monitor_and_heal("fine").unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("healer.snapshot")).unwrap(), first);
+
+        clock.advance(Duration::from_secs(30));
+        healer.monitor_and_heal("fine").unwrap();
+        assert!(!dir.join("healer.snapshot.tmp").exists());
+
+        let mut recovered = AxiomaticSelfHealer::recover(&dir, AdaptiveAxiomaticRegularizer::new());
+        assert_eq!(recovered.threshold_policy(), ThresholdPolicy::HealOnAnyViolation);
+        assert!(recovered.drain_events().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recover_from_corrupted_snapshot() {
+        let dir = temp_dir("corrupt");
+        let mut healer = AxiomaticSelfHealer::builder(AdaptiveAxiomaticRegularizer::new())
+            .with_persistence(&dir, Duration::ZERO)
+            .build();
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+        healer.persist_now().unwrap();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.persist_now().unwrap();
+
+        // Corrupt the latest snapshot: recovery falls back to the previous one
+        let path = dir.join("healer.snapshot");
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0x01;
+        std::fs::write(&path, &bytes).unwrap();
+
+        let mut recovered = AxiomaticSelfHealer::recover(&dir, AdaptiveAxiomaticRegularizer::new());
+        assert_eq!(recovered.threshold_policy(), ThresholdPolicy::Never);
+        let events = recovered.drain_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], HealerEvent::SnapshotRejected { path: p, .. } if *p == path));
+
+        // With both files unusable the defaults are used and reported
+        std::fs::write(dir.join("healer.snapshot.prev"), b"garbage").unwrap();
+        let mut recovered = AxiomaticSelfHealer::recover(&dir, AdaptiveAxiomaticRegularizer::new());
+        assert_eq!(recovered.threshold_policy(), ThresholdPolicy::HealAbove(0.5));
+        assert_eq!(recovered.drain_events().last(), Some(&HealerEvent::RecoveredWithDefaults));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn explain(healer: &mut AxiomaticSelfHealer, context: &str) -> Vec<String> {
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        report.explanations().iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_explanations_golden_builtin_strategies() {
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("TODO", Axiom::Completeness, Severity::Medium));
+        let mut healer = AxiomaticSelfHealer::new(aar);
+
+        assert_eq!(
+            explain(&mut healer, "This is inconsistent"),
+            vec!["Consistency violation (High) detected by rule:inconsistent, which assigns \
+                  that severity. Tried rollback (succeeded). Chose rollback as the first strategy \
+                  in chain order to succeed. Change: inserted \"[ROLLED_BACK] \" at byte 0."]
+        );
+        assert_eq!(
+            explain(&mut healer, "Notes: TODO"),
+            vec!["Completeness violation (Medium) detected by rule:TODO, which assigns that \
+                  severity. Tried interpolate (succeeded). Chose interpolate as the first strategy \
+                  in chain order to succeed. Change: inserted \" [INTERPOLATED]\" at byte 11."]
+        );
+
+        healer.correction_strategies.insert(
+            Axiom::Safety,
+            vec![CorrectionStrategy::QueryUser, CorrectionStrategy::ApplyDefault],
+        );
+        healer
+            .correction_strategies
+            .insert(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+        assert_eq!(
+            explain(&mut healer, "unsafe"),
+            vec!["Safety violation (Critical) detected by rule:unsafe, which assigns that \
+                  severity. Tried query_user (failed: User intervention required), \
+                  apply_default (succeeded). Chose apply_default as the first strategy in \
+                  chain order to succeed. Change: inserted \" [DEFAULT_APPLIED]\" at byte 6."]
+        );
+        assert_eq!(
+            explain(&mut healer, "This is inconsistent"),
+            vec!["Consistency violation (High) detected by rule:inconsistent, which assigns \
+                  that severity. Tried recompute (succeeded). Chose recompute as the first \
+                  strategy in chain order to succeed. Change: removed \"in\" at byte 8."]
+        );
+
+        healer.correction_strategies.insert(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        healer.correction_strategies.remove(&Axiom::Completeness);
+        assert_eq!(
+            explain(&mut healer, "unsafe TODO"),
+            vec![
+                "Safety violation (Critical) detected by rule:unsafe, which assigns that \
+                 severity. Tried query_user (failed: User intervention required). No \
+                 strategy succeeded.",
+                "Completeness violation (Medium) detected by rule:TODO, which assigns that \
+                 severity. No strategy is registered for this axiom.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explanations_apply_quote_policy() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer
+            .correction_strategies
+            .insert(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+
+        healer.set_quote_policy(QuotePolicy { max_len: None, redact: true });
+        let report = healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        let change = report.explanations()[0].change.clone().unwrap();
+        assert_eq!(change.removed, "<redacted 2 chars>");
+
+        assert_eq!(elide_middle("abcdefghij", 5), "ab…ij");
+        assert_eq!(elide_middle("short", 5), "short");
+    }
+
+    #[test]
+    fn test_explanations_mention_regression() {
+        let mut healer = AxiomaticSelfHealer::new(regressing_regularizer());
+        healer.set_verify_after_heal(true);
+        let rendered = explain(&mut healer, "Summary: TODO");
+        assert!(rendered[0].ends_with(
+            "The heal was discarded because it raised the penalty from 4 to 8."
+        ));
+    }
+
+    #[test]
+    fn test_healer_handle_shared_across_threads() {
+        let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let handle = HealerHandle::new(healer);
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let handle = handle.clone();
+                std::thread::spawn(move || handle.monitor_and_heal("inconsistent").unwrap())
+            })
+            .collect();
+        for worker in workers {
+            assert_eq!(worker.join().unwrap().outcome, HealOutcome::Healed);
+        }
+        assert_eq!(handle.get_statistics().total, 4);
+    }
+
+    #[test]
+    fn test_thread_local_pool_merges_and_propagates_weights() {
+        use std::sync::Barrier;
+
+        let pool = ThreadLocalHealerPool::new(|| {
+            let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+            // Dropped when a thread's healer is built, so it is never merged
+            healer.regularizer.record_violation(detected(Axiom::Fairness, Severity::Low, None));
+            healer
+        });
+        let initial = pool.weight(&Axiom::Safety).unwrap();
+        let (worked, merged) = (Barrier::new(4), Barrier::new(4));
+
+        std::thread::scope(|scope| {
+            for _ in 0..3 {
+                scope.spawn(|| {
+                    for _ in 0..5 {
+                        pool.with_local(|h| h.monitor_and_heal("unsafe").unwrap());
+                    }
+                    worked.wait();
+                    merged.wait();
+                    let weight = pool.with_local(|h| h.regularizer().weight(&Axiom::Safety));
+                    assert_eq!(weight, Some(initial + 0.5));
+                    assert_eq!(pool.with_local(|h| h.get_statistics().total), 0);
+                });
+            }
+
+            worked.wait();
+            let stats = pool.merge_now();
+            assert_eq!(stats.total, 15);
+            assert_eq!(stats.by_axiom[&Axiom::Safety], 15);
+            assert!(!stats.by_axiom.contains_key(&Axiom::Fairness));
+
+            pool.update_weights(Axiom::Safety, 50.0);
+            pool.merge_now();
+            merged.wait();
+        });
+
+        assert_eq!(pool.strategy_statistics()["rollback"].successes, 15);
+        // The worker threads are gone; a further merge keeps the aggregate intact
+        assert_eq!(pool.merge_now().total, 15);
+    }
+
+    #[test]
+    fn test_ab_healer_returns_control_result_and_compares_candidate() {
+        let control = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let mut candidate = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        candidate.correction_strategies.insert(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        let mut ab = ABHealer::new(control, candidate);
+
+        for _ in 0..3 {
+            let report = ab.monitor_and_heal("unsafe").unwrap();
+            assert_eq!(report.context, "[ROLLED_BACK] unsafe");
+        }
+        ab.monitor_and_heal("all good").unwrap();
+
+        let comparisons = ab.drain_comparisons();
+        assert_eq!(comparisons.len(), 4);
+        assert!(comparisons[0].same_decision);
+        assert!(comparisons[0].output_differs);
+        assert_eq!(comparisons[0].per_axiom_deltas[&Axiom::Safety], -1);
+        assert!(!comparisons[3].output_differs);
+
+        let report = ab.report();
+        assert_eq!(report.statistics.calls, 4);
+        assert_eq!(report.statistics.compared, 4);
+        assert_eq!(report.statistics.output_differs, 3);
+        assert_eq!(report.verdicts[&Axiom::Safety], ABVerdict::HealsLess);
+        assert!(report.to_string().contains("Safety: candidate heals less"));
+    }
+
+    #[test]
+    fn test_ab_healer_skips_candidate_over_latency_budget() {
+        let control = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let candidate = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let mut ab = ABHealer::new(control, candidate);
+        ab.set_latency_budget(Some(Duration::ZERO));
+
+        let report = ab.monitor_and_heal("unsafe").unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+        assert_eq!(ab.statistics().skipped_over_budget, 1);
+        assert_eq!(ab.statistics().compared, 0);
+        assert!(ab.drain_comparisons().is_empty());
+    }
+
+    crate::axiom_rules! {
+        fn key_rules {
+            safety critical: r"leaked key",
+            consistency high: "contradicts",
+        }
+    }
+
+    #[test]
+    fn test_axiom_rules_macro_expands_to_plain_rules() {
+        #[cfg(not(feature = "regex"))]
+        let rule = DetectionRule::new;
+        #[cfg(feature = "regex")]
+        let rule = |pattern, axiom, severity| {
+            DetectionRule::regex(pattern, axiom, severity).unwrap()
+        };
+        assert_eq!(
+            key_rules(),
+            vec![
+                rule("leaked key", Axiom::Safety, Severity::Critical),
+                rule("contradicts", Axiom::Consistency, Severity::High),
+            ]
+        );
+        let inline = crate::axiom_rules! { fairness low: "biased" };
+        assert_eq!(inline, vec![rule("biased", Axiom::Fairness, Severity::Low)]);
+
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        for rule in key_rules() {
+            aar.add_rule(rule);
+        }
+        assert_eq!(aar.detect_violations("this contradicts that")[0].axiom, Axiom::Consistency);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_axiom_rules_regex_patterns_match_and_agree_with_regex() {
+        let rules = crate::axiom_rules! {
+            safety critical: r"leak(ed)?\s+key",
+            consistency high: "contradicts",
+        };
+        assert!(rules.iter().all(DetectionRule::is_regex));
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        for rule in rules {
+            aar.add_rule(rule);
+        }
+        for context in ["a leaked  key", "leak key"] {
+            let found = aar.detect_violations(context);
+            assert_eq!(found[0].axiom, Axiom::Safety, "{}", context);
+        }
+        assert!(aar.detect_violations(r"leak(ed)?\s+key").is_empty());
+
+        // The build-time check never rejects a pattern regex accepts, and catches
+        // each kind of malformed syntax it looks for
+        let accepted = [
+            r"a+?b*c{2}d{1,}e{1,3}?",
+            r"(?i)x|(?:y)",
+            r"[]a][^]b][a[bc]][[:alpha:]]",
+            r"\(\)\[\{",
+            r"^a$",
+            "}]",
+            r"\p{Greek}+\P{L}\x{41}{2}\pN",
+        ];
+        let rejected = ["(a", "a)", "[ab", r"a\", "*a", "a|+b", "a{", "a{x}", r"\p{Greek"];
+        let check = |pattern| macro_support::pattern_problem(pattern).is_none();
+        for pattern in accepted {
+            assert!(regex::Regex::new(pattern).is_ok(), "{}", pattern);
+            assert!(check(pattern), "{}", pattern);
+        }
+        for pattern in rejected {
+            assert!(regex::Regex::new(pattern).is_err(), "{}", pattern);
+            assert!(!check(pattern), "{}", pattern);
+        }
+        // Semantic errors are left for `rule` to panic on, as documented
+        for pattern in [r"\p{Klingon}", r"\q"] {
+            assert!(regex::Regex::new(pattern).is_err(), "{}", pattern);
+            assert!(check(pattern), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_forecast_linear_and_flat_series() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        clock.set(0);
+        assert_eq!(
+            healer.forecast(Axiom::Safety, Duration::from_secs(60)),
+            Forecast::InsufficientData { buckets: 0 }
+        );
+
+        // Safety rises by one per minute, Consistency stays at two per minute
+        for minute in 0..5 {
+            clock.set(minute * 60_000);
+            for _ in 0..=minute {
+                healer.monitor_and_heal_detailed("unsafe").unwrap();
+            }
+            for _ in 0..2 {
+                healer.monitor_and_heal_detailed("inconsistent").unwrap();
+            }
+            if minute == 2 {
+                assert_eq!(
+                    healer.forecast(Axiom::Safety, Duration::from_secs(60)),
+                    Forecast::InsufficientData { buckets: 2 }
+                );
+            }
+        }
+        clock.set(5 * 60_000);
+
+        // The next two buckets should hold 6 and 7
+        match healer.forecast(Axiom::Safety, Duration::from_secs(120)) {
+            Forecast::Projected { expected, low, high, slope_per_bucket, buckets } => {
+                assert!((expected - 13.0).abs() < 1e-9, "{}", expected);
+                assert!((slope_per_bucket - 1.0).abs() < 1e-9);
+                assert!((high - low).abs() < 1e-9);
+                assert_eq!(buckets, 5);
+            }
+            other => panic!("unexpected forecast {:?}", other),
+        }
+        match healer.forecast(Axiom::Consistency, Duration::from_secs(180)) {
+            Forecast::Projected { expected, low, high, .. } => {
+                assert!((expected - 6.0).abs() < 1e-9, "{}", expected);
+                assert!(low <= expected && expected <= high);
+            }
+            other => panic!("unexpected forecast {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_time_to_budget_exhaustion_follows_trend() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        clock.set(0);
+        assert_eq!(healer.time_to_budget_exhaustion(&Axiom::Safety), None);
+
+        // One-minute buckets; one unhealed Safety violation per bucket
+        healer.set_violation_budget(
+            Axiom::Safety,
+            10,
+            Duration::from_secs(3600),
+            BudgetAction::RecordOnly,
+        );
+        for minute in 0..4 {
+            clock.set(minute * 60_000);
+            healer.monitor_and_heal_detailed("unsafe").unwrap();
+        }
+        clock.set(4 * 60_000);
+
+        // Four in the window so far, plus one per projected minute
+        assert_eq!(
+            healer.time_to_budget_exhaustion(&Axiom::Safety),
+            Some(Duration::from_secs(6 * 60))
+        );
+    }
+
+    #[test]
+    fn test_debug_output_elides_large_contexts() {
+        let context = format!("unsafe {} secret-tail", "x".repeat(1 << 20));
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let report = healer.monitor_and_heal_detailed(&context).unwrap();
+        let violation = report.violations[0].clone();
+
+        let debug = format!("{:?}", violation);
+        assert!(!debug.contains(&context));
+        assert!(debug.len() < 512, "{}", debug);
+        assert!(debug.contains("unsafe xx") && debug.contains("…"));
+        assert!(debug.contains("secret-tail"));
+
+        let pretty = format!("{:#?}", report);
+        assert!(!pretty.contains(&context) && !pretty.contains(&report.context));
+        assert!(pretty.len() < 4096);
+        assert!(format!("{:?}", HealError::Cancelled { partial: Box::new(report.clone()) })
+            .len() < 4096);
+
+        assert!(format!("{:?}", violation.debug_full()).contains(&context));
+        assert!(format!("{:?}", report.debug_full()).contains(&report.context));
+        // Only the rendering changes; the data itself is untouched
+        assert_eq!(violation.context, context);
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&violation).unwrap();
+            let restored: Violation = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.context, context);
+            assert_eq!(restored, violation);
+        }
+    }
+
+    #[test]
+    fn test_self_check_flags_misconfiguration() {
+        let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        assert!(healer.self_check().is_empty());
+
+        let mut aar = AdaptiveAxiomaticRegularizer::new();
+        aar.add_rule(DetectionRule::new("", Axiom::Fairness, Severity::Low));
+        let mut healer = AxiomaticSelfHealer::new(aar);
+        healer.set_threshold_policy(ThresholdPolicy::HealAbove(100.0));
+        let codes: Vec<&str> = healer.self_check().iter().map(|w| w.code()).collect();
+        assert_eq!(codes, vec!["C003", "C004", "C005"]);
+
+        healer.mark_record_only(Axiom::Fairness);
+        healer.set_threshold_policy(ThresholdPolicy::HealAbove(0.5));
+        let warnings = healer.self_check();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].to_string(), "[C005] rule: matches every context");
+    }
+
+    #[test]
+    fn test_builder_strict_self_check() {
+        let lopsided = || {
+            let mut aar = AdaptiveAxiomaticRegularizer::new();
+            aar.add_rule(DetectionRule::new("biased", Axiom::Fairness, Severity::Low));
+            aar
+        };
+
+        let err = HealerBuilder::new(lopsided())
+            .with_self_check(SelfCheckMode::Strict)
+            .try_build()
+            .err()
+            .unwrap();
+        assert_eq!(err, vec![ConfigWarning::NoStrategies { axiom: Axiom::Fairness }]);
+
+        assert!(HealerBuilder::new(lopsided())
+            .with_self_check(SelfCheckMode::Strict)
+            .allow_warning("C003")
+            .try_build()
+            .is_ok());
+
+        let mut healer = HealerBuilder::new(lopsided())
+            .with_self_check(SelfCheckMode::Report)
+            .build();
+        assert_eq!(
+            healer.drain_events(),
+            vec![HealerEvent::ConfigWarning(ConfigWarning::NoStrategies {
+                axiom: Axiom::Fairness
+            })]
+        );
+    }
+
+    #[test]
+    fn test_latency_histogram_bucket_boundaries() {
+        let ms = Duration::from_millis;
+        let histogram = LatencyHistogram::new(vec![ms(1), ms(10)].into());
+        for elapsed in [Duration::ZERO, ms(1), ms(1) + Duration::from_nanos(1), ms(10), ms(11)] {
+            histogram.record(elapsed);
+        }
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.cumulative_counts, vec![2, 4, 5]);
+        assert_eq!(snapshot.count(), 5);
+        assert_eq!(snapshot.sum, ms(23) + Duration::from_nanos(1));
+    }
+
+    #[test]
+    fn test_heal_metrics_split_clean_and_axiom_latency() {
+        let mut healer = HealerBuilder::new(AdaptiveAxiomaticRegularizer::new())
+            .with_latency_buckets(vec![Duration::from_secs(60)])
+            .build();
+        healer.monitor_and_heal_detailed("fine").unwrap();
+        healer.monitor_and_heal_detailed("unsafe and inconsistent").unwrap();
+
+        let metrics = healer.metrics();
+        assert_eq!(metrics.clean.cumulative_counts, vec![1, 1]);
+        assert_eq!(metrics.by_axiom.keys().collect::<Vec<_>>(), vec![&Axiom::Safety]);
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE selfheal_heal_duration_seconds histogram"));
+        let bucket = "selfheal_heal_duration_seconds_bucket{axiom=\"Safety\",le=\"60\"} 1";
+        assert!(text.contains(bucket));
+        assert!(text.contains("selfheal_clean_duration_seconds_bucket{le=\"+Inf\"} 1"));
+        assert!(text.contains("selfheal_clean_duration_seconds_count 1"));
+    }
+
+    struct Garbage(&'static str, fn(&str) -> String, StrategyContract);
+
+    impl CustomStrategy for Garbage {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn apply(&self, context: &str, _violation: &Violation) -> Result<String, String> {
+            Ok((self.1)(context))
+        }
+
+        fn contract(&self) -> StrategyContract {
+            self.2.clone()
+        }
+    }
+
+    fn heal_with_garbage(garbage: Garbage, context: &str) -> (HealReport, StrategyStats) {
+        let name = garbage.0;
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_contract_enforcement(ContractEnforcement::Report);
+        healer.correction_strategies.insert(
+            Axiom::Safety,
+            vec![CorrectionStrategy::custom(garbage), CorrectionStrategy::Rollback],
+        );
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        (report, healer.strategy_statistics()[name].clone())
+    }
+
+    #[test]
+    fn test_strategy_contract_rejects_each_kind_of_garbage() {
+        let strict = StrategyContract::default();
+        let cases: [(Garbage, &str); 4] = [
+            (Garbage("empty", |_| String::new(), strict.clone()), "output is empty"),
+            (Garbage("nul", |c| format!("{}\0", c), strict.clone()), "output contains NUL"),
+            (
+                Garbage("explode", |c| c.repeat(1000), strict.clone()),
+                "output grew from 6 to 6000 bytes, over the budget of 1024",
+            ),
+            (
+                Garbage("marker", |c| format!("[FIXED] {}", c), strict.clone()),
+                "output adds marker [FIXED] without declaring markers",
+            ),
+        ];
+        for (garbage, expected) in cases {
+            let (report, stats) = heal_with_garbage(garbage, "unsafe");
+            let attempts = &report.attempts[0].attempts;
+            assert_eq!(attempts[0].result, Err(format!("contract violation: {}", expected)));
+            assert_eq!(report.attempts[0].chosen, Some("rollback"));
+            assert_eq!(stats.contract_violations, 1);
+        }
+    }
+
+    #[test]
+    fn test_strategy_contract_can_be_relaxed() {
+        let contract = StrategyContract {
+            delete_at_or_above: Some(Severity::Critical),
+            emits_markers: true,
+            ..StrategyContract::default()
+        };
+        let garbage = Garbage("delete", |_| String::new(), contract);
+        let (report, _) = heal_with_garbage(garbage, "unsafe");
+        assert_eq!(report.attempts[0].chosen, Some("delete"));
+
+        let marks = StrategyContract { emits_markers: true, ..StrategyContract::default() };
+        let garbage = Garbage("mark", |c| format!("[FIXED] {}", c), marks);
+        assert_eq!(heal_with_garbage(garbage, "unsafe").0.context, "[FIXED] unsafe");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "strategy contract broken by empty: output is empty")]
+    fn test_strategy_contract_panics_in_debug_builds() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let garbage = Garbage("empty", |_| String::new(), StrategyContract::default());
+        healer
+            .correction_strategies
+            .insert(Axiom::Safety, vec![CorrectionStrategy::custom(garbage)]);
+        let _ = healer.monitor_and_heal_detailed("unsafe");
+    }
+
+    #[test]
+    fn test_import_json_lines_tolerates_messy_rows() {
+        use import::{FieldMapping, RowError, TimestampFormat};
+
+        let fixture = concat!(
+            r#"{"kind":"safety","level":"sev1","at":"2024-03-01T12:00:00.250Z","#,
+            r#""msg":"leaked key"}"#,
+            "\n\n",
+            r#"{"kind":"Consistency","level":"High","at":"2024-02-29T00:00:00Z","#,
+            r#""msg":"a \"b\" \u00e9"}"#,
+            "\n",
+            r#"{"kind":"safety","at":"2024-03-01T12:00:00Z","msg":"no level"}"#,
+            "\n",
+            r#"{"kind":"safety","level":"catastrophic","at":"2024-03-01T12:00:00Z","#,
+            r#""msg":"x"}"#,
+            "\n",
+            r#"{"kind":"safety","level":"sev1","at":"2023-02-29T00:00:00Z","msg":"x"}"#,
+            "\n",
+            r#"{"kind":"safety", oops}"#,
+            "\n",
+            r#"{"kind":"Fairness","level":"Low","at":"2024-03-01T12:00:00Z","msg":"#,
+            r#""0123456789012345678901234567890123456789012345678901234567890123456789"}"#,
+            "\n",
+        );
+        let mapping = FieldMapping::new("kind", "level", "at", "msg")
+            .axiom_value("safety", Axiom::Safety)
+            .severity_value("sev1", Severity::Critical)
+            .timestamp_format(TimestampFormat::Pattern("%Y-%m-%dT%H:%M:%S.%fZ".to_string()));
+        // Only the first row has fractional seconds
+        let imported = import::from_json_lines(fixture.as_bytes(), &mapping).unwrap();
+        assert_eq!(imported.violations.len(), 1);
+        assert_eq!(imported.violations[0].timestamp, 1_709_294_400_250);
+
+        let mapping = mapping.timestamp_format(TimestampFormat::Pattern(
+            "%Y-%m-%dT%H:%M:%SZ".to_string(),
+        ));
+        let imported = import::from_json_lines(fixture.as_bytes(), &mapping).unwrap();
+        let summary = &imported.summary;
+        assert_eq!((summary.imported, summary.skipped), (2, 5));
+        let reasons: Vec<&RowError> = summary.errors.iter().collect();
+        assert_eq!(reasons[0].line, 1);
+        assert_eq!(reasons[0].reason, "bad timestamp '2024-03-01T12:00:00.250Z'");
+        assert_eq!(reasons[1].reason, "missing field 'level'");
+        assert_eq!(reasons[2].reason, "unknown severity 'catastrophic'");
+        assert_eq!(reasons[3].reason, "bad timestamp '2023-02-29T00:00:00Z'");
+        assert_eq!(reasons[4].line, 7);
+        assert!(reasons[4].reason.starts_with("invalid JSON: expected a string"));
+
+        let consistency = &imported.violations[0];
+        assert_eq!(consistency.context, "a \"b\" é");
+        assert_eq!(consistency.timestamp, 1_709_164_800_000);
+        assert_eq!(consistency.metadata["source"], "import");
+        // The default quote policy elides long contexts to 64 chars
+        assert_eq!(imported.violations[1].context.chars().count(), 64);
+    }
+
+    #[test]
+    fn test_import_csv_with_quotes_caps_and_redaction() {
+        use import::{FieldMapping, TimestampFormat};
+
+        let fixture = "axiom,severity,ts,context\n\
+                       Safety,Critical,1700000000,\"multi\nline, \"\"quoted\"\"\"\n\
+                       Safety,Critical,not-a-time,x\n\
+                       Safety,Critical\n\
+                       Safety,Critical,1700000001,\n\
+                       \n\
+                       Fairness,Low,1700000002.5,plain\n";
+        let mut mapping = FieldMapping::new("axiom", "severity", "ts", "context")
+            .timestamp_format(TimestampFormat::EpochSeconds)
+            .quote_policy(QuotePolicy { max_len: None, redact: true });
+        mapping.max_errors = 2;
+
+        let imported = import::from_csv(fixture.as_bytes(), &mapping).unwrap();
+        assert_eq!((imported.summary.imported, imported.summary.skipped), (2, 3));
+        assert_eq!(imported.summary.errors.len(), 2);
+        assert_eq!(imported.summary.errors[0].line, 4);
+        assert_eq!(imported.summary.errors[1].reason, "expected 4 fields, found 2");
+
+        assert_eq!(imported.violations[0].context, "<redacted 20 chars>");
+        assert_eq!(imported.violations[0].timestamp, 1_700_000_000_000);
+        assert_eq!(imported.violations[1].timestamp, 1_700_000_002_500);
+    }
+
+    #[test]
+    fn test_context_tracker_lifecycle_and_regression() {
+        use ContextState::*;
+
+        let (mut healer, clock) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealAbove(0.5));
+        healer
+            .correction_strategies
+            .insert(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+        let doc = |text| Context::new("doc-1", text);
+
+        healer.monitor_and_heal_ctx(&doc("fine")).unwrap();
+        assert_eq!(healer.context_tracker().state("doc-1"), Some(Clean));
+        assert!(healer.drain_events().is_empty());
+
+        let healed = healer.monitor_and_heal_ctx(&doc("inconsistent")).unwrap().context;
+        assert_eq!(healer.context_tracker().state("doc-1"), Some(Healed));
+        // The healed text staying clean keeps the document healed
+        healer.monitor_and_heal_ctx(&doc(&healed)).unwrap();
+        assert_eq!(healer.context_tracker().state("doc-1"), Some(Healed));
+
+        healer.set_threshold_policy(ThresholdPolicy::Never);
+        clock.advance(Duration::from_secs(5));
+        healer.monitor_and_heal_ctx(&doc("unsafe again")).unwrap();
+        assert_eq!(healer.context_tracker().state("doc-1"), Some(Regressed));
+
+        let path: Vec<(ContextState, ContextState)> = healer
+            .context_tracker()
+            .history("doc-1")
+            .iter()
+            .map(|t| (t.from, t.to))
+            .collect();
+        assert_eq!(
+            path,
+            vec![(Clean, Violating), (Violating, Healing), (Healing, Healed), (Healed, Regressed)]
+        );
+        let events = healer.drain_events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[3],
+            HealerEvent::ContextTransition(ContextTransition {
+                key: "doc-1".to_string(),
+                from: Healed,
+                to: Regressed,
+                timestamp: 1_005_000,
+            })
+        );
+        assert_eq!(healer.context_tracker().keys_in(Regressed), vec!["doc-1"]);
+
+        healer.context_tracker_mut().set_history_capacity(2);
+        assert_eq!(healer.context_tracker().history("doc-1").len(), 2);
+    }
+
+    #[test]
+    fn test_context_tracker_eviction_and_snapshot() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        healer.monitor_and_heal_ctx(&Context::new("old", "unsafe")).unwrap();
+        clock.advance(Duration::from_secs(60));
+        healer.monitor_and_heal_ctx(&Context::new("a \\ key\nwith newline", "unsafe")).unwrap();
+
+        let snapshot = healer.snapshot();
+        let decoded = HealerSnapshot::decode(&snapshot.encode()).unwrap();
+        assert_eq!(decoded, snapshot);
+        let mut restored = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        restored.restore(&decoded);
+        assert_eq!(restored.context_tracker(), healer.context_tracker());
+
+        assert_eq!(healer.evict_stale_contexts(Duration::from_secs(30)), 1);
+        assert_eq!(healer.context_tracker().state("old"), None);
+        assert_eq!(healer.context_tracker().len(), 1);
+    }
+
+    #[test]
+    fn test_violation_json_schema_checks_and_migrations() {
+        use schema::{ArtifactError, Migrations, SchemaError, SchemaId};
+
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.monitor_and_heal_detailed("unsafe \"quoted\"\nline").unwrap();
+        let original = healer.regularizer().drain_history().remove(0);
+
+        let line = original.to_json();
+        assert!(line.starts_with(r#"{"schema":"aar.violation.v1","#));
+        assert_eq!(Violation::from_json(&line).unwrap(), original);
+
+        // Lines from before schema ids go through the built-in no-op migration
+        let legacy = r#"{"axiom":"Safety","severity":"Low","timestamp":5,"context":"x"}"#;
+        assert_eq!(Violation::from_json(legacy).unwrap().severity, Severity::Low);
+
+        let future = line.replace("aar.violation.v1", "aar.violation.v9");
+        let expected = SchemaError {
+            found: "aar.violation.v9".to_string(),
+            supported: schema::VIOLATION,
+        };
+        assert_eq!(Violation::from_json(&future), Err(ArtifactError::Schema(expected)));
+        let wrong_kind = line.replace("aar.violation.v1", "aar.snapshot.v1");
+        assert!(matches!(Violation::from_json(&wrong_kind), Err(ArtifactError::Schema(_))));
+
+        let target = SchemaId { kind: "test.doc", version: 2 };
+        let mut migrations = Migrations::default();
+        assert!(migrations.migrate("test.doc.v1", target, "a".to_string()).is_err());
+        migrations.register("test.doc", 1, |text| text + "+v2");
+        assert_eq!(migrations.migrate("test.doc.v1", target, "a".to_string()).unwrap(), "a+v2");
+        assert_eq!(migrations.migrate("test.doc.v2", target, "a".to_string()).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_history_export_round_trips_through_import() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.monitor_and_heal_detailed("unsafe and inconsistent").unwrap();
+        let mut file = Vec::new();
+        assert_eq!(healer.regularizer().export_history(&mut file).unwrap(), 2);
+        file.extend_from_slice(b"{\"schema\":\"aar.violation.v2\"}\n");
+
+        let imported =
+            import::from_history_jsonl(file.as_slice(), &schema::Migrations::builtin()).unwrap();
+        assert_eq!(imported.summary.imported, 2);
+        assert_eq!(imported.summary.errors[0].line, 3);
+        assert_eq!(
+            imported.summary.errors[0].reason,
+            "Unsupported schema 'aar.violation.v2' (supported: aar.violation.v1)"
+        );
+        assert_eq!(imported.violations, healer.regularizer().drain_history());
+    }
+
+    #[test]
+    fn test_detection_sampling_fixed_policies_and_bypass() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_detection_sampling(Some(DetectionSampling::EveryNth(3)));
+        let checked: Vec<bool> =
+            (0..7).map(|i| healer.audit(&format!("m{}", i)).is_some()).collect();
+        assert_eq!(checked, vec![true, false, false, true, false, false, true]);
+
+        // A context seen violating is always checked afterwards
+        healer.set_detection_sampling(Some(DetectionSampling::EveryNth(1000)));
+        assert_eq!(healer.audit("unsafe").unwrap().len(), 1);
+        healer.audit("other");
+        assert_eq!(healer.audit("unsafe").unwrap().len(), 1);
+        let stats = healer.sampling_statistics().unwrap();
+        assert_eq!((stats.seen, stats.checked, stats.bypassed), (3, 2, 1));
+        assert_eq!(stats.estimated_violating, 1000.0 + 1.0);
+
+        let run = |seed| {
+            let mut sampler = DetectionSampler::new(DetectionSampling::Probabilistic {
+                rate: 0.25,
+                seed,
+            });
+            (0..10_000)
+                .map(|i| sampler.decide(&i.to_string()) != SampleDecision::Skip)
+                .collect::<Vec<_>>()
+        };
+        let first = run(7);
+        assert_eq!(first, run(7));
+        assert_ne!(first, run(8));
+        let checked = first.iter().filter(|c| **c).count();
+        assert!((2300..2700).contains(&checked), "{}", checked);
+    }
+
+    #[test]
+    fn test_adaptive_sampling_ramps_up_on_burst_and_decays() {
+        let mut sampler = DetectionSampler::new(DetectionSampling::Adaptive(AdaptiveSampling {
+            base_rate: 0.05,
+            max_rate: 1.0,
+            threshold: 0.2,
+            window: 10,
+            decay: 0.9,
+            seed: 42,
+        }));
+        let feed = |sampler: &mut DetectionSampler, context: String, violating: bool| {
+            let decision = sampler.decide(&context);
+            if decision != SampleDecision::Skip {
+                sampler.record(&context, decision, violating);
+            }
+        };
+
+        for i in 0..500 {
+            feed(&mut sampler, format!("clean {}", i), false);
+        }
+        assert_eq!(sampler.rate(), 0.05);
+
+        let ramped = (0..300).position(|i| {
+            feed(&mut sampler, format!("burst {}", i), true);
+            sampler.rate() == 1.0
+        });
+        assert!(ramped.is_some(), "rate only reached {}", sampler.rate());
+
+        let decayed = (0..1000).position(|i| {
+            feed(&mut sampler, format!("calm {}", i), false);
+            sampler.rate() == 0.05
+        });
+        assert!(decayed.is_some(), "rate stuck at {}", sampler.rate());
+        let stats = sampler.stats();
+        assert_eq!(stats.seen, stats.checked + stats.sampled_out);
+    }
+
+    struct SlowSink {
+        delay: Duration,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ViolationSink for SlowSink {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn deliver(&mut self, violation: &Violation) -> std::io::Result<()> {
+            std::thread::sleep(self.delay);
+            self.received.lock().unwrap().push(violation.context.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_shutdown_reports_undelivered_sink_queue() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        let sink = SlowSink { delay: Duration::from_millis(20), received: received.clone() };
+        healer.add_sink(sink, 8);
+        for i in 0..10 {
+            healer.audit(&format!("unsafe {}", i));
+        }
+
+        let report = healer.shutdown(Duration::from_millis(70));
+        let delivered = received.lock().unwrap().len();
+        assert!(report.timed_out);
+        assert!(delivered > 0 && delivered < 8, "{}", delivered);
+        assert_eq!(report.delivered(), delivered);
+        assert_eq!(report.sinks[0].overflowed, 2);
+        assert_eq!(report.sinks[0].pending, 8 - delivered);
+        assert_eq!(report.dropped(), 10 - delivered);
+        assert_eq!(report.snapshot, None);
+        // The two overflowed violations were the oldest
+        assert_eq!(received.lock().unwrap()[0], "unsafe 2");
+    }
+
+    #[test]
+    fn test_compare_classifies_by_fingerprint_without_recording() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.add_rule(DetectionRule::new("unclear", Axiom::Transparency, Severity::Low));
+        let healer = AxiomaticSelfHealer::new(regularizer);
+
+        let report = healer.compare(
+            "inconsistent and unsafe",
+            "rewritten: unsafe but unclear",
+        );
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!(report.fixed[0].axiom, Axiom::Consistency);
+        assert_eq!(report.persisting.len(), 1);
+        assert_eq!(report.introduced.len(), 1);
+        assert_eq!(report.introduced[0].axiom, Axiom::Transparency);
+        let per_axiom: f64 = report.per_axiom.values().map(AxiomDelta::delta).sum();
+        assert!((report.penalty_delta() - per_axiom).abs() < 1e-9);
+        assert_eq!(report.per_axiom[&Axiom::Safety].delta(), 0.0);
+        assert_eq!(report.per_axiom[&Axiom::Consistency].after, 0.0);
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "fixed 1 (consistency), introduced 1 (transparency), persisting 1 (safety), \
+                 penalty {:.1} → {:.1}",
+                report.penalty_before, report.penalty_after
+            )
+        );
+        assert!(report.to_json().starts_with("{\"schema\":\"aar.comparison.v1\""));
+        assert!(healer.regularizer().drain_history().is_empty());
+    }
+
+    #[test]
+    fn test_disabled_axiom_is_excluded_and_restored() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.regularizer.add_rule(DetectionRule::new("", Axiom::Fairness, Severity::Low));
+        healer.regularizer.disable_axiom(Axiom::Fairness);
+        healer.regularizer.disable_axiom(Axiom::Safety);
+        assert_eq!(
+            healer.regularizer.enabled_axioms().into_iter().collect::<Vec<_>>(),
+            vec![Axiom::Consistency, Axiom::Completeness, Axiom::Transparency]
+        );
+        assert!(healer.regularizer.detect_violations("unsafe").is_empty());
+        assert!(healer.self_check().is_empty(), "{:?}", healer.self_check());
+
+        let violation = |axiom| Violation {
+            axiom,
+            severity: Severity::High,
+            context: "external".to_string(),
+            timestamp: 0,
+            metadata: BTreeMap::new(),
+        };
+        healer.regularizer.record_violation(violation(Axiom::Safety));
+        healer.regularizer.record_violation(violation(Axiom::Consistency));
+        assert_eq!(healer.regularizer.ignored_counts()[&Axiom::Safety], 1);
+        healer.regularizer.set_disabled_axiom_policy(DisabledAxiomPolicy::Reject);
+        assert_eq!(
+            healer.regularizer.try_record_violation(violation(Axiom::Safety)),
+            Err(DisabledAxiom { axiom: Axiom::Safety })
+        );
+        let statistics = healer.get_statistics();
+        assert_eq!(statistics.total, 1);
+        assert!(!statistics.by_axiom.contains_key(&Axiom::Safety));
+
+        healer.regularizer.enable_axiom(&Axiom::Safety);
+        assert_eq!(healer.regularizer.weight(&Axiom::Safety), Some(1.5));
+        assert_eq!(
+            healer.correction_strategies[&Axiom::Safety],
+            vec![CorrectionStrategy::Rollback, CorrectionStrategy::QueryUser]
+        );
+        let report = healer.monitor_and_heal_detailed("unsafe").unwrap();
+        assert_eq!(report.violations[0].axiom, Axiom::Safety);
+    }
+
+    #[test]
+    fn test_self_report_deltas_concerns_and_schedule() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.correction_strategies.insert(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        for _ in 0..10 {
+            let _ = healer.monitor_and_heal_detailed("unsafe");
+        }
+        healer.regularizer.update_weights(Axiom::Fairness, 1e6);
+        assert_eq!(healer.regularizer.weight_pinned_since(&Axiom::Fairness), Some(1_000_000));
+
+        let first = healer.self_report();
+        assert_eq!((first.since, first.heal_calls_delta), (None, 10));
+        assert_eq!(first.heal_rates[&Axiom::Safety].total, HealTally { attempted: 10, healed: 0 });
+        assert_eq!(first.strategy_deltas["query_user"].failures, 10);
+        let codes: Vec<&str> = first.concerns.iter().map(Concern::code).collect();
+        assert_eq!(codes, vec!["low_heal_rate"]);
+
+        clock.advance(Duration::from_secs(25 * 60 * 60));
+        let second = healer.self_report();
+        assert_eq!((second.since, second.heal_calls_delta), (Some(1_000_000), 0));
+        assert!(second.strategy_deltas.is_empty());
+        assert_eq!(second.heal_rates[&Axiom::Safety].recent, HealTally::default());
+        assert_eq!(second.weights[&Axiom::Fairness].delta, 0.0);
+        assert!(second.to_string().contains("[weight_pinned] Fairness weight pinned at 10"));
+        assert!(second.to_json().starts_with("{\"schema\":\"aar.self_report.v1\""));
+
+        healer.set_self_report_schedule(Some(SelfReportSchedule::EveryCalls(3)));
+        healer.drain_events();
+        for _ in 0..7 {
+            let _ = healer.monitor_and_heal_detailed("fine");
+        }
+        let published = healer
+            .drain_events()
+            .into_iter()
+            .filter(|event| matches!(event, HealerEvent::SelfReport(_)))
+            .count();
+        assert_eq!(published, 2);
+    }
+
+    #[test]
+    fn test_known_clean_filter_skips_repeats_until_rules_change() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_known_clean_filter(Some(KnownCleanConfig {
+            verify_sample_rate: 0.0,
+            ..KnownCleanConfig::default()
+        }));
+        let first = healer.monitor_and_heal_detailed("templated answer").unwrap();
+        assert!(!first.skipped_known_clean);
+        let repeat = healer.monitor_and_heal_detailed("templated answer").unwrap();
+        assert!(repeat.skipped_known_clean);
+        assert_eq!(repeat.outcome, HealOutcome::Clean);
+
+        // "Clean" was relative to the old rules
+        let rule = DetectionRule::new("templated", Axiom::Fairness, Severity::Low);
+        healer.regularizer.add_rule(rule);
+        let after = healer.monitor_and_heal_detailed("templated answer").unwrap();
+        assert!(!after.skipped_known_clean);
+        assert_eq!(after.violations.len(), 1);
+
+        let stats = healer.known_clean_statistics().unwrap().clone();
+        assert_eq!((stats.hits, stats.skipped, stats.inserted, stats.invalidations), (1, 1, 1, 1));
+        assert!(healer
+            .metrics()
+            .render_prometheus()
+            .contains("selfheal_known_clean_total{kind=\"skip\"} 1\n"));
+
+        // With every hit re-verified, a stale entry raises an alarm instead of a skip
+        healer.set_known_clean_filter(Some(KnownCleanConfig {
+            verify_sample_rate: 1.0,
+            ..KnownCleanConfig::default()
+        }));
+        healer.monitor_and_heal_detailed("plain answer").unwrap();
+        let filter = healer.known_clean.as_mut().unwrap();
+        filter.bloom.insert(fingerprint("unsafe answer"));
+        let report = healer.monitor_and_heal_detailed("unsafe answer").unwrap();
+        assert!(!report.skipped_known_clean);
+        assert_eq!(healer.known_clean_statistics().unwrap().false_positives, 1);
+        assert!(healer
+            .drain_events()
+            .iter()
+            .any(|event| matches!(event, HealerEvent::KnownCleanFalsePositive { .. })));
+    }
+
+    #[test]
+    fn test_sentence_strategies_rewrite_only_the_violating_sentence() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        let rule = DetectionRule::new("true. It is", Axiom::Consistency, Severity::Medium);
+        regularizer.add_rule(rule);
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        let excise = |healer: &AxiomaticSelfHealer, context: &str| {
+            let violation = healer.regularizer.detect_violations(context).remove(0);
+            healer.apply_strategy(&CorrectionStrategy::ExciseSentence, context, &violation)
+        };
+
+        let cases = [
+            ("First point. This is inconsistent. Last point.", "First point. Last point."),
+            ("This is inconsistent. Then more.", "Then more."),
+            ("Fine here. Now inconsistent!", "Fine here."),
+            // Spanning two sentences removes both
+            ("Start. It is true. It is also false. End.", "Start. End."),
+            (
+                "Use tools, e.g. hammers, with care. This is inconsistent. Done.",
+                "Use tools, e.g. hammers, with care. Done.",
+            ),
+            ("Intro.\n\nThis is inconsistent. Kept.\n\nOutro.", "Intro.\n\nKept.\n\nOutro."),
+            ("Intro.\n\nWholly inconsistent.\n\nOutro.", "Intro.\n\nOutro."),
+            ("Intro. (Quite \"inconsistent.\") Outro.", "Intro. Outro."),
+            ("良い。これは inconsistent です。終わり。", "良い。終わり。"),
+        ];
+        for (context, expected) in cases {
+            assert_eq!(excise(&healer, context).as_deref(), Ok(expected), "{:?}", context);
+        }
+        assert!(excise(&healer, "Only inconsistent.").is_err());
+
+        let replace = CorrectionStrategy::ReplaceSentence { template: "[{axiom} removed]".into() };
+        let context = "Keep this. This is inconsistent.\nNext line.";
+        let violation = healer.regularizer.detect_violations(context).remove(0);
+        assert_eq!(
+            healer.apply_strategy(&replace, context, &violation).unwrap(),
+            "Keep this. [consistency removed]\nNext line."
+        );
+
+        // Without a span the strategy fails and the chain moves on
+        let mut spanless = violation.clone();
+        spanless.metadata.remove("span");
+        let strategy = CorrectionStrategy::ExciseSentence;
+        assert!(healer.apply_strategy(&strategy, context, &spanless).is_err());
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.correction_strategies.insert(
+            Axiom::Consistency,
+            vec![CorrectionStrategy::ExciseSentence, CorrectionStrategy::Recompute],
+        );
+        let report = healer.monitor_and_heal_detailed("Only inconsistent.").unwrap();
+        assert_eq!(report.context, "Only consistent.");
+        assert_eq!(report.attempts[0].attempts.len(), 2);
+    }
+
+    #[test]
+    fn test_healing_reader_heals_lines_as_read() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.correction_strategies.insert(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        let oversized = format!("{} inconsistent {}", "a".repeat(50_000), "b".repeat(50_000));
+        let input = format!(
+            "clean line\nthis is inconsistent\r\nunsafe op\n{}\nlast inconsistent",
+            oversized
+        );
+        // A tiny buffer forces every line to span several fills of the inner reader
+        let source = std::io::BufReader::with_capacity(16, std::io::Cursor::new(input));
+        let notice = FailedLinePolicy::Notice("[line withheld]".to_string());
+        let mut reader = HealingReader::new(source, &mut healer, notice);
+        let mut output = String::new();
+        reader.read_to_string(&mut output).unwrap();
+        assert_eq!(reader.failed_lines(), 1);
+
+        let expected = format!(
+            "clean line\n[ROLLED_BACK] this is inconsistent\r\n[line withheld]\n\
+             [ROLLED_BACK] {}\n[ROLLED_BACK] last inconsistent",
+            oversized
+        );
+        assert_eq!(output, expected);
+        let statistics = healer.get_statistics();
+        assert_eq!(statistics.by_axiom[&Axiom::Consistency], 3);
+        assert_eq!(statistics.by_axiom[&Axiom::Safety], 1);
+
+        let source = std::io::Cursor::new("unsafe\nkept\n");
+        let mut reader = HealingReader::new(source, &mut healer, FailedLinePolicy::Drop);
+        let lines: Vec<String> = reader.by_ref().lines().map(Result::unwrap).collect();
+        assert_eq!(lines, vec!["kept"]);
+    }
+
+    #[test]
+    fn test_audit_sampling_proportions_spill_and_reviews() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.add_rule(DetectionRule::new("vague", Axiom::Completeness, Severity::Medium));
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_audit_sampler(Some(AuditSampler::new(7, |axiom, severity| {
+            match (axiom, severity) {
+                (Axiom::Safety, Severity::Critical) => 1.0,
+                (_, Severity::Medium) => 0.05,
+                _ => 0.0,
+            }
+        })));
+        healer.audit_queue().set_capacity(100_000);
+
+        let runs = 10_000;
+        for _ in 0..runs {
+            healer.monitor_and_heal_detailed("unsafe").unwrap();
+            healer.monitor_and_heal_detailed("vague").unwrap();
+            healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        }
+        let items = healer.audit_queue().drain(usize::MAX);
+        let sampled = |axiom: Axiom| {
+            items.iter().filter(|item| item.report.violations[0].axiom == axiom).count()
+        };
+        assert_eq!(sampled(Axiom::Safety), runs);
+        assert_eq!(sampled(Axiom::Consistency), 0);
+        // Binomial(10_000, 0.05): mean 500, sd about 22
+        let medium = sampled(Axiom::Completeness);
+        assert!((410..=590).contains(&medium), "{}", medium);
+
+        let queue = healer.audit_queue();
+        assert!(queue.mark_reviewed(items[0].id, AuditVerdict::Approved));
+        assert!(!queue.mark_reviewed(items[0].id, AuditVerdict::Approved));
+        let reason = "over-corrected".to_string();
+        assert!(queue.mark_reviewed(items[1].id, AuditVerdict::Rejected { reason }));
+        let reviews = queue.take_reviews();
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(reviews[0].axioms, vec![Axiom::Safety]);
+
+        let dir = temp_dir("audit-spill");
+        std::fs::create_dir_all(&dir).unwrap();
+        let spill = dir.join("audit.jsonl");
+        queue.set_capacity(2);
+        queue.spill_to_file(&spill).unwrap();
+        for _ in 0..5 {
+            healer.monitor_and_heal_detailed("unsafe").unwrap();
+        }
+        assert_eq!((healer.audit_queue().len(), healer.audit_queue().spilled()), (2, 3));
+        let lines = std::fs::read_to_string(&spill).unwrap();
+        assert_eq!(lines.lines().count(), 3);
+        assert!(lines.starts_with("{\"schema\":\"aar.audit.v1\""));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_coverage_matrix_flags_lopsided_configuration() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.regularizer.add_rule(DetectionRule::new("biased", Axiom::Fairness, Severity::Low));
+        healer.regularizer.add_rule(DetectionRule::new("unfair", Axiom::Fairness, Severity::High));
+        healer
+            .correction_strategies
+            .insert(Axiom::Transparency, vec![CorrectionStrategy::Interpolate]);
+        healer.restrict_axiom_to_roles(Axiom::Consistency, ["assistant"]);
+        healer.regularizer.disable_axiom(Axiom::Completeness);
+
+        let matrix = healer.coverage_matrix();
+        assert!(matrix.row(&Axiom::Completeness).is_none());
+        let fairness = matrix.row(&Axiom::Fairness).unwrap();
+        assert_eq!(fairness.flags, vec![CoverageFlag::NoStrategies]);
+        assert_eq!(fairness.handling, Handling::Unhandled);
+        assert_eq!(fairness.severity_floor, Some(Severity::Low));
+        assert_eq!(fairness.detectors.len(), 2);
+        let transparency = matrix.row(&Axiom::Transparency).unwrap();
+        assert_eq!(transparency.flags, vec![CoverageFlag::NoDetectors]);
+        assert_eq!(matrix.row(&Axiom::Consistency).unwrap().suppressions, vec!["roles: assistant"]);
+        assert_eq!(matrix.flagged().count(), 2);
+
+        let table = matrix.to_string();
+        let header = table.lines().next().unwrap();
+        let fairness_line = table.lines().find(|l| l.starts_with("Fairness")).unwrap();
+        assert_eq!(header.find("detectors"), fairness_line.find("rule:biased"));
+        assert!(fairness_line.ends_with("no-strategies"));
+        assert!(matrix.to_json().starts_with("{\"schema\":\"aar.coverage.v1\""));
+
+        // self_check reports the same gap, and only the one that matters
+        let codes: Vec<&str> = healer.self_check().iter().map(ConfigWarning::code).collect();
+        assert_eq!(codes, vec!["C003"]);
+    }
+
+    #[test]
+    fn test_ingest_feedback_joins_verdicts_to_audited_heals() {
+        let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        healer.set_audit_sampler(Some(AuditSampler::new(1, |_, _| 1.0)));
+        for context in ["unsafe a", "unsafe b", "inconsistent c", "inconsistent d"] {
+            healer.monitor_and_heal_detailed(context).unwrap();
+        }
+        let exported = healer.audit_queue().drain(4);
+        assert!(exported.iter().all(|item| item.to_json().contains("\"id\":")));
+        let ids: Vec<u64> = exported.iter().map(|item| item.id).collect();
+        let (safety, consistency) = (
+            healer.regularizer.weight(&Axiom::Safety).unwrap(),
+            healer.regularizer.weight(&Axiom::Consistency).unwrap(),
+        );
+
+        let verdicts = format!(
+            "{{\"heal_id\":{},\"verdict\":\"good\"}}\n\
+             {{\"heal_id\":{},\"verdict\":\"approved\"}}\n\
+             {{\"heal_id\":{},\"verdict\":\"bad\",\"corrected_text\":\"c, fixed\"}}\n\
+             {{\"heal_id\":{},\"verdict\":\"good\"}}\n\
+             {{\"heal_id\":999,\"verdict\":\"good\"}}\n\
+             {{\"heal_id\":{},\"verdict\":\"meh\"}}\n",
+            ids[0], ids[1], ids[2], ids[0], ids[3]
+        );
+        let summary = healer
+            .ingest_feedback(verdicts.as_bytes(), FeedbackFormat::JsonLines)
+            .unwrap();
+        assert_eq!(summary.applied, 3);
+        assert_eq!(summary.duplicates, vec![(4, ids[0])]);
+        assert_eq!(summary.unknown_ids, vec![(5, 999)]);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.feedback[&Axiom::Safety], 2.0);
+        assert!(healer.regularizer.weight(&Axiom::Safety).unwrap() > safety);
+        assert!(healer.regularizer.weight(&Axiom::Consistency).unwrap() < consistency);
+        let example = &healer.labeled_examples()[0];
+        assert_eq!(example.original, "inconsistent c");
+        assert_eq!(example.corrected, "c, fixed");
+
+        let csv = format!("heal_id,verdict,reason\n{},reject,\"too blunt, lost detail\"\n", ids[3]);
+        let summary = healer.ingest_feedback(csv.as_bytes(), FeedbackFormat::Csv).unwrap();
+        assert_eq!(summary.feedback[&Axiom::Consistency], -1.0);
+        let reviews = healer.audit_queue().take_reviews();
+        assert_eq!(
+            reviews.last().unwrap().verdict,
+            AuditVerdict::Rejected { reason: "too blunt, lost detail".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_severity_expr_decides_severity_per_match() {
+        let rule = DetectionRule::new("unsafe", Axiom::Safety, Severity::Low)
+            .with_severity_expr(
+                "if position_ratio >= 0.5 or match_count > 1 then critical else medium",
+            )
+            .unwrap();
+        assert_eq!(rule.severities(), BTreeSet::from([Severity::Medium, Severity::Critical]));
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.rules.clear();
+        regularizer.add_rule(rule);
+        let severity = |context: &str| regularizer.detect_violations(context)[0].severity;
+        assert_eq!(severity("unsafe, in the opening"), Severity::Medium);
+        assert_eq!(severity("in conclusion: unsafe"), Severity::Critical);
+        assert_eq!(severity("unsafe and unsafe again"), Severity::Critical);
+
+        use severity_expr::{MatchVars, SeverityExpr, Value};
+        let expr = SeverityExpr::parse(
+            "if meta.section == \"conclusion\" then high \
+             else if not (meta.weight > 2) then low else medium",
+        )
+        .unwrap();
+        let mut vars = MatchVars::default();
+        assert_eq!(expr.evaluate(&vars), Severity::Low);
+        vars.metadata.insert("weight".to_string(), "3".to_string());
+        assert_eq!(expr.evaluate(&vars), Severity::Medium);
+        vars.metadata.insert("section".to_string(), "conclusion".to_string());
+        assert_eq!(expr.evaluate(&vars), Severity::High);
+        let expr = expr.with_default("meta.section", Value::Text("conclusion".to_string()));
+        assert_eq!(expr.evaluate(&MatchVars::default()), Severity::High);
+    }
+
+    #[test]
+    fn test_severity_expr_parse_errors_point_at_column() {
+        use severity_expr::SeverityExpr;
+        let column = |source: &str| SeverityExpr::parse(source).unwrap_err().column;
+        assert_eq!(column("if match_count > 1 then hgh else low"), 25);
+        assert_eq!(column("if match_cnt > 1 then high else low"), 4);
+        assert_eq!(column("if match_count = 1 then high else low"), 16);
+        assert_eq!(column("if meta.x == \"open then high else low"), 14);
+        assert_eq!(column("if (match_len < 3 then high else low"), 19);
+        assert_eq!(column("if match_len < 3 then high"), 27);
+        assert_eq!(column("high low"), 6);
+        let error = SeverityExpr::parse("if match_len < 3 then high").unwrap_err();
+        assert_eq!(error.to_string(), "column 27: expected 'else', found end of input");
+        assert!(SeverityExpr::parse(&"(".repeat(10_000)).is_err());
+    }
+
+    #[test]
+    fn test_severity_expr_fuzz_never_panics() {
+        use severity_expr::{MatchVars, SeverityExpr};
+        const PIECES: &[&str] = &[
+            "if", "then", "else", "and", "or", "not", "(", ")", "<", "<=", ">", ">=", "==",
+            "!=", "=", "!", "low", "medium", "high", "critical", "match_count",
+            "position_ratio", "context_len", "match_len", "meta.section", "meta.", "\"x\"",
+            "\"", "1", "-2.5", "1.2.3", "NaN", "é", "∞", "#",
+        ];
+        let mut rng = SplitMix64(235);
+        let mut parsed = 0;
+        for _ in 0..20_000 {
+            let len = (rng.next_f64() * 12.0) as usize;
+            let source: Vec<&str> = (0..len)
+                .map(|_| PIECES[(rng.next_f64() * PIECES.len() as f64) as usize % PIECES.len()])
+                .collect();
+            let separator = if rng.next_f64() < 0.5 { " " } else { "" };
+            let Ok(expr) = SeverityExpr::parse(&source.join(separator)) else {
+                continue;
+            };
+            parsed += 1;
+            let metadata = BTreeMap::from([("section".to_string(), "1e400".to_string())]);
+            for ratio in [f64::NAN, f64::INFINITY, -0.0, 0.5] {
+                let vars = MatchVars {
+                    position_ratio: ratio,
+                    metadata: metadata.clone(),
+                    ..MatchVars::default()
+                };
+                assert!(expr.outcomes().contains(&expr.evaluate(&vars)));
+            }
+        }
+        assert!(parsed > 0);
+    }
+
+    #[test]
+    fn test_snapshot_diff_reports_changes_by_category() {
+        let (mut healer, _clock) = healer_with_manual_clock();
+        let before = healer.snapshot();
+        assert!(before.diff(&before).is_empty());
+        assert_eq!(before.diff(&before).to_string(), "no differences\n");
+
+        healer.regularizer.axiom_weights.insert(Axiom::Safety, 2.0);
+        healer.regularizer.threshold = 0.8;
+        healer.auto_heal = false;
+        let chain = healer.correction_strategies.get_mut(&Axiom::Safety).unwrap();
+        let first = chain.remove(0);
+        chain.push(first);
+        chain.push(CorrectionStrategy::ExciseSentence);
+        healer.accepted_regressions.insert(Axiom::Fairness);
+        healer.regularizer.disable_axiom(Axiom::Transparency);
+        healer.strategy_stats.entry("recompute".to_string()).or_default().attempts += 2;
+        healer.monitor_and_heal_ctx(&Context::new("doc-1", "unsafe")).unwrap();
+        let after = healer.snapshot();
+
+        let diff = before.diff(&after);
+        assert!(!diff.is_empty());
+        assert!((diff.weights[&Axiom::Safety].delta().unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(diff.threshold, Some(Change { before: 0.5, after: 0.8 }));
+        assert_eq!(diff.auto_heal, Some(Change { before: true, after: false }));
+        assert_eq!(diff.learning_rate, None);
+        let safety = &diff.strategies[&Axiom::Safety];
+        assert_eq!(safety.added, vec!["excise_sentence"]);
+        assert!(safety.removed.is_empty());
+        assert_eq!(safety.reordered, before.strategies[&Axiom::Safety].len() > 1);
+        assert_eq!(diff.regressions_accepted, vec![Axiom::Fairness]);
+        assert_eq!(diff.contexts.values().map(Change::<usize>::delta).sum::<i64>(), 1);
+        assert_eq!(diff.axioms_disabled, vec![Axiom::Transparency]);
+        assert_eq!(diff.violations[&Axiom::Safety], Change { before: 0, after: 1 });
+        let recompute = BTreeMap::from([("attempts", Change { before: 0, after: 2 })]);
+        assert_eq!(diff.strategy_stats, BTreeMap::from([("recompute".to_string(), recompute)]));
+        let rendered = diff.to_string();
+        for heading in [
+            "weights:",
+            "settings:",
+            "strategies:",
+            "accepted regressions:",
+            "disabled axioms:",
+            "violations:",
+            "strategy counters:",
+        ] {
+            assert!(rendered.contains(heading), "{}", rendered);
+        }
+        assert!(rendered.contains("  Safety 1.500 → 2.000 (+0.500)"));
+        assert!(rendered.contains("  Safety 0 → 1 (+1)"), "{}", rendered);
+        assert!(rendered.contains("  recompute: attempts 0 → 2 (+2)"), "{}", rendered);
+        let json = diff.to_json();
+        assert!(json.contains("\"violations\":{\"Safety\":{\"before\":0,\"after\":1}}"));
+        assert!(after.diff(&before).axioms_enabled == vec![Axiom::Transparency]);
+        assert!(diff.to_json().starts_with("{\"schema\":\"aar.snapshot_diff.v3\""));
+        assert!(after.diff(&before).regressions_unaccepted == vec![Axiom::Fairness]);
+
+        let newer = before.encode().replacen("AARSNAP 4 ", "AARSNAP 5 ", 1);
+        let error = HealerSnapshot::diff_encoded(&newer, &after.encode()).unwrap_err();
+        assert!(matches!(error, SnapshotError::Schema(_)), "{}", error);
+        assert_eq!(HealerSnapshot::diff_encoded(&before.encode(), &after.encode()).unwrap(), diff);
+    }
+
+    #[test]
+    fn test_annotations_nest_and_strip_back_to_the_context() {
+        let healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        assert_eq!(
+            healer.detect_annotated("an unsafe op", AnnotationStyle::Bracketed),
+            "an ⟦safety:critical⟧unsafe⟦/⟧ op"
+        );
+        assert_eq!(
+            healer.detect_annotated("an unsafe op", AnnotationStyle::Markdown),
+            "an **unsafe**[^1] op\n\n[^1]: Safety, Critical, rule:unsafe"
+        );
+        let ansi = healer.detect_annotated("an unsafe op", AnnotationStyle::Ansi);
+        assert_eq!(ansi, "an \x1b[1;31munsafe\x1b[0m\x1b[2m[safety]\x1b[0m op");
+
+        let context = "keep ⟦this⟧ unsafe and inconsistent text";
+        let at = |axiom: Axiom, severity: Severity, span: Option<Range<usize>>| {
+            let mut metadata = BTreeMap::from([("rule".to_string(), "rule:test".to_string())]);
+            if let Some(span) = span {
+                metadata.insert("span".to_string(), format!("{}..{}", span.start, span.end));
+            }
+            Violation {
+                axiom,
+                severity,
+                context: context.to_string(),
                 timestamp: 0,
-            },
+                metadata,
+            }
+        };
+        let unsafe_at = context.find("unsafe").unwrap();
+        let text_end = context.len();
+        let violations = vec![
+            // Crosses the end of the span below, so it is split around it
+            at(Axiom::Consistency, Severity::High, Some(unsafe_at + 7..text_end)),
+            at(Axiom::Safety, Severity::Critical, Some(unsafe_at..unsafe_at + 23)),
+            at(Axiom::Fairness, Severity::Low, Some(unsafe_at..unsafe_at + 6)),
+            at(Axiom::Transparency, Severity::Medium, None),
         ];
-        let penalty = aar.calculate_penalty(&violations);
-        assert!(penalty > 0.0);
+        let annotated = annotate(context, &violations, AnnotationStyle::Bracketed);
+        assert_eq!(
+            annotated,
+            "keep ⟦⟦this⟧ ⟦safety:critical⟧⟦fairness:low⟧unsafe⟦/⟧ \
+             ⟦consistency:high⟧and inconsistent⟦/⟧⟦/⟧⟦consistency:high⟧ text⟦/⟧\n\
+             ⟦^transparency:medium rule:test⟧"
+        );
+        assert_eq!(AxiomaticSelfHealer::strip_annotations(&annotated), context);
+        let markdown = annotate(context, &violations, AnnotationStyle::Markdown);
+        assert!(markdown.starts_with("keep ⟦this⟧ **unsafe[^2] and inconsistent[^1] text**[^3]"));
+        let note = "[^4]: Transparency, Medium, rule:test (not located in the text)";
+        assert!(markdown.ends_with(note));
+    }
+
+    #[test]
+    fn test_tiering_samples_clean_traffic_to_estimate_misses() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.add_rule(
+            DetectionRule::new("subtle", Axiom::Fairness, Severity::Medium)
+                .with_tier(DetectionTier::Thorough),
+        );
+        let (rate, seed) = (0.3, 238);
+        regularizer.set_tiering_policy(TieringPolicy::EscalateOnHitPlusSample { rate, seed });
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+
+        let report = healer.monitor_and_heal_detailed("unsafe and subtle").unwrap();
+        assert_eq!(report.tiers, TiersRun { fast: true, thorough: true, sampled: false });
+        assert!(report.violations.iter().any(|v| v.axiom == Axiom::Fairness));
+
+        // The same generator decides which clean-looking contexts are sampled
+        let mut rng = SplitMix64(seed);
+        let mut expected = TieringStats {
+            fast_passes: 1,
+            thorough_passes: 1,
+            escalated_on_hit: 1,
+            ..Default::default()
+        };
+        for i in 0..200 {
+            let context = if i % 2 == 0 { "subtle bias" } else { "all fine" };
+            let report = healer.monitor_and_heal_detailed(context).unwrap();
+            let sampled = rng.next_f64() < rate;
+            assert_eq!(report.tiers, TiersRun { fast: true, thorough: sampled, sampled });
+            assert_eq!(report.violations.is_empty(), !(sampled && i % 2 == 0));
+            expected.fast_passes += 1;
+            expected.thorough_passes += sampled as u64;
+            expected.sampled += sampled as u64;
+            expected.fast_misses += (sampled && i % 2 == 0) as u64;
+            assert_eq!(healer.regularizer.tiering_statistics(), expected);
+        }
+        assert!(expected.sampled > 0 && expected.fast_misses > 0);
+        let miss_rate = healer.regularizer.tiering_statistics().miss_rate().unwrap();
+        assert!((miss_rate - expected.fast_misses as f64 / expected.sampled as f64).abs() < 1e-12);
+        let rendered = healer.metrics().render_prometheus();
+        let misses = format!("selfheal_fast_tier_misses_total {}\n", expected.fast_misses);
+        assert!(rendered.contains(&misses), "{}", rendered);
+
+        // Read-only scans never sample, so they don't disturb the estimate
+        healer.regularizer.scan("all fine");
+        assert_eq!(healer.regularizer.tiering_statistics(), expected);
+        healer.regularizer.set_tiering_policy(TieringPolicy::EscalateOnHit);
+        assert!(healer.regularizer.detect_violations("subtle bias").is_empty());
+    }
+
+    #[test]
+    fn test_registered_detectors_aggregate_and_dedupe() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        let loaded = ["always", "obviously"];
+        regularizer.register_detector(
+            KeywordDetector::new("loaded-terms", Axiom::Fairness, Severity::Medium, loaded)
+                .ignore_case(),
+        );
+        regularizer.register_detector(LengthDetector::new("too-short", 20));
+        // Same hits as the built-in rule, so they are reported once
+        let unsafe_words = ["unsafe"];
+        regularizer.register_detector(
+            KeywordDetector::new("unsafe-words", Axiom::Safety, Severity::Critical, unsafe_words),
+        );
+
+        let axioms = |regularizer: &AdaptiveAxiomaticRegularizer, context: &str| {
+            let violations = regularizer.detect_violations(context);
+            violations.iter().map(|v| v.axiom.clone()).collect::<BTreeSet<_>>()
+        };
+        use Axiom::{Completeness, Fairness, Safety};
+        let found = regularizer.detect_violations("Obviously unsafe");
+        let rules: Vec<_> = found.iter().map(|v| v.metadata["rule"].as_str()).collect();
+        assert_eq!(rules, vec!["rule:unsafe", "loaded-terms", "too-short"]);
+        assert_eq!(found[1].span(), Some(0..9));
+        assert_eq!(found[2].context, "Obviously unsafe");
+        let covered = BTreeSet::from([Axiom::Consistency, Completeness, Safety, Fairness]);
+        assert_eq!(regularizer.covered_axioms(), covered);
+
+        let generation = regularizer.rules_generation();
+        assert!(regularizer.disable_detector("rule:unsafe"));
+        assert!(!regularizer.disable_detector("no-such-detector"));
+        assert!(regularizer.rules_generation() > generation);
+        let found = regularizer.detect_violations("Obviously unsafe");
+        assert_eq!(found[2].metadata["rule"], "unsafe-words");
+        assert!(regularizer.remove_detector("unsafe-words"));
+        let expected = BTreeSet::from([Completeness, Fairness]);
+        assert_eq!(axioms(&regularizer, "Obviously unsafe"), expected);
+        assert!(regularizer.enable_detector("rule:unsafe"));
+        assert!(regularizer.remove_detector("too-short"));
+        assert_eq!(axioms(&regularizer, "unsafe"), BTreeSet::from([Safety]));
+
+        // Registering under an existing name replaces that detector
+        let replacement = ["unsafe"];
+        regularizer.register_detector(
+            KeywordDetector::new("loaded-terms", Axiom::Fairness, Severity::Low, replacement),
+        );
+        assert_eq!(regularizer.detector_names().count(), 3);
+        let found = regularizer.detect_violations("obviously unsafe");
+        assert_eq!(found[1].severity, Severity::Low);
+        regularizer.disable_axiom(Fairness);
+        assert_eq!(axioms(&regularizer, "obviously unsafe"), BTreeSet::from([Safety]));
+    }
+
+    #[test]
+    fn test_custom_axioms_are_weighted_healed_and_counted_by_name() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        let privacy = regularizer.register_axiom("Privacy Compliance", 2.0);
+        assert_eq!(regularizer.register_axiom("Privacy Compliance", 3.0), privacy);
+        assert_eq!(regularizer.custom_axioms().len(), 1);
+        assert_eq!(regularizer.weight(&privacy), Some(3.0));
+        assert!(regularizer.enabled_axioms().contains(&privacy));
+
+        regularizer.update_weights(privacy.clone(), 100.0);
+        assert_eq!(regularizer.weight(&privacy), Some(4.0));
+
+        let violation = |axiom: Axiom| Violation {
+            axiom,
+            severity: Severity::Low,
+            context: "external".to_string(),
+            timestamp: 0,
+            metadata: BTreeMap::new(),
+        };
+        let unregistered = Axiom::custom("Latency");
+        assert_eq!(regularizer.calculate_penalty(&[violation(privacy.clone())]), 4.0);
+        assert_eq!(
+            regularizer.calculate_penalty(&[violation(unregistered.clone())]),
+            AdaptiveAxiomaticRegularizer::UNREGISTERED_AXIOM_WEIGHT
+        );
+        regularizer.update_weights(unregistered.clone(), 1.0);
+        assert_eq!(regularizer.weight(&unregistered), None);
+        regularizer.add_rule(DetectionRule::new("ssn:", privacy.clone(), Severity::High));
+
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.set_strategies(privacy.clone(), vec![CorrectionStrategy::ExciseSentence]);
+        let report = healer.monitor_and_heal_detailed("Fine. Your ssn: 123. Done.").unwrap();
+        assert_eq!(report.context, "Fine. Done.");
+        healer.regularizer.record_violation(violation(Axiom::custom("Privacy Compliance")));
+        healer.regularizer.record_violation(violation(unregistered));
+        let stats = healer.get_statistics();
+        assert_eq!(stats.count_named("Privacy Compliance"), 2);
+        assert_eq!(stats.count_named("Latency"), 1);
+
+        let encoded = healer.snapshot().encode();
+        let decoded = HealerSnapshot::decode(&encoded).unwrap();
+        assert_eq!(decoded.weights.get(&privacy), Some(&4.0));
+        assert_eq!(decoded.strategies[&privacy], vec![CorrectionStrategy::ExciseSentence]);
+        assert_eq!("Custom(\"Privacy Compliance\")".parse::<Axiom>(), Ok(privacy));
+        assert!("Latency".parse::<Axiom>().is_err());
+
+        healer.set_strategies(Axiom::custom("Privacy Compliance"), Vec::new());
+        assert!(!healer.correction_strategies.contains_key(&Axiom::custom("Privacy Compliance")));
+    }
+
+    #[test]
+    fn test_saved_state_round_trips_and_rejects_unknown_axioms() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        let privacy = healer.regularizer.register_axiom("Privacy", 2.5);
+        healer.regularizer.update_weights(Axiom::Safety, 30.0);
+        healer.set_strategies(privacy.clone(), vec![CorrectionStrategy::ExciseSentence]);
+        for context in ["unsafe and inconsistent", "unsafe \"quoted\"\nline"] {
+            for violation in healer.regularizer.detect_violations(context) {
+                healer.regularizer.record_violation(violation);
+            }
+            clock.advance(Duration::from_millis(5));
+        }
+        healer.regularizer.set_axiom_learning_rate(Axiom::Safety, Some(0.2));
+        let policy = FeedbackPolicy { rate_decay: 0.5, ..FeedbackPolicy::default() };
+        healer.regularizer.set_feedback_policy(Some(policy));
+        let consistency = detected(Axiom::Consistency, Severity::Low, None);
+        healer.regularizer.record_outcome(&consistency, true);
+
+        let mut saved = Vec::new();
+        healer.save_state(&mut saved).unwrap();
+        let (mut restored, _) = healer_with_manual_clock();
+        restored.regularizer.set_feedback_policy(Some(policy));
+        assert_eq!(restored.load_state(saved.as_slice()).unwrap(), 3);
+        assert_eq!(restored.regularizer.weight(&Axiom::Safety), Some(1.8));
+        assert_eq!(restored.regularizer.weight(&privacy), Some(2.5));
+        assert_eq!(restored.regularizer.learning_rate_for(&Axiom::Safety), 0.2);
+        let scales = BTreeMap::from([(Axiom::Consistency, 0.5)]);
+        assert_eq!(restored.regularizer.rate_scales(), scales);
+        let (mut from_value, _) = healer_with_manual_clock();
+        from_value.regularizer.set_feedback_policy(Some(policy));
+        assert_eq!(from_value.restore_state(healer.state()).unwrap(), 3);
+        assert_eq!(from_value.regularizer.learning_rates(), healer.regularizer.learning_rates());
+        assert_eq!(from_value.regularizer.rate_scales(), scales);
+        let snapshot = HealerSnapshot::decode(&healer.snapshot().encode()).unwrap();
+        assert_eq!(snapshot, healer.snapshot());
+        assert_eq!(snapshot.learning_rates, BTreeMap::from([(Axiom::Safety, 0.2)]));
+        assert_eq!(snapshot.rate_scales, scales);
+        let chain = &restored.correction_strategies[&privacy];
+        assert_eq!(chain, &healer.correction_strategies[&privacy]);
+        let history = restored.regularizer.violations_in_range(0, u64::MAX);
+        assert_eq!(history, healer.regularizer.violations_in_range(0, u64::MAX));
+        assert!(history[0].timestamp < history[2].timestamp);
+
+        let mut resaved = Vec::new();
+        restored.save_state(&mut resaved).unwrap();
+        assert_eq!(resaved, saved);
+
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        let mut partial = Vec::new();
+        healer.regularizer.save_state(&mut partial).unwrap();
+        let stray = regularizer.register_axiom("Latency", 3.0);
+        assert_eq!(regularizer.load_state(partial.as_slice()).unwrap(), 3);
+        assert_eq!(regularizer.weight(&privacy), Some(2.5));
+        assert_eq!(regularizer.weight(&stray), None);
+        assert_eq!(regularizer.custom_axioms(), BTreeSet::from([privacy.clone()]));
+
+        let text = String::from_utf8(partial).unwrap();
+        let stale = text.replacen("\"Safety\":1.8", "\"Honesty\":1", 1);
+        let err = regularizer.load_state(stale.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "State header: Malformed artifact: unknown axiom 'Honesty'");
+        let stale = text.replace("\"axiom\":\"Safety\"", "\"axiom\":\"Honesty\"");
+        let err = regularizer.load_state(stale.as_bytes()).unwrap_err();
+        assert!(matches!(err, StateError::History { .. }), "{}", err);
+        assert!(err.to_string().ends_with("unknown axiom 'Honesty'"), "{}", err);
+        let truncated: String = text.lines().take(2).map(|l| format!("{}\n", l)).collect();
+        assert!(matches!(
+            regularizer.load_state(truncated.as_bytes()),
+            Err(StateError::Truncated { expected: 3, found: 1 })
+        ));
+        assert!(matches!(regularizer.load_state(&b""[..]), Err(StateError::Header(_))));
+        assert_eq!(regularizer.history_len(), 3);
+    }
+
+    #[test]
+    fn test_state_value_restores_like_a_saved_file_and_checks_its_version() {
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.regularizer.update_weights(Axiom::Safety, 30.0);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::ExciseSentence]);
+        for violation in healer.regularizer.detect_violations("unsafe and inconsistent") {
+            healer.regularizer.record_violation(violation);
+        }
+        let state = healer.state();
+        assert_eq!(state.version, schema::STATE.version);
+        assert!(state.healer.is_some());
+        assert_eq!(healer.regularizer.state().healer, None);
+
+        let (mut restored, _) = healer_with_manual_clock();
+        assert_eq!(restored.restore_state(state.clone()).unwrap(), 2);
+        assert_eq!(restored.regularizer.weight(&Axiom::Safety), Some(1.8));
+        assert_eq!(restored.state(), state);
+        let mut from_value = Vec::new();
+        let mut from_healer = Vec::new();
+        restored.save_state(&mut from_value).unwrap();
+        healer.save_state(&mut from_healer).unwrap();
+        assert_eq!(from_value, from_healer);
+
+        let future = RegularizerState { version: 3, ..state };
+        let err = restored.restore_state(future).unwrap_err();
+        assert!(err.to_string().contains("Unsupported schema 'aar.state.v3'"), "{}", err);
+
+        #[cfg(feature = "serde")]
+        {
+            let chain = vec![
+                CorrectionStrategy::Recompute,
+                CorrectionStrategy::ReplaceSentence { template: "[{axiom}]".to_string() },
+            ];
+            let json = serde_json::to_string(&chain).unwrap();
+            assert_eq!(serde_json::from_str::<Vec<CorrectionStrategy>>(&json).unwrap(), chain);
+            let custom = CorrectionStrategy::from_fn("noop", |context, _| Ok(context.to_string()));
+            assert!(serde_json::to_string(&custom).is_err());
+
+            let privacy = healer.regularizer.register_axiom("Privacy \"PII\"", 2.5);
+            let state = healer.state();
+            let json = serde_json::to_string(&state).unwrap();
+            assert!(json.contains(r#""Custom(\"Privacy \\\"PII\\\"\")":2.5"#), "{}", json);
+            let decoded: RegularizerState = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, state);
+            let (mut restored, _) = healer_with_manual_clock();
+            restored.restore_state(decoded).unwrap();
+            assert_eq!(restored.regularizer.weight(&privacy), Some(2.5));
+            let stale = json.replacen("\"Safety\"", "\"Honesty\"", 1);
+            let err = serde_json::from_str::<RegularizerState>(&stale).unwrap_err();
+            assert!(err.to_string().contains("unknown axiom 'Honesty'"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_retries_escalates_and_handles_exhaustion() {
+        let flaky = |failures: usize| {
+            let calls = Arc::new(AtomicU64::new(0));
+            let seen = Arc::clone(&calls);
+            let strategy = CorrectionStrategy::from_fn("flaky", move |context, _| {
+                match seen.fetch_add(1, Ordering::SeqCst) < failures as u64 {
+                    true => Err("model timed out".to_string()),
+                    false => Ok(context.replace("unsafe", "safe")),
+                }
+            });
+            (strategy, calls)
+        };
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        let (strategy, calls) = flaky(2);
+        healer.set_strategies(Axiom::Safety, vec![strategy, CorrectionStrategy::Rollback]);
+
+        healer.set_retry_policy(RetryPolicy { max_attempts: 3, ..RetryPolicy::default() });
+        let report = healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(report.context, "an safe reply");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let results: Vec<bool> =
+            report.attempts[0].attempts.iter().map(|a| a.result.is_ok()).collect();
+        assert_eq!(results, vec![false, false, true]);
+        assert_eq!(healer.strategy_statistics()["flaky"].attempts, 3);
+
+        // Without retries the chain escalates to rollback, unless escalation is off
+        let (strategy, _) = flaky(2);
+        healer.set_strategies(Axiom::Safety, vec![strategy, CorrectionStrategy::Rollback]);
+        healer.set_retry_policy(RetryPolicy::default());
+        let report = healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(report.attempts[0].chosen, Some("rollback"));
+        healer.set_retry_policy(RetryPolicy { escalate: false, ..RetryPolicy::default() });
+        let report = healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(report.outcome, HealOutcome::Unhealed);
+        assert_eq!(report.attempts[0].attempts.len(), 1);
+
+        // Consistency heals, Safety can't: keep the original or fail outright
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        healer.set_strategies(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+        let context = "inconsistent and unsafe";
+        healer.set_retry_policy(RetryPolicy::default());
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+        assert_eq!(report.context, "consistent and unsafe");
+
+        let on_exhausted = |on_exhausted| RetryPolicy { on_exhausted, ..RetryPolicy::default() };
+        healer.set_retry_policy(on_exhausted(ExhaustionAction::KeepOriginal));
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.outcome, HealOutcome::Unhealed);
+        assert_eq!(report.context, context);
+        let reasons: BTreeSet<String> =
+            report.unhealed.iter().map(|(_, reason)| format!("{:?}", reason)).collect();
+        assert_eq!(reasons, BTreeSet::from(["Reverted".into(), "StrategiesFailed".into()]));
+
+        healer.set_retry_policy(on_exhausted(ExhaustionAction::ReturnError));
+        match healer.monitor_and_heal_detailed(context) {
+            Err(HealError::UserInterventionRequired { axioms, partial }) => {
+                assert_eq!(axioms, vec![Axiom::Safety]);
+                assert_eq!(partial.context, "consistent and unsafe");
+            }
+            other => panic!("expected UserInterventionRequired, got {:?}", other),
+        }
+
+        healer.set_user_query(Some(Box::new(|context: &str, _: &Violation| {
+            Ok(context.replace("unsafe", "reviewed"))
+        })));
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.context, "consistent and reviewed");
+    }
+
+    #[test]
+    fn test_history_is_bounded_with_counters_matching_the_buffer() {
+        let regularizer = AdaptiveAxiomaticRegularizer::new().with_history_capacity(1_000);
+        let severities = [Severity::Low, Severity::Medium, Severity::High, Severity::Critical];
+        for i in 0..1_000_000u64 {
+            regularizer.record_violation(Violation {
+                axiom: Axiom::ALL[(i % 5) as usize].clone(),
+                severity: severities[(i % 4) as usize],
+                context: String::new(),
+                timestamp: i,
+                metadata: BTreeMap::new(),
+            });
+        }
+        assert_eq!(regularizer.history_len(), 1_000);
+        assert_eq!(regularizer.history_evicted(), 999_000);
+
+        let started = Instant::now();
+        for _ in 0..10_000 {
+            assert_eq!(regularizer.statistics().total, 1_000);
+        }
+        assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+
+        let mut walked = ViolationStatistics::default();
+        for violation in regularizer.violations_in_range(0, u64::MAX) {
+            walked.record(&violation);
+        }
+        let counted = regularizer.statistics();
+        assert_eq!(counted.by_axiom, walked.by_axiom);
+        assert_eq!(counted.by_severity, walked.by_severity);
+        assert_eq!(counted.by_axiom[&Axiom::Safety], 200);
+
+        let window = regularizer.violations_in_range(999_990, 999_995);
+        let timestamps: Vec<u64> = window.iter().map(|v| v.timestamp).collect();
+        assert_eq!(timestamps, (999_990..999_995).collect::<Vec<_>>());
+        assert_eq!(regularizer.statistics_since(999_900).total, 100);
+        assert_eq!(regularizer.statistics_since(0).total, 1_000);
+        let rates = regularizer.recent_axiom_rates(10);
+        assert_eq!(rates.values().sum::<f64>(), 1.0);
+        assert_eq!(rates[&Axiom::Safety], 0.2);
+
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.regularizer.disable_axiom(Axiom::Safety);
+        assert_eq!(healer.get_statistics().total, 800);
+        assert_eq!(healer.regularizer.statistics_since(999_900).total, 80);
+        let rates = healer.regularizer.recent_axiom_rates(8);
+        assert!(!rates.contains_key(&Axiom::Safety));
+        assert!(rates.values().all(|&rate| rate == 0.25), "{:?}", rates);
+        assert_eq!(healer.regularizer.drain_history().len(), 1_000);
+        assert_eq!(healer.regularizer.statistics().total, 0);
+    }
+
+    #[test]
+    fn test_feedback_policy_raises_weight_until_unhealed_violations_heal() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.add_rule(DetectionRule::new("risky", Axiom::Safety, Severity::Low));
+        regularizer.threshold = 3.0;
+        regularizer.set_feedback_policy(Some(FeedbackPolicy {
+            weight_step: 10.0,
+            min_threshold: 0.5,
+            ..FeedbackPolicy::default()
+        }));
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::ApplyDefault]);
+
+        let mut weights = Vec::new();
+        for _ in 0..5 {
+            let report = healer.monitor_and_heal_detailed("a risky plan").unwrap();
+            assert_eq!(report.outcome, HealOutcome::Recorded);
+            weights.push(healer.regularizer.weight(&Axiom::Safety).unwrap());
+        }
+        assert!(weights.windows(2).all(|w| w[1] > w[0]), "{:?}", weights);
+        assert!(weights[0] > 1.5);
+        let threshold = healer.regularizer.threshold();
+        assert!((0.5..3.0).contains(&threshold), "{}", threshold);
+        assert_eq!(healer.threshold_policy(), ThresholdPolicy::HealAbove(threshold));
+
+        let report = healer.monitor_and_heal_detailed("a risky plan").unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+
+        // Healing that keeps recurring never pushes the threshold under its floor
+        for _ in 0..100 {
+            healer.monitor_and_heal_detailed("a risky plan").unwrap();
+        }
+        assert!(healer.regularizer.threshold() >= 0.5);
+        assert_eq!(healer.regularizer.weight(&Axiom::Safety), Some(10.0));
+
+        healer.regularizer.set_severity_multipliers(SeverityMultipliers {
+            critical: 20.0,
+            ..SeverityMultipliers::default()
+        });
+        assert_eq!(healer.regularizer.severity_multiplier(Severity::Critical), 20.0);
+        assert_eq!(healer.regularizer.severity_multiplier(Severity::High), 4.0);
+    }
+
+    #[test]
+    fn test_detectors_run_by_priority_and_toggle_at_runtime() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        let unsafe_at = |tag: &'static str| {
+            move |context: &str| {
+                context
+                    .find("unsafe")
+                    .map(|start| {
+                        let mut violation =
+                            detected(Axiom::Safety, Severity::Critical, Some(start..start + 6));
+                        violation.metadata.insert("rule".to_string(), tag.to_string());
+                        violation
+                    })
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            }
+        };
+        regularizer.register_detector(FnDetector::new("low", [Axiom::Safety], unsafe_at("low")));
+        regularizer.register_detector(
+            FnDetector::new("high", [Axiom::Safety], unsafe_at("high")).with_priority(10),
+        );
+        let names: Vec<String> = regularizer.detector_names().collect();
+        assert_eq!(names, vec!["high", "rule:inconsistent", "rule:unsafe", "low"]);
+
+        let rule_of = |r: &AdaptiveAxiomaticRegularizer| {
+            let violations = r.detect_violations("unsafe");
+            assert_eq!(violations.len(), 1);
+            violations[0].metadata["rule"].clone()
+        };
+        assert_eq!(rule_of(&regularizer), "high");
+        assert!(regularizer.set_detector_priority("low", 20));
+        assert_eq!(rule_of(&regularizer), "low");
+        assert!(regularizer.set_detector_priority("rule:unsafe", 30));
+        assert_eq!(rule_of(&regularizer), "rule:unsafe");
+        assert!(!regularizer.set_detector_priority("missing", 1));
+
+        let generation = regularizer.rules_generation();
+        regularizer.disable_detector("rule:unsafe");
+        regularizer.disable_detector("low");
+        assert_eq!(rule_of(&regularizer), "high");
+        assert!(regularizer.rules_generation() > generation);
+        regularizer.enable_detector("low");
+        assert_eq!(rule_of(&regularizer), "low");
+
+        assert!(regularizer.remove_detector("low"));
+        regularizer.register_detector(FnDetector::new("low", [Axiom::Safety], unsafe_at("low")));
+        assert_eq!(rule_of(&regularizer), "high");
+    }
+
+    #[test]
+    fn test_defined_axiom_carries_weight_multipliers_and_strategies() {
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        let flat = SeverityMultipliers { low: 1.0, medium: 1.0, high: 1.0, critical: 3.0 };
+        let latency = healer.define_axiom(
+            AxiomDefinition::new("Latency", 2.0)
+                .with_severity_multipliers(flat)
+                .with_strategies(vec![CorrectionStrategy::ApplyDefault]),
+        );
+        healer.regularizer.add_rule(DetectionRule::new("slow", latency.clone(), Severity::High));
+
+        let violations = healer.regularizer.detect_violations("slow and unsafe");
+        let slow: Vec<Violation> =
+            violations.iter().filter(|v| v.axiom == latency).cloned().collect();
+        assert_eq!(healer.regularizer.calculate_penalty(&slow), 2.0);
+        assert_eq!(healer.regularizer.calculate_penalty(&violations), 2.0 + 1.5 * 8.0);
+        assert_eq!(healer.regularizer.severity_multiplier_for(&latency, Severity::Critical), 3.0);
+
+        let report = healer.monitor_and_heal_detailed("slow reply").unwrap();
+        assert_eq!(report.context, "slow reply [DEFAULT_APPLIED]");
+        assert_eq!(healer.get_statistics().count_named("Latency"), 1);
+
+        // Redefining replaces every part of the earlier definition
+        healer.define_axiom(AxiomDefinition::new("Latency", 4.0));
+        assert_eq!(healer.regularizer.custom_axioms().len(), 1);
+        assert_eq!(healer.regularizer.calculate_penalty(&slow), 16.0);
+        assert!(!healer.correction_strategies.contains_key(&latency));
+    }
+
+    #[test]
+    fn test_structured_fields_are_healed_in_place() {
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::ExciseSentence]);
+        let mut state = BTreeMap::from([
+            ("answer".to_string(), "Sure. Do the unsafe thing.".to_string()),
+            ("summary".to_string(), "All fine.".to_string()),
+        ]);
+
+        let reports = healer.monitor_and_heal_fields(&mut state).unwrap();
+        assert_eq!(state["answer"], "Sure.");
+        assert_eq!(state["summary"], "All fine.");
+        assert_eq!(reports.changed(), vec!["answer"]);
+        let fields: Vec<&str> =
+            reports.violations().map(|v| v.metadata["field"].as_str()).collect();
+        assert_eq!(fields, vec!["answer"]);
+
+        let mut steps = vec!["ok".to_string(), "unsafe step".to_string()];
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        healer.set_retry_policy(RetryPolicy {
+            on_exhausted: ExhaustionAction::ReturnError,
+            ..RetryPolicy::default()
+        });
+        let err = healer.monitor_and_heal_fields(&mut steps).unwrap_err();
+        assert!(matches!(&err, FieldHealError::Heal { path, .. } if path == "1"), "{}", err);
+        assert_eq!(steps, vec!["ok".to_string(), "unsafe step".to_string()]);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derived_healable_heals_annotated_fields() {
+        mod optional_text {
+            pub fn to_text(text: &Option<String>) -> String {
+                text.clone().unwrap_or_default()
+            }
+            pub fn from_text(text: String) -> Result<Option<String>, String> {
+                Ok(Some(text).filter(|text| !text.is_empty()))
+            }
+        }
+
+        #[derive(Debug, PartialEq, Healable)]
+        struct Reply {
+            #[healable]
+            answer: String,
+            #[healable(redact, with = "optional_text")]
+            notes: Option<String>,
+            #[healable(skip)]
+            retries: u32,
+            draft: String,
+        }
+
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::ExciseSentence]);
+        let mut reply = Reply {
+            answer: "Sure. Do the unsafe thing.".to_string(),
+            notes: Some("Checked. Also unsafe.".to_string()),
+            retries: 3,
+            draft: "unsafe draft".to_string(),
+        };
+        assert_eq!(
+            reply.fields(),
+            vec![
+                ("answer".to_string(), "Sure. Do the unsafe thing.".to_string()),
+                ("notes".to_string(), "Checked. Also unsafe.".to_string()),
+            ]
+        );
+
+        let reports = healer.monitor_and_heal_fields(&mut reply).unwrap();
+        assert_eq!(
+            reply,
+            Reply {
+                answer: "Sure.".to_string(),
+                notes: Some("Checked.".to_string()),
+                retries: 3,
+                draft: "unsafe draft".to_string(),
+            }
+        );
+        assert_eq!(reports.changed(), vec!["answer", "notes"]);
+        let (_, notes) = &reports.fields[1];
+        assert_eq!(notes.context, "<redacted 8 chars>");
+        assert_eq!(notes.violations[0].context, "<redacted 21 chars>");
+        assert_eq!(notes.violations[0].metadata["field"], "notes");
+        assert_eq!(reports.fields[0].1.violations[0].context, "Sure. Do the unsafe thing.");
+
+        assert!(reply.redacted("notes") && !reply.redacted("answer"));
+        assert_eq!(reply.set_field("retries", "4".to_string()), Err("no field 'retries'".into()));
+    }
+
+    #[test]
+    fn test_correction_handlers_fall_back_partially_heal_and_escalate() {
+        struct Redactor {
+            calls: Arc<AtomicU64>,
+            outcome: fn(&str, u64) -> Correction,
+        }
+        impl CorrectionHandler for Redactor {
+            fn name(&self) -> &'static str {
+                "redactor"
+            }
+            fn correct(&self, context: &str, _: &Violation) -> Correction {
+                (self.outcome)(context, self.calls.fetch_add(1, Ordering::SeqCst))
+            }
+            fn max_attempts(&self) -> Option<u32> {
+                Some(2)
+            }
+        }
+        let handler = |outcome: fn(&str, u64) -> Correction| {
+            let calls = Arc::new(AtomicU64::new(0));
+            let handler = Redactor { calls: Arc::clone(&calls), outcome };
+            (CorrectionStrategy::handler(handler), calls)
+        };
+        let (mut healer, _) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+
+        // Fails once, retried under its own limit, then hands a partial fix on
+        let (strategy, calls) = handler(|context, call| match call {
+            0 => Correction::Failed("busy".into()),
+            _ => Correction::Partial(context.replace("reply", "answer")),
+        });
+        assert_eq!(strategy.max_attempts(), Some(2));
+        healer.set_strategies(Axiom::Safety, vec![strategy, CorrectionStrategy::Rollback]);
+        let report = healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(report.context, "[ROLLED_BACK] an unsafe answer");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let results: Vec<bool> =
+            report.attempts[0].attempts.iter().map(|a| a.result.is_ok()).collect();
+        assert_eq!(results, vec![false, true, true]);
+        assert_eq!(report.attempts[0].chosen, Some("rollback"));
+        assert_eq!(report.outcome, HealOutcome::Healed);
+
+        // A partial fix on its own is kept but reported
+        let (strategy, _) = handler(|context, _| Correction::Partial(context.replace("un", "")));
+        healer.set_strategies(Axiom::Safety, vec![strategy]);
+        let report = healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(report.context, "an safe reply");
+        assert_eq!(report.attempts[0].selection, SelectionReason::PartiallyHealed);
+        assert_eq!(report.unhealed[0].1, UnhealedReason::PartiallyHealed);
+
+        // Escalation skips the rest of the chain
+        let (strategy, calls) = handler(|_, _| Correction::Escalate("needs legal".into()));
+        healer.set_strategies(Axiom::Safety, vec![strategy, CorrectionStrategy::Rollback]);
+        healer.set_retry_policy(RetryPolicy {
+            on_exhausted: ExhaustionAction::ReturnError,
+            ..RetryPolicy::default()
+        });
+        match healer.monitor_and_heal_detailed("an unsafe reply") {
+            Err(HealError::UserInterventionRequired { axioms, partial }) => {
+                assert_eq!(axioms, vec![Axiom::Safety]);
+                assert_eq!(partial.attempts[0].selection, SelectionReason::Escalated);
+                assert_eq!(partial.attempts[0].attempts.len(), 1);
+            }
+            other => panic!("expected UserInterventionRequired, got {:?}", other),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_observers_see_each_decision_and_metrics_count_violations() {
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+        impl HealObserver for Recorder {
+            fn on_violation_detected(&mut self, violation: &Violation) {
+                self.0.lock().unwrap().push(format!("detected {:?}", violation.axiom));
+            }
+            fn on_heal_attempted(&mut self, attempts: &ViolationAttempts) {
+                self.0.lock().unwrap().push(format!("attempted {:?}", attempts.chosen));
+            }
+            fn on_heal_failed(&mut self, violation: &Violation, reason: UnhealedReason) {
+                let line = format!("failed {:?} {:?}", violation.axiom, reason);
+                self.0.lock().unwrap().push(line);
+            }
+            fn on_weights_updated(&mut self, axiom: &Axiom, old: f64, new: f64) {
+                let line = format!("weight {} {} -> {}", axiom.name(), old, new);
+                self.0.lock().unwrap().push(line);
+            }
+        }
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.set_feedback_policy(Some(FeedbackPolicy::default()));
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        healer.add_observer(Recorder(Arc::clone(&seen)));
+
+        healer.monitor_and_heal_detailed("fine").unwrap();
+        assert!(seen.lock().unwrap().is_empty());
+        healer.monitor_and_heal_detailed("an unsafe reply").unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                "detected Safety",
+                "attempted None",
+                "failed Safety StrategiesFailed",
+                "weight Safety 1.5 -> 1.51",
+            ]
+        );
+
+        let regularizer = AdaptiveAxiomaticRegularizer::new();
+        let mut healer = AxiomaticSelfHealer::new(regularizer);
+        healer.define_axiom(AxiomDefinition::new("say \"hi\"", 2.0));
+        healer.regularizer.add_rule(DetectionRule::new(
+            "greet",
+            Axiom::custom("say \"hi\""),
+            Severity::Low,
+        ));
+        healer.monitor_and_heal_detailed("unsafe and unsafe").unwrap();
+        healer.monitor_and_heal_detailed("greet").unwrap();
+        healer.monitor_and_heal_detailed("fine").unwrap();
+        let metrics = healer.metrics();
+        assert_eq!(metrics.violations[&(Axiom::Safety, Severity::Critical)], 1);
+        assert_eq!(metrics.penalty.count(), 2);
+        let text = metrics.render_prometheus();
+        let counter = "selfheal_violations_total{axiom=\"Safety\",severity=\"Critical\"} 1";
+        assert!(text.contains(counter), "{}", text);
+        assert!(text.contains("axiom=\"say \\\"hi\\\"\",severity=\"Low\"} 1"), "{}", text);
+        assert!(text.contains("selfheal_penalty_count 2"));
+    }
+
+    #[test]
+    fn test_rollback_restores_last_validated_checkpoint_with_retention() {
+        let (mut healer, clock) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        let chain = vec![CorrectionStrategy::Rollback, CorrectionStrategy::ApplyDefault];
+        healer.set_strategies(Axiom::Safety, chain);
+        healer.set_checkpoints(Some(CheckpointStore::new(CheckpointRetention {
+            max_count: 2,
+            max_age: Some(Duration::from_secs(10)),
+            dedup: true,
+        })));
+
+        // Nothing validated yet, so rollback falls through to the next strategy
+        let report = healer.monitor_and_heal_detailed("unsafe at first").unwrap();
+        assert_eq!(report.context, "unsafe at first [DEFAULT_APPLIED]");
+
+        healer.monitor_and_heal_detailed("all good").unwrap();
+        clock.advance(Duration::from_secs(1));
+        healer.monitor_and_heal_detailed("all good").unwrap();
+        let store = healer.checkpoints().unwrap();
+        assert_eq!(store.len(), 2);
+        let good = store.latest_validated().unwrap().clone();
+        assert_eq!((good.context.as_str(), good.timestamp), ("all good", 1_001_000));
+
+        let report = healer.monitor_and_heal_detailed("now unsafe").unwrap();
+        assert_eq!(report.context, "all good");
+        assert_eq!(report.attempts[0].chosen, Some("rollback"));
+        let store = healer.checkpoints().unwrap();
+        let contexts: Vec<&str> = store.checkpoints().map(|c| c.context.as_str()).collect();
+        assert_eq!(contexts, vec!["all good", "now unsafe"]);
+        assert!(!store.checkpoints().nth(1).unwrap().validated);
+
+        // A pinned checkpoint outlives both count and age limits
+        assert!(healer.checkpoints_mut().unwrap().pin(good.id));
+        clock.advance(Duration::from_secs(60));
+        healer.monitor_and_heal_detailed("later").unwrap();
+        healer.monitor_and_heal_detailed("later still").unwrap();
+        healer.monitor_and_heal_detailed("latest").unwrap();
+        let store = healer.checkpoints().unwrap();
+        let contexts: Vec<&str> = store.checkpoints().map(|c| c.context.as_str()).collect();
+        assert_eq!(contexts, vec!["all good", "later still", "latest"]);
+        assert_eq!(store.latest_validated().unwrap().context, "latest");
+
+        let store = healer.checkpoints_mut().unwrap();
+        assert!(store.unpin(good.id));
+        assert_eq!(store.prune(clock.now_millis()), 1);
+        assert_eq!(store.remove(good.id), None);
+        assert!(!store.pin(good.id));
+
+        healer.set_checkpoints(None);
+        let report = healer.monitor_and_heal_detailed("unsafe again").unwrap();
+        assert_eq!(report.context, "[ROLLED_BACK] unsafe again");
+    }
+
+    #[test]
+    fn test_history_query_filters_and_statistics_report_window_rates() {
+        let clock = ManualClock::new(1_000_000);
+        let mut regularizer =
+            AdaptiveAxiomaticRegularizer::new().with_statistics_window(Duration::from_secs(120));
+        regularizer.set_clock(clock.clone());
+        let record = |axiom: Axiom, severity: Severity| {
+            let timestamp = regularizer.current_timestamp();
+            regularizer.record_violation(Violation {
+                axiom,
+                severity,
+                context: String::new(),
+                timestamp,
+                metadata: BTreeMap::new(),
+            });
+        };
+        record(Axiom::Safety, Severity::Low);
+        clock.advance(Duration::from_secs(300));
+        let recent = clock.now_millis();
+        record(Axiom::Safety, Severity::Critical);
+        record(Axiom::Safety, Severity::High);
+        record(Axiom::Fairness, Severity::High);
+
+        let query = regularizer.history_query().axiom(Axiom::Safety);
+        assert_eq!(query.count(), 3);
+        let serious = query.clone().severity_at_least(Severity::High).since(recent);
+        let severities: Vec<Severity> = serious.violations().iter().map(|v| v.severity).collect();
+        assert_eq!(severities, vec![Severity::Critical, Severity::High]);
+        assert_eq!(serious.clone().limit(1).violations()[0].severity, Severity::High);
+        assert_eq!(query.clone().until(recent).count(), 1);
+        assert_eq!(regularizer.history_query().statistics().by_axiom[&Axiom::Fairness], 1);
+
+        let window = regularizer.statistics().window.unwrap();
+        assert_eq!(window.violations, 3);
+        assert_eq!(window.per_minute, 1.5);
+        assert_eq!(window.per_minute_by_axiom[&Axiom::Fairness], 0.5);
+        let penalty = regularizer.calculate_penalty(&serious.violations())
+            + regularizer.calculate_penalty(&regularizer.history_query().limit(1).violations());
+        assert!((window.average_penalty - penalty / 3.0).abs() < 1e-9);
+
+        clock.advance(Duration::from_secs(121));
+        let statistics = regularizer.statistics();
+        assert_eq!(statistics.total, 4);
+        assert_eq!(statistics.window.unwrap().per_minute, 0.0);
+        regularizer.disable_axiom(Axiom::Safety);
+        assert_eq!(regularizer.history_query().count(), 1);
+    }
+
+    #[test]
+    fn test_policy_file_configures_healer_and_errors_name_the_key() {
+        let source = r#"
+learning_rate = 0.05
+threshold = 2.0
+heal = "at_or_above"
+
+[severity_multipliers]
+low = 0.5
+
+[axioms.Safety]
+weight = 2.5
+learning_rate = 0.2
+strategies = ["excise_sentence", "rollback"]  # tried in order
+
+[axioms."Privacy"]
+custom = true
+weight = 1.5
+strategies = ["replace_sentence:[{axiom} removed]"]
+
+[axioms.Privacy.severity_multipliers]
+critical = 12.0
+
+[[rules]]
+pattern = "ssn"
+axiom = "Privacy"
+severity = "critical"
+
+[[rules]]
+pattern = "maybe risky"
+axiom = "Safety"
+severity = "low"
+tier = "thorough"
+"#;
+        let dir = temp_dir("policy");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("policy.toml");
+        std::fs::write(&path, source).unwrap();
+        let healer = AxiomaticSelfHealer::from_policy_file(&path).unwrap();
+        let privacy = Axiom::custom("Privacy");
+        assert_eq!(healer.regularizer.learning_rate, 0.05);
+        assert_eq!(healer.threshold_policy(), ThresholdPolicy::HealAtOrAbove(2.0));
+        assert_eq!(healer.regularizer.weight(&Axiom::Safety), Some(2.5));
+        assert_eq!(healer.regularizer.learning_rate_for(&Axiom::Safety), 0.2);
+        assert_eq!(healer.regularizer.weight(&privacy), Some(1.5));
+        assert_eq!(healer.regularizer.severity_multiplier(Severity::Low), 0.5);
+        // The axiom's own table starts from the policy's multipliers
+        assert_eq!(healer.regularizer.severity_multiplier_for(&privacy, Severity::Low), 0.5);
+        assert_eq!(healer.regularizer.severity_multiplier_for(&privacy, Severity::Critical), 12.0);
+        assert_eq!(
+            healer.correction_strategies[&Axiom::Safety],
+            vec![CorrectionStrategy::ExciseSentence, CorrectionStrategy::Rollback]
+        );
+        let found = healer.regularizer.detect_violations("my ssn is here");
+        assert!(found.iter().any(|v| v.axiom == privacy && v.severity == Severity::Critical));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let error = |source: &str| policy::Policy::parse(source).unwrap_err();
+        let e = error("[axioms.Safety]\nweight = 40\n");
+        assert_eq!((e.key.as_str(), e.line), ("axioms.Safety.weight", 2));
+        assert_eq!(e.to_string(), "line 2: axioms.Safety.weight: must be between 0.1 and 10");
+        let e = error("[axioms.Safety]\nstrategies = [\"rollback\", \"pray\"]\n");
+        assert_eq!(e.message, "unknown strategy 'pray'");
+        let e = error("[[rules]]\npattern = \"x\"\naxiom = \"Safety\"\n");
+        assert_eq!((e.key.as_str(), e.line), ("rules[0].severity", 1));
+        let e = error("[[rules]]\npattern = \"x\"\naxiom = \"Honesty\"\nseverity = \"low\"\n");
+        assert_eq!((e.key.as_str(), e.line), ("rules[0].axiom", 3));
+        let e = error("[axioms.Saftey]\nweight = 2\n");
+        assert_eq!((e.key.as_str(), e.line), ("axioms.Saftey", 1));
+        assert_eq!(
+            e.message,
+            "unknown axiom (did you mean 'Safety'?); set `custom = true` to define a custom one"
+        );
+        let e = error("[axioms.Latency]\ncustom = false\n");
+        assert_eq!(e.message, "unknown axiom; set `custom = true` to define a custom one");
+        let e = error("[axioms.Safety]\ncustom = true\n");
+        assert_eq!((e.key.as_str(), e.line), ("axioms.Safety.custom", 2));
+        let e = error("[axioms.Privacy]\ncustom = \"yes\"\n");
+        assert_eq!(e.message, "expected true or false");
+        let e = error("[[rules]]\npattern = \"x\"\naxiom = \"fairnes\"\nseverity = \"low\"\n");
+        assert!(e.message.ends_with("declared (did you mean 'Fairness'?)"), "{}", e);
+        assert_eq!(error("treshold = 1").key, "treshold");
+        assert_eq!(error("heal = \"sometimes\"").key, "heal");
+        let e = error("threshold = 1\nthreshold = 2\n");
+        assert_eq!((e.line, e.message.as_str()), (2, "'threshold' is set twice"));
+        assert_eq!(error("[weights\n").message, "expected ']' at column 9");
+        assert_eq!(error("x = \"open\n").message, "unterminated string at column 5");
+    }
+
+    #[test]
+    fn test_escalation_rule_fires_and_enters_fallback_mode() {
+        use escalation::{ChannelAlertSink, EscalationAction, EscalationRule};
+        let (mut healer, clock) = healer_with_manual_clock();
+        healer.add_escalation_rule(
+            EscalationRule::new("consistency-burst", 3, Duration::from_secs(60))
+                .with_axiom(Axiom::Consistency)
+                .with_min_severity(Severity::High)
+                .with_cooldown(Duration::from_secs(300))
+                .with_action(EscalationAction::RaiseSeverity(Severity::Critical))
+                .with_action(EscalationAction::DisableAutoHeal)
+                .with_action(EscalationAction::Fallback("[withheld]".to_string())),
+        );
+        let (sender, receiver) = std::sync::mpsc::channel();
+        healer.add_alert_sink(ChannelAlertSink::new("ops", sender));
+        let (dead, _) = std::sync::mpsc::channel();
+        healer.add_alert_sink(ChannelAlertSink::new("gone", dead));
+
+        // The first violation leaves the window before the count is reached
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        clock.advance(Duration::from_secs(61));
+        healer.monitor_and_heal_detailed("inconsistent and unsafe").unwrap();
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        let report = healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        assert_eq!(report.context, "[withheld]");
+        let alert = receiver.try_recv().unwrap();
+        assert_eq!(alert.rule, "consistency-burst");
+        assert_eq!(alert.axiom, Axiom::Consistency);
+        assert_eq!(alert.severity, Severity::Critical);
+        assert_eq!(alert.violations.len(), 3);
+        assert!(alert.to_json().starts_with("{\"schema\":\"aar.alert.v1\""));
+        assert!(!healer.auto_heal);
+        assert_eq!(healer.escalation_fallback(), Some("[withheld]"));
+        assert!(healer.drain_events().iter().any(|event| matches!(
+            event,
+            HealerEvent::AlertFailed { sink, .. } if sink == "gone"
+        )));
+
+        // Cooling down: a fresh burst does not alert, but fallback still applies
+        for _ in 0..3 {
+            healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        }
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(healer.monitor_and_heal_detailed("fine").unwrap().context, "fine");
+
+        healer.clear_escalation();
+        assert!(healer.auto_heal);
+        assert_eq!(healer.escalation_fallback(), None);
+        let report = healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        assert_eq!(report.context, "inconsistent");
+        assert!(healer.remove_escalation_rule("consistency-burst"));
+        assert!(!healer.remove_escalation_rule("consistency-burst"));
+    }
+
+    #[test]
+    fn test_webhook_alert_sink_posts_json() {
+        use escalation::{AlertSink, EscalationAlert, WebhookAlertSink};
+        use std::net::TcpListener;
+        assert!(WebhookAlertSink::new("hook", "https://example.com/alerts").is_err());
+        assert!(WebhookAlertSink::new("hook", "http://:80/").is_err());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let mut statuses = ["200 OK", "503 Service Unavailable"].into_iter();
+            let mut requests = Vec::new();
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                let status = statuses.next().unwrap();
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let url = format!("http://127.0.0.1:{}/alerts", port);
+        let mut sink = WebhookAlertSink::new("hook", &url).unwrap();
+        let alert = EscalationAlert {
+            rule: "critical-safety".to_string(),
+            axiom: Axiom::Safety,
+            severity: Severity::Critical,
+            violations: Vec::new(),
+            actions: Vec::new(),
+            timestamp: 7,
+        };
+        sink.alert(&alert).unwrap();
+        let err = sink.alert(&alert).unwrap_err();
+        assert!(err.to_string().contains("503"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /alerts HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with(&alert.to_json()));
+    }
+
+    #[test]
+    fn test_feedback_policy_decays_per_axiom_rates_and_records_trajectory() {
+        let mut regularizer = AdaptiveAxiomaticRegularizer::new();
+        regularizer.set_axiom_learning_rate(Axiom::Safety, Some(0.1));
+        assert_eq!(regularizer.learning_rate_for(&Axiom::Safety), 0.1);
+        assert_eq!(regularizer.learning_rate_for(&Axiom::Consistency), 0.01);
+        regularizer.set_feedback_policy(Some(FeedbackPolicy {
+            recurrence_limit: 100,
+            rate_decay: 0.5,
+            min_rate_scale: 0.25,
+            penalty_smoothing: 0.5,
+            penalty_rise: 2.0,
+            trajectory_capacity: 3,
+            ..FeedbackPolicy::default()
+        }));
+        let violation = detected(Axiom::Safety, Severity::High, None);
+
+        regularizer.record_penalty(1.0);
+        for _ in 0..3 {
+            regularizer.record_outcome(&violation, true);
+        }
+        // Rose past twice the running average of 1
+        regularizer.record_penalty(3.0);
+        assert_eq!(regularizer.penalty_trend(), Some(2.0));
+        regularizer.record_outcome(&violation, true);
+
+        let steps: Vec<_> = regularizer.adaptation_trajectory().collect();
+        assert_eq!(steps.len(), 3);
+        let causes: Vec<_> = steps.iter().map(|step| step.cause).collect();
+        assert_eq!(
+            causes,
+            vec![AdaptationCause::Healed, AdaptationCause::Healed, AdaptationCause::PenaltyRising]
+        );
+        let deltas: Vec<f64> = steps
+            .iter()
+            .map(|step| step.weight.map(|w| w.after - w.before).unwrap())
+            .collect();
+        for (delta, expected) in deltas.iter().zip([-0.05, -0.025, 0.025]) {
+            assert!((delta - expected).abs() < 1e-9, "{:?}", deltas);
+        }
+        assert_eq!(steps[2].rate_scale, 0.25);
+        assert!(steps[2].threshold.after < steps[2].threshold.before);
+
+        regularizer.record_penalty(2.0);
+        regularizer.record_outcome(&violation, false);
+        let last = regularizer.adaptation_trajectory().last().unwrap();
+        assert_eq!(last.cause, AdaptationCause::Unhealed);
+
+        regularizer.set_feedback_policy(None);
+        assert_eq!(regularizer.adaptation_trajectory().count(), 0);
+    }
+
+    #[test]
+    fn test_dry_run_plans_without_mutating_and_replays_history() {
+        let (mut healer, _clock) = healer_with_manual_clock();
+        healer.set_strategies(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+        healer.set_strategies(
+            Axiom::Safety,
+            vec![CorrectionStrategy::QueryUser, CorrectionStrategy::ExciseSentence],
+        );
+        let context = "All is inconsistent. This is unsafe. Fine.";
+        let plan = healer.dry_run(context);
+        assert!(!plan.would_heal);
+        assert_eq!(plan.violations.len(), 2);
+        assert_eq!(plan.outcome, HealOutcome::Healed);
+        assert_eq!(plan.proposed, "All is consistent. Fine.");
+        assert_eq!(plan.predicted_penalty, 0.0);
+        assert_eq!(plan.penalty_reduction(), plan.penalty);
+        let safety = plan.corrections.iter().find(|c| c.violation.axiom == Axiom::Safety);
+        let safety = safety.unwrap();
+        assert_eq!(safety.chosen, Some("excise_sentence"));
+        assert_eq!(safety.attempts[0].result, Err("Not asked in a dry run".to_string()));
+        assert_eq!(healer.history_query().count(), 0);
+        assert!(healer.strategy_statistics().is_empty());
+
+        // The plan matches what healing does once it is allowed to run
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_user_query(Some(Box::new(|_, _| Err("declined".to_string()))));
+        let planned = healer.dry_run(context);
+        assert!(planned.would_heal);
+        let report = healer.monitor_and_heal_detailed(context).unwrap();
+        assert_eq!(report.context, planned.proposed);
+        assert_eq!(report.outcome, planned.outcome);
+
+        healer.monitor_and_heal_detailed("Nothing wrong here.").unwrap();
+        healer.monitor_and_heal_detailed("It is unsafe. Otherwise fine.").unwrap();
+        let history = healer.history_query().violations();
+        assert_eq!(history.len(), 3);
+
+        let mut candidate = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+        candidate.set_strategies(Axiom::Safety, Vec::new());
+        let current = healer.replay_history(&history);
+        let proposed = candidate.replay_history(&history);
+        assert_eq!(current.plans.len(), 2);
+        assert_eq!(current.healed(), 2);
+        assert_eq!(current.penalty_after(), 0.0);
+        assert_eq!(proposed.healed(), 1);
+        assert!(proposed.penalty_reduction() < current.penalty_reduction());
+        assert_eq!(proposed.chosen_strategies().get("excise_sentence"), None);
+        assert_eq!(current.chosen_strategies()["excise_sentence"], 2);
+
+        let replayed = healer.replay(["fine", "unsafe"]);
+        assert_eq!(replayed.violations(), 1);
+        assert_eq!(replayed.would_heal(), 1);
+    }
+
+    #[test]
+    fn test_dry_run_models_budgets_and_escalation_fallback() {
+        use escalation::{EscalationAction, EscalationRule};
+        let (mut healer, _clock) = healer_with_manual_clock();
+        healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+        healer.set_strategies(Axiom::Safety, vec![CorrectionStrategy::QueryUser]);
+        let hour = Duration::from_secs(3600);
+        healer.set_violation_budget(Axiom::Safety, 1, hour, BudgetAction::ReturnError);
+        assert!(healer.dry_run("unsafe").would_heal);
+        healer.monitor_and_heal_detailed("unsafe").unwrap();
+
+        let plan = healer.dry_run("It is unsafe. Otherwise fine.");
+        assert!(!plan.would_heal);
+        let err = healer.monitor_and_heal_detailed("It is unsafe. Otherwise fine.").unwrap_err();
+        assert_eq!(plan.blocked_by, Some(err.to_string()));
+        // The rest of the plan still describes healing as if it ran
+        assert_eq!(plan.outcome, HealOutcome::Unhealed);
+        healer.remove_violation_budget(&Axiom::Safety);
+        assert_eq!(healer.dry_run("unsafe").blocked_by, None);
+
+        healer.set_strategies(Axiom::Consistency, vec![CorrectionStrategy::Recompute]);
+        healer.add_escalation_rule(
+            EscalationRule::new("burst", 1, hour)
+                .with_axiom(Axiom::Consistency)
+                .with_action(EscalationAction::Fallback("[withheld]".to_string())),
+        );
+        // Only a fallback already active is modelled, not one this call would trigger
+        assert_eq!(healer.dry_run("inconsistent").proposed, "consistent");
+        healer.monitor_and_heal_detailed("inconsistent").unwrap();
+
+        let plan = healer.dry_run("inconsistent");
+        assert_eq!(plan.proposed, "[withheld]");
+        assert_eq!(plan.predicted_penalty, 0.0);
+        assert_eq!(plan.outcome, HealOutcome::Healed);
+        let report = healer.monitor_and_heal_detailed("inconsistent").unwrap();
+        assert_eq!(report.context, plan.proposed);
+        assert_eq!(healer.dry_run("fine").proposed, "fine");
+    }
+
+    #[cfg(feature = "tokio")]
+    mod async_monitor {
+        use crate::monitor::{
+            AsyncCorrectionHandler, AsyncDetector, AsyncMonitor, BoxFuture, MonitorEvent,
+        };
+        use super::*;
+        use tokio::sync::mpsc;
+
+        /// Flags every context as a Fairness violation
+        struct FlagAll;
+
+        impl AsyncDetector for FlagAll {
+            fn name(&self) -> &str {
+                "flag_all"
+            }
+
+            fn detect<'a>(&'a self, context: &'a str) -> BoxFuture<'a, Vec<Violation>> {
+                let violation = Violation {
+                    context: context.to_string(),
+                    ..detected(Axiom::Fairness, Severity::Low, None)
+                };
+                Box::pin(async move { vec![violation] })
+            }
+        }
+
+        /// Takes a while to correct, tracking how many corrections overlap
+        #[derive(Default)]
+        struct Slow {
+            in_flight: Arc<AtomicU64>,
+            peak: Arc<AtomicU64>,
+        }
+
+        impl AsyncCorrectionHandler for Slow {
+            fn name(&self) -> &'static str {
+                "slow"
+            }
+
+            fn correct<'a>(
+                &'a self,
+                context: &'a str,
+                _: &'a Violation,
+            ) -> BoxFuture<'a, Correction> {
+                Box::pin(async move {
+                    let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    self.peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Correction::Healed(format!("{} [checked]", context))
+                })
+            }
+        }
+
+        fn healer() -> AxiomaticSelfHealer {
+            let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+            healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+            healer
+        }
+
+        async fn run(
+            monitor: AsyncMonitor,
+            contexts: impl IntoIterator<Item = &str>,
+        ) -> Vec<MonitorEvent> {
+            let (input, receiver) = mpsc::channel(16);
+            let (mut output, task) = monitor.spawn(receiver);
+            for context in contexts {
+                input.send(context.to_string()).await.unwrap();
+            }
+            drop(input);
+            let mut events = Vec::new();
+            while let Some(event) = output.recv().await {
+                events.push(event);
+            }
+            task.await.unwrap();
+            events
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_monitor_bounds_corrections_in_flight() {
+            let slow = Slow::default();
+            let peak = Arc::clone(&slow.peak);
+            let monitor = AsyncMonitor::new(healer())
+                .with_parallelism(2)
+                .with_detector(FlagAll)
+                .with_handler(Axiom::Fairness, slow);
+
+            let contexts: Vec<String> = (0..8).map(|i| format!("reply {}", i)).collect();
+            let events = run(monitor, contexts.iter().map(String::as_str)).await;
+            let healed = events.iter().filter(|e| matches!(e, MonitorEvent::Healed { .. }));
+            assert_eq!(healed.count(), 8);
+            assert_eq!(peak.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test]
+        async fn test_monitor_reports_violations_then_healed_for_every_context() {
+            let monitor = AsyncMonitor::new(healer()).with_detector(FlagAll);
+            let events = run(monitor, ["unsafe one", "all fine", "unsafe two"]).await;
+
+            for seq in 0..3 {
+                let mine: Vec<&MonitorEvent> = events.iter().filter(|e| e.seq() == seq).collect();
+                let (last, violations) = mine.split_last().unwrap();
+                assert!(matches!(last, MonitorEvent::Healed { .. }), "{:?}", mine);
+                let axioms: Vec<&Axiom> = violations
+                    .iter()
+                    .map(|e| match e {
+                        MonitorEvent::Violation { violation, .. } => &violation.axiom,
+                        other => panic!("unexpected {:?}", other),
+                    })
+                    .collect();
+                let expected = match seq {
+                    1 => vec![&Axiom::Fairness],
+                    _ => vec![&Axiom::Fairness, &Axiom::Safety],
+                };
+                assert_eq!(axioms, expected);
+            }
+            let outcome = |seq| {
+                events.iter().find_map(|e| match e {
+                    MonitorEvent::Healed { seq: s, report } if *s == seq => Some(report.outcome),
+                    _ => None,
+                })
+            };
+            assert_eq!(outcome(0), Some(HealOutcome::Healed));
+            assert_eq!(outcome(1), Some(HealOutcome::Clean));
+        }
+
+        #[tokio::test]
+        async fn test_monitor_reports_a_panicking_heal_as_aborted_and_keeps_serving() {
+            let mut healer = healer();
+            let boom = CorrectionStrategy::from_fn("boom", |_, _| panic!("strategy blew up"));
+            healer.set_strategies(Axiom::Safety, vec![boom]);
+            let mut events = run(AsyncMonitor::new(healer), ["unsafe", "fine"]).await;
+            events.sort_by_key(MonitorEvent::seq);
+
+            assert_eq!(events.len(), 2, "{:?}", events);
+            let aborted = matches!(
+                &events[0],
+                MonitorEvent::Aborted { seq: 0, reason } if reason.contains("panic")
+            );
+            assert!(aborted, "{:?}", events[0]);
+            assert!(matches!(&events[1], MonitorEvent::Healed { seq: 1, .. }));
+        }
+
+        #[tokio::test]
+        async fn test_monitor_task_ends_when_input_closes_or_output_drops() {
+            let (input, receiver) = mpsc::channel::<String>(4);
+            let (output, task) = healer().spawn_monitor(receiver);
+            drop(input);
+            tokio::time::timeout(Duration::from_secs(5), task).await.unwrap().unwrap();
+            drop(output);
+
+            let (input, receiver) = mpsc::channel(4);
+            let (output, task) = healer().spawn_monitor(receiver);
+            drop(output);
+            input.send("unsafe".to_string()).await.unwrap();
+            tokio::time::timeout(Duration::from_secs(5), task).await.unwrap().unwrap();
+            assert!(input.is_closed());
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip_keeps_custom_strategies() {
+        let (mut healer, _) = healer_with_manual_clock();
+        let template = CorrectionStrategy::ReplaceSentence { template: "[{axiom}]".into() };
+        let mine = CorrectionStrategy::from_fn("mine", |text, _| Ok(text.replace("unsafe", "")));
+        healer.set_strategies(Axiom::Safety, vec![template.clone(), mine.clone()]);
+        healer.set_strategies(Axiom::Consistency, vec![mine.clone()]);
+        let configured = healer.correction_strategies.clone();
+
+        let mut saved = Vec::new();
+        healer.save_state(&mut saved).unwrap();
+        healer.load_state(saved.as_slice()).unwrap();
+        assert_eq!(healer.correction_strategies, configured);
+        let mut resaved = Vec::new();
+        healer.save_state(&mut resaved).unwrap();
+        assert_eq!(resaved, saved);
+        let row = |healer: &AxiomaticSelfHealer| {
+            let matrix = healer.coverage_matrix();
+            matrix.rows.into_iter().find(|row| row.axiom == Axiom::Safety).unwrap()
+        };
+        assert_eq!(row(&healer).strategies, ["replace_sentence", "mine"]);
+
+        // A chain whose built-in part changed is replaced by the snapshot's
+        let mut snapshot = healer.snapshot();
+        snapshot.strategies.insert(Axiom::Safety, vec![CorrectionStrategy::ExciseSentence]);
+        healer.restore(&snapshot);
+        let chain = &healer.correction_strategies;
+        assert_eq!(chain[&Axiom::Safety], [CorrectionStrategy::ExciseSentence]);
+        assert_eq!(chain[&Axiom::Consistency], [mine]);
+    }
+
+    #[test]
+    fn test_accepted_regression_axiom_keeps_heal() {
+        let mut healer = AxiomaticSelfHealer::new(regressing_regularizer());
+        healer.set_verify_after_heal(true);
+        healer.accept_regressions_for(Axiom::Transparency);
+
+        let report = healer.monitor_and_heal_detailed("Summary: TODO").unwrap();
+        assert_eq!(report.outcome, HealOutcome::Healed);
+        assert_eq!(report.context, "Summary: TODO [INTERPOLATED]");
+        assert_eq!(healer.strategy_statistics()["interpolate"].regressions, 0);
     }
 }
@@ -0,0 +1,52 @@
+//! Command-line tools for healer artifacts.
+//!
+//! `selfheal snapshot-diff [--json] <before> <after>` prints what changed between two
+//! snapshot files, such as the `healer.snapshot` a persisting healer writes. It exits
+//! with 0 when they match, 1 when they differ and 2 on errors, like `diff`.
+
+use meta_axiomatic_self_healer::HealerSnapshot;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: selfheal snapshot-diff [--json] <before> <after>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("snapshot-diff") => snapshot_diff(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn snapshot_diff(args: &[String]) -> ExitCode {
+    let json = args.iter().any(|arg| arg == "--json");
+    let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--json").collect();
+    let [before, after] = paths[..] else {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    };
+
+    let read = |path: &String| {
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+    };
+    let diff = read(before).and_then(|before| {
+        let after = read(after)?;
+        HealerSnapshot::diff_encoded(&before, &after).map_err(|e| e.to_string())
+    });
+    match diff {
+        Ok(diff) => {
+            if json {
+                println!("{}", diff.to_json());
+            } else {
+                print!("{}", diff);
+            }
+            ExitCode::from(u8::from(!diff.is_empty()))
+        }
+        Err(error) => {
+            eprintln!("selfheal: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
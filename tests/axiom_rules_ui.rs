@@ -0,0 +1,12 @@
+//! Compile-fail cases for `axiom_rules!`; regenerate the expected output with
+//! `TRYBUILD=overwrite cargo test --test axiom_rules_ui`, with and without `regex`
+
+#[test]
+fn axiom_rules_rejects_malformed_rules() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/axiom_rules/*.rs");
+    #[cfg(feature = "regex")]
+    cases.compile_fail("tests/ui/axiom_rules_regex/*.rs");
+    #[cfg(not(feature = "regex"))]
+    cases.compile_fail("tests/ui/axiom_rules_substring/*.rs");
+}
@@ -0,0 +1,19 @@
+//! The global healer after `init`; a binary of its own since it can only be set once
+//! per process
+
+use meta_axiomatic_self_healer::{
+    global, AdaptiveAxiomaticRegularizer, AxiomaticSelfHealer, HealOutcome,
+};
+
+#[test]
+fn init_once_then_heal_and_detect() {
+    let healer = || AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+    assert_eq!(global::init(healer()), Ok(()));
+    assert_eq!(global::init(healer()), Err(global::AlreadyInitialized));
+
+    assert!(global::try_healer().is_some());
+    assert_eq!(global::detect("unsafe").len(), 1);
+    let report = global::heal("inconsistent").unwrap();
+    assert_eq!(report.outcome, HealOutcome::Healed);
+    assert_eq!(global::healer().get_statistics().total, 1);
+}
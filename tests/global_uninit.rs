@@ -0,0 +1,14 @@
+//! The global healer before `init`; a binary of its own so nothing has set it
+
+use meta_axiomatic_self_healer::global;
+
+#[test]
+fn try_healer_is_none_before_init() {
+    assert!(global::try_healer().is_none());
+}
+
+#[test]
+#[should_panic(expected = "global::init must be called before global::healer")]
+fn healer_panics_before_init() {
+    global::healer();
+}
@@ -0,0 +1,9 @@
+//! Compile-fail cases for `#[derive(Healable)]`; regenerate the expected output with
+//! `TRYBUILD=overwrite cargo test --features derive --test healable_ui`
+#![cfg(feature = "derive")]
+
+#[test]
+fn derive_healable_rejects_unsupported_fields() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/healable/*.rs");
+}
@@ -0,0 +1,51 @@
+//! `selfheal snapshot-diff` against snapshot files written by a healer
+
+use meta_axiomatic_self_healer::{
+    AdaptiveAxiomaticRegularizer, AxiomaticSelfHealer, HealerSnapshot, ThresholdPolicy,
+};
+use std::process::Command;
+
+fn selfheal(args: &[&std::path::Path]) -> (Option<i32>, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_selfheal"))
+        .arg("snapshot-diff")
+        .args(args)
+        .output()
+        .unwrap();
+    let text = |bytes: Vec<u8>| String::from_utf8(bytes).unwrap();
+    (output.status.code(), text(output.stdout), text(output.stderr))
+}
+
+#[test]
+fn snapshot_diff_prints_changes_and_exits_like_diff() {
+    let dir = std::env::temp_dir().join(format!("aar-selfheal-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut healer = AxiomaticSelfHealer::new(AdaptiveAxiomaticRegularizer::new());
+    let (before, after) = (dir.join("before.snapshot"), dir.join("after.snapshot"));
+    std::fs::write(&before, healer.snapshot().encode()).unwrap();
+    healer.set_threshold_policy(ThresholdPolicy::HealOnAnyViolation);
+    std::fs::write(&after, healer.snapshot().encode()).unwrap();
+
+    let (code, stdout, _) = selfheal(&[&before, &before]);
+    assert_eq!((code, stdout.as_str()), (Some(0), "no differences\n"));
+
+    let (code, stdout, _) = selfheal(&[&before, &after]);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("settings:\n  policy "), "{}", stdout);
+
+    let json = Command::new(env!("CARGO_BIN_EXE_selfheal"))
+        .args(["snapshot-diff", "--json"])
+        .args([&before, &after])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8(json.stdout).unwrap().starts_with("{\"schema\":"));
+
+    let newer = dir.join("newer.snapshot");
+    let encoded = std::fs::read_to_string(&after).unwrap();
+    let header = format!("AARSNAP {} ", HealerSnapshot::FORMAT_VERSION);
+    std::fs::write(&newer, encoded.replacen(&header, "AARSNAP 99 ", 1)).unwrap();
+    let (code, stdout, stderr) = selfheal(&[&before, &newer]);
+    assert_eq!((code, stdout.as_str()), (Some(2), ""));
+    assert!(stderr.contains("aar.snapshot.v99"), "{}", stderr);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,9 @@
+meta_axiomatic_self_healer::axiom_rules! {
+    fn rules {
+        saftey critical: "leaked key",
+    }
+}
+
+fn main() {
+    rules();
+}
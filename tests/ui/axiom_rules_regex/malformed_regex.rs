@@ -0,0 +1,9 @@
+meta_axiomatic_self_healer::axiom_rules! {
+    fn rules {
+        safety critical: r"leak(ed?\s+key",
+    }
+}
+
+fn main() {
+    rules();
+}
@@ -0,0 +1,11 @@
+use meta_axiomatic_self_healer::Healable;
+
+mod count {}
+
+#[derive(Healable)]
+struct Reply {
+    #[healable(with = "count")]
+    retries: u32,
+}
+
+fn main() {}
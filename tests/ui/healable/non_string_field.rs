@@ -0,0 +1,11 @@
+use meta_axiomatic_self_healer::Healable;
+
+#[derive(Healable)]
+struct Reply {
+    #[healable]
+    answer: String,
+    #[healable]
+    retries: u32,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use meta_axiomatic_self_healer::Healable;
+
+#[derive(Healable)]
+struct Reply(#[healable] String);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use meta_axiomatic_self_healer::Healable;
+
+#[derive(Healable)]
+struct Reply {
+    #[healable(redacted)]
+    answer: String,
+}
+
+fn main() {}